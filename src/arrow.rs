@@ -0,0 +1,131 @@
+//! conversions between slices of `DateTime` / `Date` and arrow `Timestamp(unit, tz)` / `Date32` arrays.
+//!
+//! timestamps are the instant in `unit` since the epoch. reading a timestamp array with a
+//! fixed offset time zone such as `+08:00` gives datetimes in that offset, named zones
+//! such as `Asia/Shanghai` can not be resolved and give datetimes at utc
+use std::sync::Arc;
+use arrow_array::cast::AsArray;
+use arrow_array::types::{TimestampMicrosecondType, TimestampMillisecondType, TimestampNanosecondType, TimestampSecondType};
+use arrow_array::{Array, ArrayRef, Date32Array, PrimitiveArray, TimestampMicrosecondArray, TimestampMillisecondArray, TimestampNanosecondArray, TimestampSecondArray};
+use arrow_schema::{DataType, TimeUnit};
+use crate::{Date, DateTime};
+use crate::datetime::parse_offset;
+use crate::error::{Error, Field};
+
+/// `values` as a timestamp array in `unit`, tagged with the time zone `tz`.
+/// fails if a value does not fit, nanoseconds only reach the years 1677...2262
+pub fn to_timestamp_array(values: &[DateTime], unit: TimeUnit, tz: Option<&str>) -> Result<ArrayRef, Error> {
+    let micros = values.iter().map(|v| v.unix_micros());
+    let tz: Option<Arc<str>> = tz.map(Arc::from);
+    let array: ArrayRef = match unit {
+        TimeUnit::Second => Arc::new(
+            TimestampSecondArray::from(micros.map(|m| m.div_euclid(1_000_000)).collect::<Vec<_>>()).with_timezone_opt(tz),
+        ),
+        TimeUnit::Millisecond => Arc::new(
+            TimestampMillisecondArray::from(micros.map(|m| m.div_euclid(1_000)).collect::<Vec<_>>()).with_timezone_opt(tz),
+        ),
+        TimeUnit::Microsecond => Arc::new(TimestampMicrosecondArray::from(micros.collect::<Vec<_>>()).with_timezone_opt(tz)),
+        TimeUnit::Nanosecond => {
+            let nanos = micros
+                .map(|m| m.checked_mul(1_000))
+                .collect::<Option<Vec<_>>>()
+                .ok_or(Error::OutOfRange { field: Field::Timestamp })?;
+            Arc::new(TimestampNanosecondArray::from(nanos).with_timezone_opt(tz))
+        }
+    };
+    Ok(array)
+}
+
+/// the values of a timestamp array of any unit, `None` for nulls
+pub fn from_timestamp_array(array: &dyn Array) -> Result<Vec<Option<DateTime>>, Error> {
+    let (unit, tz) = match array.data_type() {
+        DataType::Timestamp(unit, tz) => (*unit, tz.clone()),
+        _ => return Err(Error::NotTimestampArray),
+    };
+    let offset = match tz.as_deref() {
+        Some(tz) if tz.starts_with('+') || tz.starts_with('-') => parse_offset(tz.as_bytes(), 0)?,
+        _ => 0,
+    };
+    let micros: Vec<Option<i128>> = match unit {
+        TimeUnit::Second => collect(array.as_primitive::<TimestampSecondType>(), |v| v as i128 * 1_000_000),
+        TimeUnit::Millisecond => collect(array.as_primitive::<TimestampMillisecondType>(), |v| v as i128 * 1_000),
+        TimeUnit::Microsecond => collect(array.as_primitive::<TimestampMicrosecondType>(), |v| v as i128),
+        TimeUnit::Nanosecond => collect(array.as_primitive::<TimestampNanosecondType>(), |v| (v as i128).div_euclid(1_000)),
+    };
+    micros
+        .into_iter()
+        .map(|m| match m {
+            None => Ok(None),
+            Some(m) => DateTime::from_unix_micros(m)
+                .map(|d| Some(d.to_offset_secs(offset)))
+                .ok_or(Error::OutOfRange { field: Field::Timestamp }),
+        })
+        .collect()
+}
+
+fn collect<T: arrow_array::ArrowPrimitiveType<Native = i64>>(
+    array: &PrimitiveArray<T>,
+    to_micros: impl Fn(i64) -> i128,
+) -> Vec<Option<i128>> {
+    array.iter().map(|v| v.map(&to_micros)).collect()
+}
+
+/// `values` as days since the epoch
+pub fn to_date32_array(values: &[Date]) -> Date32Array {
+    Date32Array::from(values.iter().map(|d| d.to_unix_day() as i32).collect::<Vec<_>>())
+}
+
+/// the values of a date array, `None` for nulls. fails outside of `Date::MIN...Date::MAX`
+pub fn from_date32_array(array: &Date32Array) -> Result<Vec<Option<Date>>, Error> {
+    array
+        .iter()
+        .map(|v| match v {
+            None => Ok(None),
+            Some(days) => Date::from_unix_day(days as i64)
+                .map(Some)
+                .ok_or(Error::OutOfRange { field: Field::Date }),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use arrow_array::{Array, Date32Array, TimestampMillisecondArray};
+    use arrow_schema::{DataType, TimeUnit};
+    use crate::{Date, DateTime};
+    use super::*;
+
+    #[test]
+    fn test_arrow_timestamp() {
+        let values = [
+            DateTime::from_str("2023-08-20T13:02:03.123456+08:00").unwrap(),
+            DateTime::from_str("1970-01-01T00:00:00Z").unwrap(),
+        ];
+        let array = to_timestamp_array(&values, TimeUnit::Millisecond, Some("+08:00")).unwrap();
+        assert_eq!(array.data_type(), &DataType::Timestamp(TimeUnit::Millisecond, Some("+08:00".into())));
+        let millis = array.as_any().downcast_ref::<TimestampMillisecondArray>().unwrap();
+        assert_eq!(millis.value(0), 1692507723123);
+        let back = from_timestamp_array(array.as_ref()).unwrap();
+        assert_eq!(back[0].unwrap().to_rfc3339(), "2023-08-20T13:02:03.123000+08:00");
+        assert_eq!(back[1].unwrap().to_rfc3339(), "1970-01-01T08:00:00.000000+08:00");
+
+        let array = to_timestamp_array(&values, TimeUnit::Nanosecond, None).unwrap();
+        assert_eq!(from_timestamp_array(array.as_ref()).unwrap()[0].unwrap().to_rfc3339(), "2023-08-20T05:02:03.123456Z");
+        assert!(to_timestamp_array(&[DateTime::MAX], TimeUnit::Nanosecond, None).is_err());
+
+        let with_null = TimestampMillisecondArray::from(vec![Some(0), None]);
+        assert_eq!(from_timestamp_array(&with_null).unwrap()[1], None);
+        assert!(from_timestamp_array(&Date32Array::from(vec![0])).is_err());
+    }
+
+    #[test]
+    fn test_arrow_date32() {
+        let values = [Date::from_str("2024-02-29").unwrap()];
+        let array = to_date32_array(&values);
+        assert_eq!(array.value(0), 19782);
+        assert_eq!(from_date32_array(&array).unwrap(), vec![Some(values[0])]);
+        assert_eq!(from_date32_array(&Date32Array::from(vec![Some(-1)])).unwrap()[0].unwrap().to_string(), "1969-12-31");
+        assert!(from_date32_array(&Date32Array::from(vec![Some(-719163)])).is_err());
+    }
+}
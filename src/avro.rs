@@ -0,0 +1,118 @@
+//! avro values of the `date`, `time-micros`, `timestamp-millis`, `timestamp-micros` and
+//! `local-timestamp-*` logical types.
+//!
+//! `Value::from` gives `date`, `time-micros` and the lossless `timestamp-micros`,
+//! the functions here pick another timestamp type. reading accepts any of them
+use apache_avro::types::Value;
+use crate::{Date, DateTime, Time};
+use crate::error::{Error, Field};
+
+/// `timestamp-millis`, the instant rounded down to milliseconds
+pub fn timestamp_millis(dt: &DateTime) -> Value {
+    Value::TimestampMillis(dt.unix_micros().div_euclid(1000))
+}
+
+/// `timestamp-micros`, the instant
+pub fn timestamp_micros(dt: &DateTime) -> Value {
+    Value::TimestampMicros(dt.unix_micros())
+}
+
+/// `local-timestamp-micros`, the wall-clock fields without the offset
+pub fn local_timestamp_micros(dt: &DateTime) -> Value {
+    Value::LocalTimestampMicros(dt.wall_micros())
+}
+
+impl From<DateTime> for Value {
+    fn from(v: DateTime) -> Self {
+        timestamp_micros(&v)
+    }
+}
+
+impl TryFrom<Value> for DateTime {
+    type Error = Error;
+
+    /// any timestamp type, at utc. nanoseconds are truncated
+    fn try_from(v: Value) -> Result<Self, Self::Error> {
+        let micros = match v {
+            Value::TimestampMillis(n) | Value::LocalTimestampMillis(n) => n as i128 * 1000,
+            Value::TimestampMicros(n) | Value::LocalTimestampMicros(n) => n as i128,
+            Value::TimestampNanos(n) | Value::LocalTimestampNanos(n) => n.div_euclid(1000) as i128,
+            _ => return Err(Error::InvalidAvroType),
+        };
+        DateTime::from_unix_micros(micros).ok_or(Error::OutOfRange { field: Field::Timestamp })
+    }
+}
+
+impl From<Date> for Value {
+    /// `date`, days since 1970-01-01
+    fn from(v: Date) -> Self {
+        Value::Date(v.to_unix_day() as i32)
+    }
+}
+
+impl TryFrom<Value> for Date {
+    type Error = Error;
+
+    fn try_from(v: Value) -> Result<Self, Self::Error> {
+        match v {
+            Value::Date(days) => DateTime::from_unix_micros(days as i128 * 86_400_000_000)
+                .map(Date::from)
+                .ok_or(Error::OutOfRange { field: Field::Day }),
+            _ => Err(Error::InvalidAvroType),
+        }
+    }
+}
+
+impl From<Time> for Value {
+    /// `time-micros`, microseconds since midnight
+    fn from(v: Time) -> Self {
+        let secs = v.hour as i64 * 3600 + v.min as i64 * 60 + v.sec as i64;
+        Value::TimeMicros(secs * 1_000_000 + v.micro as i64)
+    }
+}
+
+impl TryFrom<Value> for Time {
+    type Error = Error;
+
+    fn try_from(v: Value) -> Result<Self, Self::Error> {
+        let micros = match v {
+            Value::TimeMillis(n) => n as i64 * 1000,
+            Value::TimeMicros(n) => n,
+            _ => return Err(Error::InvalidAvroType),
+        };
+        if !(0..86_400_000_000).contains(&micros) {
+            return Err(Error::OutOfRange { field: Field::Micro });
+        }
+        Ok(Time::from(DateTime::from_wall_micros(micros, 0)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use apache_avro::types::Value;
+    use crate::{Date, DateTime, Time};
+
+    #[test]
+    fn test_avro_values() {
+        let d = DateTime::from_str("2023-08-20T13:02:03.123456+08:00").unwrap();
+        assert_eq!(Value::from(d), Value::TimestampMicros(1692507723123456));
+        assert_eq!(super::timestamp_millis(&d), Value::TimestampMillis(1692507723123));
+        assert_eq!(super::local_timestamp_micros(&d), Value::LocalTimestampMicros(1692536523123456));
+        assert_eq!(DateTime::try_from(Value::from(d)).unwrap().to_rfc3339(), "2023-08-20T05:02:03.123456Z");
+        let millis = DateTime::try_from(Value::TimestampMillis(-1)).unwrap();
+        assert_eq!(millis.to_string(), "1969-12-31 23:59:59.999000");
+        assert!(DateTime::try_from(Value::Long(0)).is_err());
+        assert!(DateTime::try_from(Value::TimestampMillis(i64::MAX)).is_err());
+
+        let date = Date::from_str("2023-08-20").unwrap();
+        assert_eq!(Value::from(date), Value::Date(19589));
+        assert_eq!(Date::try_from(Value::Date(19589)).unwrap(), date);
+        assert_eq!(Date::try_from(Value::Date(-1)).unwrap().to_string(), "1969-12-31");
+
+        let time = Time::from(d);
+        assert_eq!(Value::from(time), Value::TimeMicros(46923123456));
+        assert_eq!(Time::try_from(Value::TimeMicros(46923123456)).unwrap(), time);
+        assert!(Time::try_from(Value::TimeMillis(86_400_000)).is_err());
+    }
+}
@@ -0,0 +1,377 @@
+use alloc::vec::Vec;
+use crate::{days_in_month, Date, DateTime};
+use crate::datetime::{parse_offset, MIN_SECS};
+use crate::digits::{all_digits, pairs, ZEROS};
+use crate::error::{Error, Field};
+
+/// the error of the first byte at `positions` that is not `ok`, once a swar check failed
+#[cold]
+fn invalid(b: &[u8], field: Field, positions: &[usize], ok: fn(u8) -> bool) -> Error {
+    let pos = positions.iter().copied().find(|&i| !ok(b[i])).unwrap_or(positions[0]);
+    Error::InvalidChar { field, pos }
+}
+
+/// `YYYY-MM-DDTHH:MM:SS` read as two little-endian words with 8 digit checks each
+fn parse_head(b: &[u8]) -> Result<DateTime, Error> {
+    // "YYYY-MM-", "DDTHH:MM" and the overlapping ":MM:SS" of bytes 11...18
+    let w0 = u64::from_le_bytes(b[0..8].try_into().unwrap());
+    let w1 = u64::from_le_bytes(b[8..16].try_into().unwrap());
+    let w2 = u64::from_le_bytes(b[11..19].try_into().unwrap());
+    const M0: u64 = 0x00ff_ff00_ffff_ffff;
+    const M1: u64 = 0xffff_00ff_ff00_ffff;
+    const M2: u64 = 0xffff_00ff_ff00_ffff;
+    if w0 & !M0 != 0x2d00_002d_0000_0000 {
+        return Err(invalid(b, Field::DateSep, &[4, 7], |c| c == b'-'));
+    }
+    if w1 & 0x0000_ff00_0000_0000 != 0x0000_3a00_0000_0000 || w2 & !M2 != 0x0000_3a00_003a_0000 {
+        return Err(invalid(b, Field::TimeSep, &[13, 16], |c| c == b':'));
+    }
+    if !matches!(b[10], b'T' | b't' | b' ') {
+        return Err(Error::InvalidChar { field: Field::DateTimeSep, pos: 10 });
+    }
+    if !all_digits(w0, M0) || !all_digits(w1, M1) || !all_digits(w2, M2) {
+        let digits = [0, 1, 2, 3, 5, 6, 8, 9, 11, 12, 14, 15, 17, 18];
+        let date_ok = all_digits(w0, M0) && b[8..10].iter().all(u8::is_ascii_digit);
+        let field = if date_ok { Field::Time } else { Field::Date };
+        return Err(invalid(b, field, &digits, |c| c.is_ascii_digit()));
+    }
+    let p0 = pairs((w0 & M0) - (ZEROS & M0));
+    let p1 = pairs((w1 & M1) - (ZEROS & M1));
+    let p2 = pairs((w2 & M2) - (ZEROS & M2));
+    let year = (p0 & 0xff) as u16 * 100 + (p0 >> 16 & 0xff) as u16;
+    let mon = (p0 >> 40 & 0xff) as u8;
+    let day = (p1 & 0xff) as u8;
+    let hour = (p1 >> 24 & 0xff) as u8;
+    let min = (p1 >> 48 & 0xff) as u8;
+    let sec = (p2 >> 48 & 0xff) as u8;
+    let max_days = days_in_month(year, mon);
+    if max_days == 0 {
+        return Err(Error::OutOfRange { field: Field::Month });
+    }
+    if day < 1 || day > max_days {
+        return Err(Error::OutOfRange { field: Field::Day });
+    }
+    if hour > 23 {
+        return Err(Error::OutOfRange { field: Field::Hour });
+    }
+    if min > 59 {
+        return Err(Error::OutOfRange { field: Field::Minute });
+    }
+    if sec > 59 {
+        return Err(Error::OutOfRange { field: Field::Second });
+    }
+    Ok(DateTime { micro: 0, sec, min, hour, day, mon, year, offset: 0 })
+}
+
+fn parse_one(b: &[u8]) -> Result<DateTime, Error> {
+    if b.len() < 19 {
+        return Err(Error::TooShort);
+    }
+    let mut dt = parse_head(b)?;
+    let mut pos = 19;
+    if matches!(b.get(pos), Some(b'.') | Some(b',')) {
+        pos += 1;
+        let digits = b[pos..].iter().take_while(|c| c.is_ascii_digit()).count();
+        if digits == 0 {
+            return Err(Error::SecondFractionMissing);
+        }
+        if digits > 9 {
+            return Err(Error::SecondFractionTooLong);
+        }
+        let mut micro = 0;
+        for c in &b[pos..pos + digits.min(6)] {
+            micro = micro * 10 + (c - b'0') as u32;
+        }
+        dt.micro = micro * 10u32.pow(6 - digits.min(6) as u32);
+        pos += digits;
+    }
+    dt.offset = parse_offset(b, pos)?;
+    Ok(dt)
+}
+
+/// parse many RFC 3339 timestamps as `DateTime::parse_rfc3339`, the layout of
+/// `DateTime::from_str` with an optional fraction of up to 9 digits and an optional offset,
+/// for log ingestion.
+///
+/// the date and time digits and separators of each input are checked and converted
+/// 8 bytes at a time with swar arithmetic
+pub fn parse_rfc3339_batch<S: AsRef<[u8]>>(inputs: &[S]) -> Vec<Result<DateTime, Error>> {
+    inputs.iter().map(|s| parse_one(s.as_ref())).collect()
+}
+
+/// The unit of the integers given to `DateTime::from_timestamps`
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum TimestampUnit {
+    Seconds,
+    Millis,
+    Micros,
+    Nanos,
+}
+
+impl TimestampUnit {
+    /// the microseconds of one unit, 0 for nanoseconds that are smaller
+    fn micros(self) -> i64 {
+        match self {
+            TimestampUnit::Seconds => 1_000_000,
+            TimestampUnit::Millis => 1_000,
+            TimestampUnit::Micros => 1,
+            TimestampUnit::Nanos => 0,
+        }
+    }
+}
+
+/// What a conversion into a coarser unit does with the digits that do not fit,
+/// e.g. the nanoseconds of a source given to the microseconds of `DateTime`, or the
+/// microseconds of a `DateTime` given to the milliseconds of bson or javascript
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum PrecisionLoss {
+    /// drop them, rounding toward the past so the order of the values is kept
+    #[default]
+    Truncate,
+    /// round to the nearest unit, halves to the even one
+    RoundHalfEven,
+    /// fail with `Error::InexactConversion` unless they are all zero
+    Reject,
+}
+
+impl PrecisionLoss {
+    /// `n / d` for a positive `d`, the remainder handled as `self` says
+    pub(crate) fn div(self, n: i128, d: i128) -> Result<i128, Error> {
+        let (q, r) = (n.div_euclid(d), n.rem_euclid(d));
+        match self {
+            PrecisionLoss::Truncate => Ok(q),
+            PrecisionLoss::RoundHalfEven if r * 2 > d || (r * 2 == d && q % 2 != 0) => Ok(q + 1),
+            PrecisionLoss::RoundHalfEven => Ok(q),
+            PrecisionLoss::Reject if r != 0 => Err(Error::InexactConversion),
+            PrecisionLoss::Reject => Ok(q),
+        }
+    }
+}
+
+impl DateTime {
+    /// the instant as a count of `unit` since the epoch, microseconds past a coarser unit
+    /// handled as `policy` says. fails for nanoseconds outside of the years 1677...2262
+    pub fn to_timestamp(&self, unit: TimestampUnit, policy: PrecisionLoss) -> Result<i64, Error> {
+        let micros = self.unix_micros();
+        match unit {
+            TimestampUnit::Nanos => micros.checked_mul(1000).ok_or(Error::OutOfRange { field: Field::Timestamp }),
+            _ => Ok(policy.div(micros as i128, unit.micros() as i128)? as i64),
+        }
+    }
+
+    /// the utc datetime `v` units of `unit` after the epoch, nanoseconds handled as `policy`
+    /// says. fails outside of the years 0001...9999
+    pub fn from_timestamp_with(v: i64, unit: TimestampUnit, policy: PrecisionLoss) -> Result<Self, Error> {
+        let micros = match unit {
+            TimestampUnit::Nanos => policy.div(v as i128, 1000)?,
+            _ => v as i128 * unit.micros() as i128,
+        };
+        Self::from_unix_micros(micros).ok_or(Error::OutOfRange { field: Field::Timestamp })
+    }
+
+    /// parse one RFC 3339 timestamp as `parse_rfc3339_batch` does, the layout of `from_str`
+    /// with a `T`, `t` or space separator always followed by the time. the most common layout
+    /// never goes through the pattern engine of `parse_from_str`, the digits are checked and
+    /// converted with the same swar arithmetic as the batch
+    pub fn parse_rfc3339(s: &str) -> Result<DateTime, Error> {
+        parse_one(s.as_bytes())
+    }
+
+    /// the utc datetimes of timestamps in `unit` since the epoch, for time-series loaders.
+    /// the calendar date is only computed again when the day changes, so sorted or mostly
+    /// sorted series skip the epoch-day decomposition. nanoseconds are rounded toward the past,
+    /// fails if a value is outside of the years 0001...9999
+    pub fn from_timestamps(values: &[i64], unit: TimestampUnit) -> Result<Vec<DateTime>, Error> {
+        Self::from_timestamps_with(values, unit, PrecisionLoss::Truncate)
+    }
+
+    /// as `from_timestamps`, nanoseconds handled as `policy` says
+    pub fn from_timestamps_with(values: &[i64], unit: TimestampUnit, policy: PrecisionLoss) -> Result<Vec<DateTime>, Error> {
+        const MICROS_PER_DAY: i64 = 86_400_000_000;
+        let min_day = MIN_SECS.div_euclid(86400);
+        let max_day = Date::MAX.to_unix_day();
+        let mut out = Vec::with_capacity(values.len());
+        let mut last: Option<(i64, Date)> = None;
+        for &v in values {
+            let micros = match unit {
+                TimestampUnit::Seconds => v.checked_mul(1_000_000),
+                TimestampUnit::Millis => v.checked_mul(1_000),
+                TimestampUnit::Micros => Some(v),
+                TimestampUnit::Nanos => Some(policy.div(v as i128, 1_000)? as i64),
+            }
+            .ok_or(Error::OutOfRange { field: Field::Timestamp })?;
+            let day = micros.div_euclid(MICROS_PER_DAY);
+            let date = match last {
+                Some((last_day, date)) if last_day == day => date,
+                _ => {
+                    if day < min_day || day > max_day {
+                        return Err(Error::OutOfRange { field: Field::Timestamp });
+                    }
+                    let date = Date::from_days_since_epoch(day);
+                    last = Some((day, date));
+                    date
+                }
+            };
+            let micros_of_day = micros.rem_euclid(MICROS_PER_DAY);
+            let secs_of_day = (micros_of_day / 1_000_000) as u32;
+            out.push(DateTime {
+                micro: (micros_of_day % 1_000_000) as u32,
+                sec: (secs_of_day % 60) as u8,
+                min: (secs_of_day % 3600 / 60) as u8,
+                hour: (secs_of_day / 3600) as u8,
+                day: date.day,
+                mon: date.mon,
+                year: date.year,
+                offset: 0,
+            });
+        }
+        Ok(out)
+    }
+}
+
+/// `parse_rfc3339_batch` on the rayon thread pool, the results in the order of the inputs
+#[cfg(feature = "rayon")]
+pub fn par_parse<S: AsRef<[u8]> + Sync>(inputs: &[S]) -> Vec<Result<DateTime, Error>> {
+    use rayon::prelude::*;
+    inputs.par_iter().with_min_len(PAR_MIN_LEN).map(|s| parse_one(s.as_ref())).collect()
+}
+
+/// `to_rfc3339` of each value on the rayon thread pool, in the order of the values
+#[cfg(feature = "rayon")]
+pub fn par_format(values: &[DateTime]) -> Vec<alloc::string::String> {
+    use rayon::prelude::*;
+    values.par_iter().with_min_len(PAR_MIN_LEN).map(DateTime::to_rfc3339).collect()
+}
+
+/// the fewest items a rayon job handles, a single one takes tens of nanoseconds
+#[cfg(feature = "rayon")]
+const PAR_MIN_LEN: usize = 4096;
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use crate::{parse_rfc3339_batch, DateTime};
+
+    #[test]
+    fn test_parse_rfc3339() {
+        let v = "2023-08-20T13:02:03.123456+08:00";
+        assert_eq!(DateTime::parse_rfc3339(v).unwrap(), DateTime::from_str(v).unwrap());
+        assert_eq!(DateTime::parse_rfc3339(v).unwrap().to_rfc3339(), v);
+        assert_eq!(DateTime::parse_rfc3339("2023-08-20 13:02:03Z").unwrap().sec, 3);
+        assert!(DateTime::parse_rfc3339("2023-08-20").is_err());
+        assert!(DateTime::parse_rfc3339("2023-08-20T13:02:03 UTC").is_err());
+    }
+
+    #[test]
+    fn test_parse_rfc3339_batch() {
+        let inputs = [
+            "2023-08-20T13:02:03.123456+08:00",
+            "2023-08-20 13:02:03Z",
+            "1999-12-31t23:59:59,5-05:30",
+            "2024-02-29T00:00:00.1Z",
+            "0001-01-01T00:00:00",
+        ];
+        let parsed = parse_rfc3339_batch(&inputs);
+        for (s, d) in inputs.iter().zip(&parsed).take(4) {
+            assert_eq!(d.as_ref().unwrap(), &DateTime::from_str(s).unwrap());
+        }
+        assert_eq!(parsed[4].as_ref().unwrap().to_rfc3339(), "0001-01-01T00:00:00.000000Z");
+        let bad = [
+            "2023-08-20T13:02:0",
+            "2023/08/20T13:02:03Z",
+            "2023-08-20T13-02:03Z",
+            "2023-08-20X13:02:03Z",
+            "2a23-08-20T13:02:03Z",
+            "2023-08-20T13:0:033Z",
+            "2023-02-29T13:02:03Z",
+            "2023-13-20T13:02:03Z",
+            "2023-08-20T24:02:03Z",
+            "2023-08-20T13:60:03Z",
+            "2023-08-20T13:02:60Z",
+            "2023-08-20T13:02:03.Z",
+            "2023-08-20T13:02:03.1234567890Z",
+            "2023-08-20T13:02:03+0800",
+        ];
+        for r in parse_rfc3339_batch(&bad) {
+            assert!(r.is_err());
+        }
+        let bytes: [&[u8]; 1] = [b"2023-08-20T13:02:03Z"];
+        assert!(parse_rfc3339_batch(&bytes)[0].is_ok());
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn test_par_parse_format() {
+        let d = DateTime::from_str("2023-08-20T13:02:03.123456+08:00").unwrap();
+        let values: Vec<DateTime> = (0..10_000).map(|i| d + std::time::Duration::from_secs(i)).collect();
+        let text = crate::par_format(&values);
+        assert_eq!(text[1], "2023-08-20T13:02:04.123456+08:00");
+        let parsed = crate::par_parse(&text);
+        assert_eq!(parsed.len(), values.len());
+        for (p, v) in parsed.iter().zip(&values) {
+            assert_eq!(p.as_ref().unwrap(), v);
+        }
+        assert!(crate::par_parse(&["2023-02-30T00:00:00Z"])[0].is_err());
+    }
+
+    #[test]
+    fn test_from_timestamps() {
+        use crate::TimestampUnit;
+        let secs = [1692536523, 1692536524, 1692576000, -1, 0];
+        let d = DateTime::from_timestamps(&secs, TimestampUnit::Seconds).unwrap();
+        assert_eq!(d[0].to_rfc3339(), "2023-08-20T13:02:03.000000Z");
+        assert_eq!(d[1].to_rfc3339(), "2023-08-20T13:02:04.000000Z");
+        assert_eq!(d[2].to_rfc3339(), "2023-08-21T00:00:00.000000Z");
+        assert_eq!(d[3].to_rfc3339(), "1969-12-31T23:59:59.000000Z");
+        assert_eq!(d[4], DateTime::UNIX_EPOCH);
+        let millis = DateTime::from_timestamps(&[1692536523123], TimestampUnit::Millis).unwrap();
+        assert_eq!(millis[0].micro, 123000);
+        let micros = DateTime::from_timestamps(&[1692536523123456], TimestampUnit::Micros).unwrap();
+        assert_eq!(micros[0].micro, 123456);
+        let nanos = DateTime::from_timestamps(&[-1], TimestampUnit::Nanos).unwrap();
+        assert_eq!(nanos[0].to_string(), "1969-12-31 23:59:59.999999");
+        for (v, unit) in [(i64::MAX, TimestampUnit::Micros), (i64::MAX, TimestampUnit::Seconds), (-62135596801, TimestampUnit::Seconds)] {
+            assert!(DateTime::from_timestamps(&[v], unit).is_err());
+        }
+        let first = DateTime::from_timestamps(&[-62135596800], TimestampUnit::Seconds).unwrap();
+        assert_eq!(first[0].to_string(), "0001-01-01 00:00:00.000000");
+        for (i, d) in DateTime::from_timestamps(&secs, TimestampUnit::Seconds).unwrap().iter().enumerate() {
+            assert_eq!(d.unix_micros(), secs[i] * 1_000_000);
+        }
+    }
+
+    #[test]
+    fn test_precision_loss() {
+        use crate::{PrecisionLoss, TimestampUnit};
+        let d = DateTime::from_str("2023-08-20T13:02:03.123500Z").unwrap();
+        assert_eq!(d.to_timestamp(TimestampUnit::Millis, PrecisionLoss::Truncate).unwrap(), 1692536523123);
+        assert_eq!(d.to_timestamp(TimestampUnit::Millis, PrecisionLoss::RoundHalfEven).unwrap(), 1692536523124);
+        assert_eq!(
+            d.to_timestamp(TimestampUnit::Millis, PrecisionLoss::Reject).unwrap_err().to_string(),
+            "Rbatis Error: InexactConversion"
+        );
+        assert_eq!(d.to_timestamp(TimestampUnit::Micros, PrecisionLoss::Reject).unwrap(), 1692536523123500);
+        assert_eq!(d.to_timestamp(TimestampUnit::Nanos, PrecisionLoss::Reject).unwrap(), 1692536523123500000);
+        assert_eq!(d.to_timestamp(TimestampUnit::Seconds, PrecisionLoss::RoundHalfEven).unwrap(), 1692536523);
+        assert!(DateTime::MAX.to_timestamp(TimestampUnit::Nanos, PrecisionLoss::Truncate).is_err());
+        // halves to even, below the epoch as well
+        let half = DateTime::from_str("2023-08-20T13:02:03.122500Z").unwrap();
+        assert_eq!(half.to_timestamp(TimestampUnit::Millis, PrecisionLoss::RoundHalfEven).unwrap(), 1692536523122);
+        let before = DateTime::from_str("1969-12-31T23:59:59.999500Z").unwrap();
+        assert_eq!(before.to_timestamp(TimestampUnit::Millis, PrecisionLoss::Truncate).unwrap(), -1);
+        assert_eq!(before.to_timestamp(TimestampUnit::Millis, PrecisionLoss::RoundHalfEven).unwrap(), 0);
+
+        let from = |v, p| DateTime::from_timestamp_with(v, TimestampUnit::Nanos, p).map(|d| d.to_string());
+        assert_eq!(from(1692536523123456789, PrecisionLoss::Truncate).unwrap(), "2023-08-20 13:02:03.123456");
+        assert_eq!(from(1692536523123456789, PrecisionLoss::RoundHalfEven).unwrap(), "2023-08-20 13:02:03.123457");
+        assert_eq!(from(1692536523123456500, PrecisionLoss::RoundHalfEven).unwrap(), "2023-08-20 13:02:03.123456");
+        assert!(from(1692536523123456789, PrecisionLoss::Reject).is_err());
+        assert_eq!(from(1692536523123456000, PrecisionLoss::Reject).unwrap(), "2023-08-20 13:02:03.123456");
+        assert_eq!(from(-1, PrecisionLoss::Truncate).unwrap(), "1969-12-31 23:59:59.999999");
+        assert_eq!(from(-1, PrecisionLoss::RoundHalfEven).unwrap(), "1970-01-01 00:00:00.000000");
+        assert_eq!(DateTime::from_timestamp_with(1692536523, TimestampUnit::Seconds, PrecisionLoss::Reject).unwrap(), d.floor_to(core::time::Duration::from_secs(1)).unwrap());
+        let v = DateTime::from_timestamps_with(&[999, 1_500, -1], TimestampUnit::Nanos, PrecisionLoss::RoundHalfEven).unwrap();
+        assert_eq!(v.iter().map(|d| d.micro).collect::<Vec<_>>(), [1, 2, 0]);
+        assert!(DateTime::from_timestamps_with(&[1, 1000], TimestampUnit::Nanos, PrecisionLoss::Reject).is_err());
+    }
+}
@@ -0,0 +1,273 @@
+#[cfg(feature = "std")]
+use std::cell::RefCell;
+#[cfg(feature = "std")]
+use std::sync::{Arc, Mutex};
+#[cfg(feature = "std")]
+use core::time::Duration;
+use crate::{Date, DateTime, SignedDuration};
+use crate::http::RetryAfter;
+
+#[cfg(feature = "std")]
+thread_local! {
+    /// the clocks installed by `MockClock::install` on this thread, the last one is in use
+    static INSTALLED: RefCell<alloc::vec::Vec<MockClock>> = const { RefCell::new(alloc::vec::Vec::new()) };
+}
+
+/// the present of the innermost installed `MockClock` of this thread
+#[cfg(feature = "std")]
+pub(crate) fn mocked_now() -> Option<DateTime> {
+    INSTALLED.with(|c| c.borrow().last().map(|m| m.now()))
+}
+
+/// A source of the current time, for code that takes the clock as a parameter instead of
+/// reading the system clock, so tests can pass a fixed one.
+///
+/// closures `Fn() -> DateTime` are clocks too
+pub trait Clock {
+    /// the current instant, in the offset the clock reports it in
+    fn now(&self) -> DateTime;
+
+    /// the current date in the offset of `now`
+    fn today(&self) -> Date {
+        Date::from(self.now())
+    }
+}
+
+impl<F: Fn() -> DateTime> Clock for F {
+    fn now(&self) -> DateTime {
+        self()
+    }
+}
+
+/// The system clock in the offset of `DateTime::now`
+#[cfg(feature = "std")]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime {
+        DateTime::now()
+    }
+}
+
+/// A clock that only moves when told to, for deterministic tests.
+///
+/// clones share the time, so a test can keep one and hand another to the code under test.
+/// `install` also makes `DateTime::now_utc`, `now_local`, `now`, `utc` and `Date::today`
+/// of the current thread read it until the guard is dropped
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct MockClock {
+    now: Arc<Mutex<DateTime>>,
+}
+
+#[cfg(feature = "std")]
+impl MockClock {
+    /// a clock stopped at `now`
+    pub fn new(now: DateTime) -> Self {
+        Self { now: Arc::new(Mutex::new(now)) }
+    }
+
+    /// move the clock to `now`, also backward
+    pub fn set(&self, now: DateTime) {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner()) = now;
+    }
+
+    /// move the clock forward by `d`. panics past `DateTime::MAX`
+    pub fn advance(&self, d: Duration) {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner()) += d;
+    }
+
+    /// use this clock for the `now` functions of the current thread while the guard lives.
+    /// guards nest, dropping one goes back to the clock installed before it
+    pub fn install(&self) -> MockClockGuard {
+        INSTALLED.with(|c| c.borrow_mut().push(self.clone()));
+        MockClockGuard { _not_send: core::marker::PhantomData }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Clock for MockClock {
+    fn now(&self) -> DateTime {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Uninstalls a `MockClock` when dropped, see `MockClock::install`
+#[cfg(feature = "std")]
+#[must_use = "the clock is uninstalled when the guard is dropped"]
+#[derive(Debug)]
+pub struct MockClockGuard {
+    /// the clock is installed on one thread
+    _not_send: core::marker::PhantomData<*const ()>,
+}
+
+#[cfg(feature = "std")]
+impl Drop for MockClockGuard {
+    fn drop(&mut self) {
+        INSTALLED.with(|c| c.borrow_mut().pop());
+    }
+}
+
+/// Which clock of the os `OsClock` reads, trading precision for speed. a source the
+/// platform does not have falls back to `System`
+#[cfg(feature = "std")]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum ClockSource {
+    /// `SystemTime::now`, as `DateTime::now`
+    #[default]
+    System,
+    /// `CLOCK_REALTIME` on unix, `GetSystemTimePreciseAsFileTime` on windows
+    Realtime,
+    /// `CLOCK_REALTIME_COARSE` on linux and android, read without a syscall and only advancing
+    /// every few milliseconds. `GetSystemTimeAsFileTime` on windows, `Realtime` on other unix
+    RealtimeCoarse,
+    /// `GetSystemTimePreciseAsFileTime` on windows
+    Precise,
+}
+
+/// The os clock `source` in the offset of `DateTime::now`, `SystemClock` with a choice of clock
+#[cfg(feature = "std")]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct OsClock {
+    pub source: ClockSource,
+}
+
+#[cfg(feature = "std")]
+impl OsClock {
+    pub fn new(source: ClockSource) -> Self {
+        Self { source }
+    }
+
+    /// the current time of the clock with a zero offset, or that of an installed `MockClock`
+    pub fn now_utc(&self) -> DateTime {
+        if let Some(now) = mocked_now() {
+            return now.to_offset_secs(0);
+        }
+        DateTime::from_unix_nanos(crate::sys::unix_nanos(self.source)).expect("date must be between the years 0001 and 9999")
+    }
+}
+
+#[cfg(feature = "std")]
+impl Clock for OsClock {
+    fn now(&self) -> DateTime {
+        self.now_utc().to_offset_secs(DateTime::now_offset())
+    }
+}
+
+impl Date {
+    /// the current date of `clock`, see `today`
+    pub fn today_with(clock: &impl Clock) -> Self {
+        clock.today()
+    }
+}
+
+impl DateTime {
+    /// the time from `self` until the present of `clock`, negative if `self` is later
+    pub fn elapsed_with(&self, clock: &impl Clock) -> SignedDuration {
+        clock.now() - *self
+    }
+
+    /// whether the present of `clock` is at or past `self`, e.g. a deadline
+    pub fn has_passed_with(&self, clock: &impl Clock) -> bool {
+        clock.now() >= *self
+    }
+}
+
+impl RetryAfter {
+    /// the instant to retry at, a `Delay` counted from the present of `clock`
+    pub fn deadline_with(&self, clock: &impl Clock) -> DateTime {
+        match self {
+            RetryAfter::Delay(d) => clock.now() + *d,
+            RetryAfter::Date(d) => *d,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::str::FromStr;
+    use core::time::Duration;
+    use crate::{Clock, Date, DateTime, SignedDuration};
+    use crate::http::RetryAfter;
+
+    struct Fixed(DateTime);
+
+    impl Clock for Fixed {
+        fn now(&self) -> DateTime {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_clock() {
+        let clock = Fixed(DateTime::from_str("2023-08-20T23:30:00-02:00").unwrap());
+        assert_eq!(Date::today_with(&clock).to_string(), "2023-08-20");
+        let start = DateTime::from_str("2023-08-21T00:00:00Z").unwrap();
+        assert_eq!(start.elapsed_with(&clock), SignedDuration::from_secs(5400));
+        assert!(start.has_passed_with(&clock));
+        assert!(!clock.0.with_offset(0).unwrap().add(Duration::from_micros(1)).has_passed_with(&clock));
+        let retry = RetryAfter::Delay(Duration::from_secs(120));
+        assert_eq!(retry.deadline_with(&clock).to_rfc3339(), "2023-08-20T23:32:00.000000-02:00");
+        assert_eq!(RetryAfter::Date(start).deadline_with(&clock), start);
+        let closure = || start;
+        assert_eq!(Date::today_with(&closure).to_string(), "2023-08-21");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_mock_clock() {
+        use crate::MockClock;
+        let start = DateTime::from_str("2023-08-20T23:30:00+08:00").unwrap();
+        let clock = MockClock::new(start);
+        let shared = clock.clone();
+        clock.advance(Duration::from_secs(60));
+        assert_eq!(shared.now().to_rfc3339(), "2023-08-20T23:31:00.000000+08:00");
+        shared.set(start);
+        assert_eq!(clock.now(), start);
+        {
+            let _guard = clock.install();
+            assert_eq!(DateTime::now_utc().to_rfc3339(), "2023-08-20T15:30:00.000000Z");
+            assert_eq!(DateTime::utc(), start);
+            assert_eq!(DateTime::now(), start);
+            assert_eq!(Date::today_utc().to_string(), "2023-08-20");
+            clock.advance(Duration::from_secs(9 * 3600));
+            assert_eq!(Date::today_utc().to_string(), "2023-08-21");
+            let inner = MockClock::new(DateTime::UNIX_EPOCH);
+            {
+                let _inner = inner.install();
+                assert_eq!(DateTime::now_utc(), DateTime::UNIX_EPOCH);
+            }
+            assert_eq!(DateTime::now_utc(), clock.now());
+            let other = std::thread::spawn(DateTime::now_utc).join().unwrap();
+            assert_ne!(other, clock.now());
+        }
+        assert_ne!(DateTime::now_utc(), clock.now());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_os_clock() {
+        use crate::{ClockSource, OsClock};
+        assert_eq!(OsClock::default(), OsClock::new(ClockSource::System));
+        for source in [ClockSource::System, ClockSource::Realtime, ClockSource::RealtimeCoarse, ClockSource::Precise] {
+            let clock = OsClock::new(source);
+            let (before, now) = (DateTime::now_utc(), clock.now_utc());
+            assert_eq!(now.offset, 0);
+            assert!((now - before).unsigned_abs() < Duration::from_millis(100), "{:?}", source);
+            assert_eq!(clock.now().offset, DateTime::local_offset());
+        }
+        let mock = crate::MockClock::new(DateTime::UNIX_EPOCH);
+        let _guard = mock.install();
+        assert_eq!(OsClock::new(ClockSource::RealtimeCoarse).now(), DateTime::UNIX_EPOCH);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_system_clock() {
+        let now = crate::SystemClock.now();
+        assert_eq!(now.offset, DateTime::local_offset());
+        assert!(now.elapsed_with(&crate::SystemClock) >= SignedDuration::ZERO);
+    }
+}
@@ -1,10 +1,16 @@
-use std::cmp;
-use std::fmt::{self, Display, Formatter, Pointer};
-use std::str::FromStr;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
-use crate::{DateTime, get_digit_unchecked};
+use core::cmp;
+use core::fmt::{self, Display, Formatter};
+use core::ops::{Add, AddAssign, Sub, SubAssign};
+use core::str::FromStr;
+use core::time::Duration;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "serde")]
+use alloc::string::{String, ToString};
+use crate::{DateTime, Period, Time, Weekday, Weekend, const_digits, get_digit};
+use crate::digits::{all_digits, pairs, put2, put4, ZEROS};
 
-use crate::error::Error as Error;
+use crate::error::{Error, Field};
 
 /// Log timestamp type.
 ///
@@ -12,68 +18,241 @@ use crate::error::Error as Error;
 /// Format using the `Display` trait.
 /// Convert timestamp into/from `SytemTime` to use.
 /// Supports comparsion and sorting.
+/// Serde writes "YYYY-MM-DD" to human-readable formats and the `i32` days since
+/// 1970-01-01 to binary ones.
+/// Dates are in the proleptic gregorian calendar, also before its adoption in 1582:
+/// every 4th year is a leap year except centuries not divisible by 400, and the weekdays
+/// run on without the 10 skipped days, so 1582-10-04 is a monday. `to_julian_calendar` and
+/// `from_julian_calendar` convert the dates of older records.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "diesel", derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow))]
+#[cfg_attr(feature = "diesel", diesel(sql_type = diesel::sql_types::Date))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize), rkyv(compare(PartialEq), derive(Debug), bytecheck(verify)))]
 pub struct Date {
     /// 1...31
     pub day: u8,
     /// 1...12
     pub mon: u8,
-    /// 1970...9999
+    /// 1...9999
     pub year: u16,
 }
 
+/// the panic message of formatting a year that does not fit in 4 digits
+pub(crate) const YEAR_DIGITS: &str = "year must be at most 9999 to be formatted";
+
+/// the Julian Day Number of 1970-01-01
+const UNIX_EPOCH_JULIAN_DAY: i64 = 2440588;
+
+/// How `add_months` treats a day that does not exist in the target month
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum MonthOverflow {
+    /// clamp to the last day of the month, 2022-01-31 + 1 month = 2022-02-28
+    Clamp,
+    /// carry the extra days into the next month, 2022-01-31 + 1 month = 2022-03-03
+    Rollover,
+    /// return an `OutOfRangeDay` error
+    Error,
+}
+
+/// How much of a parsed date is checked, see `Date::parse_with` and `DateTime::parse_with`
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum Validation {
+    /// reject a day the month does not have, as `from_str`
+    #[default]
+    Strict,
+    /// only check the digits, the month 1...12 and the day 1...31, leaving out the days of the
+    /// month and leap years. for rows of a database that already enforced them, a 2023-02-30
+    /// of other input is kept as is
+    Relaxed,
+}
+
+/// How a `Date` shifted by a `Duration` that is not a whole number of days is rounded.
+///
+/// The duration is applied to midnight of the date, the policy then picks a day for the
+/// resulting instant.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum DayRounding {
+    /// the day the instant falls on, 2022-12-31 + 36h = 2023-01-01
+    Floor,
+    /// the next day unless the instant is exactly midnight, 2022-12-31 + 36h = 2023-01-02
+    Ceil,
+    /// the nearest midnight, half a day rounds up
+    Nearest,
+}
+
+/// How `Date::shift_to_business_day` moves a date that falls on a weekend
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum BusinessDayConvention {
+    /// the next business day
+    Following,
+    /// the previous business day
+    Preceding,
+    /// the next business day, unless that is in the next month, then the previous one
+    ModifiedFollowing,
+}
+
 impl Date{
-    /// Parse a date from bytes, no check is performed for extract characters at the end of the string
-    pub(crate) fn parse_bytes_partial(bytes: &[u8]) -> Result<Self, Error> {
+    /// the earliest representable date, 0001-01-01
+    pub const MIN: Date = Date {
+        day: 1,
+        mon: 1,
+        year: 1,
+    };
+
+    /// the day of the unix epoch, 1970-01-01
+    pub const UNIX_EPOCH: Date = Date {
+        day: 1,
+        mon: 1,
+        year: 1970,
+    };
+
+    /// the latest representable date, 9999-12-31
+    pub const MAX: Date = Date {
+        day: 31,
+        mon: 12,
+        year: 9999,
+    };
+
+    /// the date `year`-`mon`-`day`, `None` unless the year is 1...9999 and the day
+    /// exists in the month. usable in consts: `const D: Date = Date::new(2023, 8, 20).unwrap();`
+    pub const fn new(year: u16, mon: u8, day: u8) -> Option<Self> {
+        if year < 1 || year > 9999 || day < 1 || day > days_in_month(year, mon) {
+            return None;
+        }
+        Some(Self { day, mon, year })
+    }
+
+    /// the date `year`-`mon`-`day` as `new`, with an error naming the field out of range.
+    /// building the struct directly skips these checks
+    pub fn try_new(year: u16, mon: u8, day: u8) -> Result<Self, Error> {
+        if !(1..=9999).contains(&year) {
+            return Err(Error::OutOfRange { field: Field::Year });
+        }
+        let max_days = days_in_month(year, mon);
+        if max_days == 0 {
+            return Err(Error::OutOfRange { field: Field::Month });
+        }
+        if day < 1 || day > max_days {
+            return Err(Error::OutOfRange { field: Field::Day });
+        }
+        Ok(Self { day, mon, year })
+    }
+
+    /// the current date in the offset of `DateTime::now`
+    pub fn today() -> Self {
+        Self::from(DateTime::now())
+    }
+
+    /// the current date in utc
+    pub fn today_utc() -> Self {
+        Self::from(DateTime::now_utc())
+    }
+
+    /// the day before `today`
+    pub fn yesterday() -> Self {
+        Self::from_days_since_epoch(Self::today().to_unix_day() - 1)
+    }
+
+    /// the day after `today`
+    pub fn tomorrow() -> Self {
+        Self::from_days_since_epoch(Self::today().to_unix_day() + 1)
+    }
+
+    /// the utc datetime of this date at `time`, `DateTime::new` for another offset
+    pub const fn and_time(self, time: Time) -> DateTime {
+        DateTime::new(self, time, 0)
+    }
+
+    /// the utc datetime of this date at `hour:min:sec`, checked as `Time::try_new`
+    pub fn and_hms(self, hour: u8, min: u8, sec: u8) -> Result<DateTime, Error> {
+        Ok(self.and_time(Time::try_new(hour, min, sec, 0)?))
+    }
+
+    /// parse exactly "YYYY-MM-DD" in a const context, `None` if `s` is anything else
+    pub const fn parse_const(s: &str) -> Option<Self> {
+        let bytes = s.as_bytes();
+        if bytes.len() != 10 {
+            return None;
+        }
+        Self::parse_const_partial(bytes)
+    }
+
+    /// the date in the first 10 bytes, "YYYY-MM-DD", as `parse_bytes_partial` in a const fn
+    pub(crate) const fn parse_const_partial(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < 10 || bytes[4] != b'-' || bytes[7] != b'-' {
+            return None;
+        }
+        match (const_digits(bytes, 0, 4), const_digits(bytes, 5, 2), const_digits(bytes, 8, 2)) {
+            (Some(year), Some(mon), Some(day)) => Self::new(year as u16, mon as u8, day as u8),
+            _ => None,
+        }
+    }
+
+    /// parse "YYYY-MM-DD" from trusted input such as our own output, skipping the digit and range
+    /// checks. only the length is checked, the fields of invalid input are unspecified and may
+    /// panic in later calendar arithmetic
+    pub fn parse_unchecked(s: &str) -> Result<Self, Error> {
+        let bytes = s.as_bytes();
         if bytes.len() < 10 {
-            return Err(Error::E("TooShort".to_string()));
-        }
-        let year: u16;
-        let month: u8;
-        let day: u8;
-        unsafe {
-            let y1 = get_digit_unchecked!(bytes, 0, "InvalidCharYear") as u16;
-            let y2 = get_digit_unchecked!(bytes, 1, "InvalidCharYear") as u16;
-            let y3 = get_digit_unchecked!(bytes, 2, "InvalidCharYear") as u16;
-            let y4 = get_digit_unchecked!(bytes, 3, "InvalidCharYear") as u16;
-            year = y1 * 1000 + y2 * 100 + y3 * 10 + y4;
-
-            match bytes.get_unchecked(4) {
-                b'-' => (),
-                _ => return Err(Error::E("InvalidCharDateSep".to_string())),
-            }
+            return Err(Error::TooShort);
+        }
+        Ok(Self::parse_unchecked_partial(bytes))
+    }
 
-            let m1 = get_digit_unchecked!(bytes, 5, "InvalidCharMonth");
-            let m2 = get_digit_unchecked!(bytes, 6, "InvalidCharMonth");
-            month = m1 * 10 + m2;
+    /// the fields of "YYYY-MM-DD" in the first 10 bytes, which must exist
+    pub(crate) fn parse_unchecked_partial(bytes: &[u8]) -> Self {
+        let d = |i: usize| bytes[i].wrapping_sub(b'0');
+        Self {
+            day: d(8).wrapping_mul(10).wrapping_add(d(9)),
+            mon: d(5).wrapping_mul(10).wrapping_add(d(6)),
+            year: (d(0) as u16).wrapping_mul(1000)
+                .wrapping_add((d(1) as u16).wrapping_mul(100))
+                .wrapping_add((d(2) as u16).wrapping_mul(10))
+                .wrapping_add(d(3) as u16),
+        }
+    }
 
-            match bytes.get_unchecked(7) {
-                b'-' => (),
-                _ => return Err(Error::E("InvalidCharDateSep".to_string())),
-            }
+    /// parse "YYYY-MM-DD" as `from_str`, checking the day as `validation` says
+    pub fn parse_with(s: &str, validation: Validation) -> Result<Self, Error> {
+        Self::parse_bytes_partial_with(s.as_bytes(), validation)
+    }
 
-            let d1 = get_digit_unchecked!(bytes, 8, "InvalidCharDay");
-            let d2 = get_digit_unchecked!(bytes, 9, "InvalidCharDay");
-            day = d1 * 10 + d2;
+    /// Parse a date from bytes, no check is performed for extract characters at the end of the string
+    pub(crate) fn parse_bytes_partial(bytes: &[u8]) -> Result<Self, Error> {
+        Self::parse_bytes_partial_with(bytes, Validation::Strict)
+    }
+
+    pub(crate) fn parse_bytes_partial_with(bytes: &[u8], validation: Validation) -> Result<Self, Error> {
+        if bytes.len() < 10 {
+            return Err(Error::TooShort);
+        }
+        // "YYYY-MM-" as one little-endian word, its 6 digits checked at once
+        let w = u64::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7]]);
+        const DIGITS: u64 = 0x00ff_ff00_ffff_ffff;
+        if w & !DIGITS != 0x2d00_002d_0000_0000 || !all_digits(w, DIGITS) {
+            return Err(invalid_date_char(bytes));
+        }
+        let p = pairs((w & DIGITS) - (ZEROS & DIGITS));
+        let year = (p & 0xff) as u16 * 100 + (p >> 16 & 0xff) as u16;
+        let month = (p >> 40 & 0xff) as u8;
+        let d1 = get_digit!(bytes, 8, Field::Day);
+        let d2 = get_digit!(bytes, 9, Field::Day);
+        let day = d1 * 10 + d2;
+        if year < 1 {
+            return Err(Error::OutOfRange { field: Field::Year });
         }
 
-        // calculate the maximum number of days in the month, accounting for leap years in the
-        // gregorian calendar
-        let max_days = match month {
-            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
-            4 | 6 | 9 | 11 => 30,
-            2 => {
-                if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) {
-                    29
-                } else {
-                    28
-                }
-            }
-            _ => return Err(Error::E("OutOfRangeMonth".to_string())),
+        let max_days = match validation {
+            Validation::Strict => days_in_month(year, month),
+            Validation::Relaxed if (1..=12).contains(&month) => 31,
+            Validation::Relaxed => 0,
         };
+        if max_days == 0 {
+            return Err(Error::OutOfRange { field: Field::Month });
+        }
 
         if day < 1 || day > max_days {
-            return Err(Error::E("OutOfRangeDay".to_string()));
+            return Err(Error::OutOfRange { field: Field::Day });
         }
 
         Ok(Self {
@@ -82,6 +261,578 @@ impl Date{
             year
         })
     }
+
+    /// add a duration, returning `None` if the result is out of the range 0001...9999.
+    /// the time of day the duration carries into is dropped
+    pub fn checked_add(self, d: Duration) -> Option<Self> {
+        self.checked_add_rounded(d, DayRounding::Floor)
+    }
+
+    /// sub a duration, returning `None` if the result is out of the range 0001...9999.
+    /// the time of day the duration carries into is dropped
+    pub fn checked_sub(self, d: Duration) -> Option<Self> {
+        self.checked_sub_rounded(d, DayRounding::Floor)
+    }
+
+    /// add a duration, rounding a partial day with `rounding`.
+    /// returns `None` if the result is out of the range 0001...9999
+    pub fn checked_add_rounded(self, d: Duration, rounding: DayRounding) -> Option<Self> {
+        Self::round_datetime(self.to_datetime().checked_add(d)?, rounding)
+    }
+
+    /// sub a duration, rounding a partial day with `rounding`.
+    /// returns `None` if the result is out of the range 0001...9999
+    pub fn checked_sub_rounded(self, d: Duration, rounding: DayRounding) -> Option<Self> {
+        Self::round_datetime(self.to_datetime().checked_sub(d)?, rounding)
+    }
+
+    fn round_datetime(v: DateTime, rounding: DayRounding) -> Option<Self> {
+        let is_midnight = v.hour == 0 && v.min == 0 && v.sec == 0 && v.micro == 0;
+        let next_day = match rounding {
+            DayRounding::Floor => false,
+            DayRounding::Ceil => !is_midnight,
+            DayRounding::Nearest => v.hour >= 12,
+        };
+        let date = Date::from(v);
+        if next_day {
+            date.checked_add(Duration::from_secs(86400))
+        } else {
+            Some(date)
+        }
+    }
+
+    /// add a duration, clamping to `Date::MAX` instead of overflowing
+    pub fn saturating_add(self, d: Duration) -> Self {
+        self.checked_add(d).unwrap_or(Self::MAX)
+    }
+
+    /// sub a duration, clamping to `Date::MIN` instead of overflowing
+    pub fn saturating_sub(self, d: Duration) -> Self {
+        self.checked_sub(d).unwrap_or(Self::MIN)
+    }
+
+    /// add `n` months (negative to go back), resolving a day past the end of
+    /// the target month with `policy`
+    pub fn add_months(self, n: i32, policy: MonthOverflow) -> Result<Self, Error> {
+        let months = self.year as i64 * 12 + (self.mon as i64 - 1) + n as i64;
+        let year = months.div_euclid(12);
+        let mon = (months.rem_euclid(12) + 1) as u8;
+        if year < Self::MIN.year as i64 || year > Self::MAX.year as i64 {
+            return Err(Error::OutOfRange { field: Field::Year });
+        }
+        Self::resolve(year as u16, mon, self.day, policy)
+    }
+
+    /// 1...9999
+    pub const fn year(&self) -> u16 {
+        self.year
+    }
+
+    /// 1...12
+    pub const fn month(&self) -> u8 {
+        self.mon
+    }
+
+    /// 1...31
+    pub const fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// set the year, failing with `OutOfRangeDay` for 02-29 in a common year
+    pub fn set_year(&mut self, year: u16) -> Result<(), Error> {
+        *self = self.with_year(year, MonthOverflow::Error)?;
+        Ok(())
+    }
+
+    /// set the month, failing with `OutOfRangeDay` if the day does not exist in it
+    pub fn set_month(&mut self, mon: u8) -> Result<(), Error> {
+        *self = self.with_month(mon, MonthOverflow::Error)?;
+        Ok(())
+    }
+
+    /// set the day, which must exist in the month
+    pub fn set_day(&mut self, day: u8) -> Result<(), Error> {
+        *self = self.with_day(day)?;
+        Ok(())
+    }
+
+    /// the same date in `year`, resolving 02-29 in a common year with `policy`
+    pub fn with_year(self, year: u16, policy: MonthOverflow) -> Result<Self, Error> {
+        if !(Self::MIN.year..=Self::MAX.year).contains(&year) {
+            return Err(Error::OutOfRange { field: Field::Year });
+        }
+        Self::resolve(year, self.mon, self.day, policy)
+    }
+
+    /// the same date in month `mon` (1...12), resolving a day past the end of the month with `policy`
+    pub fn with_month(self, mon: u8, policy: MonthOverflow) -> Result<Self, Error> {
+        if !(1..=12).contains(&mon) {
+            return Err(Error::OutOfRange { field: Field::Month });
+        }
+        Self::resolve(self.year, mon, self.day, policy)
+    }
+
+    /// the same month with day `day`, which must exist in the month
+    pub fn with_day(self, day: u8) -> Result<Self, Error> {
+        if day < 1 || day > days_in_month(self.year, self.mon) {
+            return Err(Error::OutOfRange { field: Field::Day });
+        }
+        Ok(Self { day, ..self })
+    }
+
+    /// build a date from a valid year and month and a day that may be past the end of the month
+    fn resolve(year: u16, mon: u8, day: u8, policy: MonthOverflow) -> Result<Self, Error> {
+        let max_days = days_in_month(year, mon);
+        if day <= max_days {
+            return Ok(Self { day, mon, year });
+        }
+        match policy {
+            MonthOverflow::Clamp => Ok(Self { day: max_days, mon, year }),
+            // december has 31 days, so the carry never leaves the year
+            MonthOverflow::Rollover => Ok(Self { day: day - max_days, mon: mon + 1, year }),
+            MonthOverflow::Error => Err(Error::OutOfRange { field: Field::Day }),
+        }
+    }
+
+    /// whole days from `other` to `self`, negative when `self` is earlier
+    pub fn diff_days(self, other: Date) -> i64 {
+        self.to_datetime().diff_days(other.to_datetime())
+    }
+
+    /// whole weeks from `other` to `self`, truncated toward zero
+    pub fn diff_weeks(self, other: Date) -> i64 {
+        self.diff_days(other) / 7
+    }
+
+    /// whole calendar months from `other` to `self`, truncated toward zero.
+    /// a month is complete once the same day of month is reached, clamped to the
+    /// end of shorter months, so 01-31 to 02-28 is one month
+    pub fn diff_months(self, other: Date) -> i64 {
+        self.to_datetime().diff_months(other.to_datetime())
+    }
+
+    /// whole calendar years from `other` to `self`, truncated toward zero
+    pub fn diff_years(self, other: Date) -> i64 {
+        self.to_datetime().diff_years(other.to_datetime())
+    }
+
+    /// number of business days (monday to friday) in `[start, end)`,
+    /// negative when `end` is before `start`
+    pub fn working_days_between(start: Date, end: Date) -> i64 {
+        let days = end.to_unix_day() - start.to_unix_day();
+        if days < 0 {
+            return -Self::working_days_between(end, start);
+        }
+        let mut count = days / 7 * 5;
+        for n in 0..days % 7 {
+            if Date::from_days_since_epoch(start.to_unix_day() + n).is_weekday() {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// move a saturday or sunday to a business day according to `convention`,
+    /// business days are returned unchanged
+    pub fn shift_to_business_day(self, convention: BusinessDayConvention) -> Self {
+        const DAY: Duration = Duration::from_secs(86400);
+        let following = |mut d: Date| {
+            while d.is_weekend() {
+                d += DAY;
+            }
+            d
+        };
+        let preceding = |mut d: Date| {
+            while d.is_weekend() {
+                d -= DAY;
+            }
+            d
+        };
+        match convention {
+            BusinessDayConvention::Following => following(self),
+            BusinessDayConvention::Preceding => preceding(self),
+            BusinessDayConvention::ModifiedFollowing => {
+                let d = following(self);
+                if d.mon != self.mon {
+                    preceding(self)
+                } else {
+                    d
+                }
+            }
+        }
+    }
+
+    /// the `n`th `weekday` of a month, counted from the end when `n` is negative:
+    /// `n = 2` is the second, `n = -1` the last one.
+    /// returns `None` if the month has no such day (a 5th friday, `n = 0`), `mon` is not 1...12
+    /// or `year` is not 1...9999
+    pub fn nth_weekday_of_month(year: u16, mon: u8, weekday: Weekday, n: i8) -> Option<Self> {
+        if !(1..=9999).contains(&year) || !(1..=12).contains(&mon) || n == 0 {
+            return None;
+        }
+        let target = weekday.num_days_from_monday() as i64;
+        let last_day = days_in_month(year, mon) as i64;
+        let day = if n > 0 {
+            let first = Date { day: 1, mon, year }.weekday_from_monday() as i64;
+            1 + (target - first).rem_euclid(7) + 7 * (n as i64 - 1)
+        } else {
+            let last = Date { day: last_day as u8, mon, year }.weekday_from_monday() as i64;
+            last_day - (last - target).rem_euclid(7) - 7 * (-(n as i64) - 1)
+        };
+        if day < 1 || day > last_day {
+            return None;
+        }
+        Some(Date { day: day as u8, mon, year })
+    }
+
+    /// restrict `self` to `[min, max]` like `Ord::clamp`, but returns an
+    /// `InvalidClampRange` error instead of panicking when `min > max`
+    pub fn clamp(self, min: Date, max: Date) -> Result<Self, Error> {
+        let (days, min_days, max_days) = (self.to_unix_day(), min.to_unix_day(), max.to_unix_day());
+        if min_days > max_days {
+            return Err(Error::InvalidClampRange);
+        }
+        if days < min_days {
+            Ok(min)
+        } else if days > max_days {
+            Ok(max)
+        } else {
+            Ok(self)
+        }
+    }
+
+    /// the quarter of the year, 1...4
+    pub fn quarter(self) -> u8 {
+        self.mon.div_ceil(3)
+    }
+
+    /// the first day of the same quarter
+    pub fn start_of_quarter(self) -> Self {
+        Self {
+            day: 1,
+            mon: self.quarter() * 3 - 2,
+            year: self.year,
+        }
+    }
+
+    /// the last day of the same quarter
+    pub fn end_of_quarter(self) -> Self {
+        let mon = self.quarter() * 3;
+        Self {
+            day: days_in_month(self.year, mon),
+            mon,
+            year: self.year,
+        }
+    }
+
+    /// whether the year of this date is a leap year
+    pub fn is_leap_year(self) -> bool {
+        is_leap_year(self.year)
+    }
+
+    /// the ordinal day of the year, 1...366
+    pub fn day_of_year(self) -> u16 {
+        // days since march 1st, the last day of february is then the end of the year
+        let doy = day_of_march_year(self.mon as u32, self.day as u32) as u16;
+        if self.mon > 2 {
+            doy + 60 + is_leap_year(self.year) as u16
+        } else {
+            doy - 305
+        }
+    }
+
+    /// the ISO 8601 week-based year and week number (1...53).
+    ///
+    /// weeks start on monday and week 1 is the one containing the first thursday of the
+    /// year, so early january can belong to the last week of the previous year and late
+    /// december to week 1 of the next year
+    pub fn iso_week(self) -> (i32, u8) {
+        fn weeks_in_year(year: i32) -> i32 {
+            // a year has 53 weeks when it starts on a thursday, or on a wednesday in a leap year
+            let jan1 = Date { day: 1, mon: 1, year: year as u16 }.weekday_from_monday();
+            if jan1 == 3 || (jan1 == 2 && is_leap_year(year as u16)) {
+                53
+            } else {
+                52
+            }
+        }
+        let year = self.year as i32;
+        let week = (self.day_of_year() as i32 - self.weekday_from_monday() as i32 + 9) / 7;
+        if week < 1 {
+            (year - 1, weeks_in_year(year - 1) as u8)
+        } else if week > weeks_in_year(year) {
+            (year + 1, 1)
+        } else {
+            (year, week as u8)
+        }
+    }
+
+    /// the day of the week.
+    ///
+    /// computed from the proleptic gregorian day count since 1970-01-01 (a thursday),
+    /// which is exact for every representable date
+    pub fn weekday(self) -> Weekday {
+        Weekday::from_num_days_from_monday(self.weekday_from_monday())
+    }
+
+    /// whether this date is a saturday or sunday
+    pub fn is_weekend(self) -> bool {
+        self.is_weekend_in(Weekend::SATURDAY_SUNDAY)
+    }
+
+    /// whether this date is monday to friday
+    pub fn is_weekday(self) -> bool {
+        !self.is_weekend()
+    }
+
+    /// whether this date falls on `weekend`, e.g. `Weekend::FRIDAY_SATURDAY`
+    pub fn is_weekend_in(self, weekend: Weekend) -> bool {
+        weekend.contains(self.weekday())
+    }
+
+    /// the row (1...6) of this date in a month calendar grid whose weeks start on `first_day`.
+    /// the week containing the 1st is week 1, however few days of it are in the month
+    pub fn week_of_month(self, first_day: Weekday) -> u8 {
+        let first = Date { day: 1, ..self }.weekday_from_monday();
+        let offset = (first + 7 - first_day.num_days_from_monday()) % 7;
+        (self.day - 1 + offset) / 7 + 1
+    }
+
+    /// the first `weekday` after this date, a week later if it is one.
+    /// `None` past `Date::MAX`
+    pub fn next_weekday(self, weekday: Weekday) -> Option<Self> {
+        let days = (weekday.num_days_from_monday() + 6 - self.weekday_from_monday()) % 7 + 1;
+        Self::from_unix_day(self.to_unix_day() + days as i64)
+    }
+
+    /// the last `weekday` before this date, a week earlier if it is one.
+    /// `None` before `Date::MIN`
+    pub fn prev_weekday(self, weekday: Weekday) -> Option<Self> {
+        let days = (self.weekday_from_monday() + 6 - weekday.num_days_from_monday()) % 7 + 1;
+        Self::from_unix_day(self.to_unix_day() - days as i64)
+    }
+
+    /// western (gregorian) easter sunday of `year`, using the anonymous gregorian computus.
+    /// `None` unless the year is 1...9999
+    pub fn easter(year: u16) -> Option<Self> {
+        if !(1..=9999).contains(&year) {
+            return None;
+        }
+        let y = year as i64;
+        let a = y % 19;
+        let b = y / 100;
+        let c = y % 100;
+        let d = b / 4;
+        let e = b % 4;
+        let f = (b + 8) / 25;
+        let g = (b - f + 1) / 3;
+        let h = (19 * a + b - d - g + 15) % 30;
+        let i = c / 4;
+        let k = c % 4;
+        let l = (32 + 2 * e + 2 * i - h - k) % 7;
+        let m = (a + 11 * h + 22 * l) / 451;
+        let n = h + l - 7 * m + 114;
+        Some(Self {
+            day: (n % 31 + 1) as u8,
+            mon: (n / 31) as u8,
+            year,
+        })
+    }
+
+    /// orthodox easter sunday of `year`, computed in the julian calendar (meeus)
+    /// and returned as a gregorian date. `None` unless the year is 1...9999
+    pub fn orthodox_easter(year: u16) -> Option<Self> {
+        if !(1..=9999).contains(&year) {
+            return None;
+        }
+        let y = year as i64;
+        let d = (19 * (y % 19) + 15) % 30;
+        let e = (2 * (y % 4) + 4 * (y % 7) - d + 34) % 7;
+        let n = d + e + 114;
+        let julian = Self {
+            day: (n % 31 + 1) as u8,
+            mon: (n / 31) as u8,
+            year,
+        };
+        // the gregorian calendar runs ahead of the julian one by 13 days in 1900...2099
+        let shift = y / 100 - y / 400 - 2;
+        Some(Self::from_days_since_epoch(julian.to_unix_day() + shift))
+    }
+
+    /// the date of `year`-`mon`-`day` in the julian calendar, where every 4th year is a leap
+    /// year, e.g. 1582-10-05 julian is 1582-10-15, the first day of the gregorian calendar.
+    /// fails for an invalid julian date or one outside of `Date::MIN...Date::MAX`
+    pub fn from_julian_calendar(year: u16, mon: u8, day: u8) -> Result<Self, Error> {
+        let max_days = match mon {
+            2 if year.is_multiple_of(4) => 29,
+            _ => days_in_month(year, mon),
+        };
+        if max_days == 0 {
+            return Err(Error::OutOfRange { field: Field::Month });
+        }
+        if day < 1 || day > max_days {
+            return Err(Error::OutOfRange { field: Field::Day });
+        }
+        // the julian day number of the date, the years counted from march of -4800
+        let a = (14 - mon as i64) / 12;
+        let y = year as i64 + 4800 - a;
+        let m = mon as i64 + 12 * a - 3;
+        let jdn = day as i64 + (153 * m + 2) / 5 + 365 * y + y / 4 - 32083;
+        Self::from_julian_day(jdn).ok_or(Error::OutOfRange { field: Field::Date })
+    }
+
+    /// the year, month and day of the date in the julian calendar, 0001-01-01 is 0001-01-03
+    pub fn to_julian_calendar(self) -> (u16, u8, u8) {
+        let c = self.to_julian_day() + 32082;
+        let d = (4 * c + 3) / 1461;
+        let e = c - 1461 * d / 4;
+        let m = (5 * e + 2) / 153;
+        let day = e - (153 * m + 2) / 5 + 1;
+        let mon = m + 3 - 12 * (m / 10);
+        ((d - 4800 + m / 10) as u16, mon as u8, day as u8)
+    }
+
+    /// the Julian Day Number, the number of days since noon on -4713-11-24 (proleptic
+    /// gregorian), as used by astronomy software. 1970-01-01 is day 2440588
+    pub fn to_julian_day(self) -> i64 {
+        self.to_unix_day() + UNIX_EPOCH_JULIAN_DAY
+    }
+
+    /// the date of a Julian Day Number, `None` if it is out of the range `Date::MIN...Date::MAX`
+    pub fn from_julian_day(jdn: i64) -> Option<Self> {
+        Self::from_unix_day(jdn.checked_sub(UNIX_EPOCH_JULIAN_DAY)?)
+    }
+
+    /// the date `days` days after 1970-01-01, `None` if it is out of the range `Date::MIN...Date::MAX`
+    pub fn from_unix_day(days: i64) -> Option<Self> {
+        if days < Self::MIN.to_unix_day() || days > Self::MAX.to_unix_day() {
+            return None;
+        }
+        Some(Self::from_days_since_epoch(days))
+    }
+
+    /// days since 1970-01-01, negative before it.
+    /// this is the `DATE32` representation of arrow and parquet
+    pub const fn to_unix_day(self) -> i64 {
+        days_from_civil(self.year as i64, self.mon as u32, self.day as u32)
+    }
+
+    /// pack into `year << 9 | mon << 5 | day`, lossless and in the same order as the dates,
+    /// so columnar stores can sort and binary search them as plain integers
+    pub const fn to_bits(self) -> u32 {
+        (self.year as u32) << 9 | (self.mon as u32) << 5 | self.day as u32
+    }
+
+    /// unpack `to_bits`, `None` if `bits` is not the packing of a valid date
+    pub const fn from_bits(bits: u32) -> Option<Self> {
+        if bits >> 9 < 1 || bits >> 9 > 9999 {
+            return None;
+        }
+        Self::new((bits >> 9) as u16, (bits >> 5 & 0xf) as u8, (bits & 0x1f) as u8)
+    }
+
+    /// the date `days` days after 1970-01-01, negative before it
+    pub(crate) const fn from_days_since_epoch(days: i64) -> Self {
+        let (year, mon, day) = civil_from_days(days);
+        Self {
+            day: day as u8,
+            mon: mon as u8,
+            year: year as u16,
+        }
+    }
+
+    /// 0 = monday ... 6 = sunday
+    pub(crate) fn weekday_from_monday(self) -> u8 {
+        // 1970-01-01 was a thursday
+        (self.to_unix_day() + 3).rem_euclid(7) as u8
+    }
+
+    /// midnight of this date at utc
+    fn to_datetime(self) -> DateTime {
+        self.and_time(Time::MIDNIGHT)
+    }
+}
+
+/// the error of the first byte of "YYYY-MM-" that is not a digit or the `-`, once the word check failed
+#[cold]
+fn invalid_date_char(bytes: &[u8]) -> Error {
+    let fields = [Field::Year, Field::Year, Field::Year, Field::Year, Field::DateSep, Field::Month, Field::Month, Field::DateSep];
+    for (pos, field) in fields.into_iter().enumerate() {
+        let ok = if field == Field::DateSep { bytes[pos] == b'-' } else { bytes[pos].is_ascii_digit() };
+        if !ok {
+            return Error::InvalidChar { field, pos };
+        }
+    }
+    Error::InvalidDate
+}
+
+/// days since 1970-01-01 of `y`-`m`-`d` in the proleptic gregorian calendar, without loops or
+/// tables. howard hinnant's `days_from_civil`: years start on march 1st so the leap day is the
+/// last of the year, and 400 years are always 146097 days
+/// <https://howardhinnant.github.io/date_algorithms.html#days_from_civil>
+pub(crate) const fn days_from_civil(y: i64, m: u32, d: u32) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    // 0...399
+    let yoe = y - era * 400;
+    // 0...146096
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + day_of_march_year(m, d) as i64;
+    // 719468 days from 0000-03-01 to 1970-01-01
+    era * 146097 + doe - 719468
+}
+
+/// `(year, month, day)` of the date `z` days after 1970-01-01, the inverse of `days_from_civil`
+/// <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+pub(crate) const fn civil_from_days(z: i64) -> (i64, u32, u32) {
+    let z = z + 719468;
+    let era = z.div_euclid(146097);
+    // 0...146096
+    let doe = z - era * 146097;
+    // 0...399
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    // 0...365, from march 1st
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    // 0...11, from march
+    let mp = (5 * doy + 2) / 153;
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let y = yoe + era * 400;
+    (if m <= 2 { y + 1 } else { y }, m, d)
+}
+
+/// 0...365, the day of the year that starts on march 1st
+const fn day_of_march_year(m: u32, d: u32) -> u32 {
+    let mp = (m + 9) % 12;
+    (153 * mp + 2) / 5 + d - 1
+}
+
+/// whether `y` is a leap year in the gregorian calendar:
+/// divisible by 4, except centuries that are not divisible by 400
+#[allow(clippy::manual_is_multiple_of)]
+pub const fn is_leap_year(y: u16) -> bool {
+    y % 4 == 0 && (y % 100 != 0 || y % 400 == 0)
+}
+
+/// the number of days in month `mon` (1...12) of `year`, accounting for leap years in the
+/// gregorian calendar. returns 0 when `mon` is not a month, so `day <= days_in_month(..)`
+/// still rejects it
+pub const fn days_in_month(year: u16, mon: u8) -> u8 {
+    match mon {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        _ => 0,
+    }
+}
+
+/// the number of days in `year`, 365 or 366
+pub const fn days_in_year(year: u16) -> u16 {
+    if is_leap_year(year) {
+        366
+    } else {
+        365
+    }
 }
 
 impl From<DateTime> for Date{
@@ -94,6 +845,86 @@ impl From<DateTime> for Date{
     }
 }
 
+impl Add<Duration> for Date {
+    type Output = Date;
+
+    /// add a duration, the time of day it carries into is dropped
+    fn add(self, rhs: Duration) -> Self::Output {
+        self.checked_add(rhs).expect("overflow when adding duration to date")
+    }
+}
+
+impl Sub<Duration> for Date {
+    type Output = Date;
+
+    /// sub a duration, the time of day it carries into is dropped
+    fn sub(self, rhs: Duration) -> Self::Output {
+        self.checked_sub(rhs).expect("overflow when subtracting duration from date")
+    }
+}
+
+impl AddAssign<Duration> for Date {
+    fn add_assign(&mut self, rhs: Duration) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign<Duration> for Date {
+    fn sub_assign(&mut self, rhs: Duration) {
+        *self = *self - rhs;
+    }
+}
+
+impl Add<Period> for Date {
+    type Output = Date;
+
+    /// add a calendar period, the time of day it carries into is dropped
+    fn add(self, rhs: Period) -> Self::Output {
+        Date::from(self.to_datetime() + rhs)
+    }
+}
+
+impl Sub<Period> for Date {
+    type Output = Date;
+
+    /// sub a calendar period, the time of day it carries into is dropped
+    fn sub(self, rhs: Period) -> Self::Output {
+        Date::from(self.to_datetime() - rhs)
+    }
+}
+
+impl AddAssign<Period> for Date {
+    fn add_assign(&mut self, rhs: Period) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign<Period> for Date {
+    fn sub_assign(&mut self, rhs: Period) {
+        *self = *self - rhs;
+    }
+}
+
+/// 1970-01-01
+impl Default for Date {
+    fn default() -> Self {
+        Date::UNIX_EPOCH
+    }
+}
+
+/// by year, then month, then day, unlike the order of the fields
+impl Ord for Date {
+    fn cmp(&self, other: &Date) -> cmp::Ordering {
+        (self.year, self.mon, self.day).cmp(&(other.year, other.mon, other.day))
+    }
+}
+
+impl PartialOrd for Date {
+    fn partial_cmp(&self, other: &Date) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl FromStr for Date {
     type Err = Error;
 
@@ -104,28 +935,62 @@ impl FromStr for Date {
     }
 }
 
-impl Display for Date{
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+impl Date {
+    /// "YYYY-MM-DD" as ascii bytes, years below 1000 padded with zeros. panics for a year
+    /// past 9999, which only a struct built without `new` or `try_new` can have
+    pub(crate) fn to_ascii(self) -> [u8; 10] {
+        assert!(self.year <= 9999, "{}", YEAR_DIGITS);
         let mut buf: [u8; 10] = *b"0000-00-00";
+        put4(&mut buf, 0, self.year as u32);
+        put2(&mut buf, 5, self.mon as u32);
+        put2(&mut buf, 8, self.day as u32);
+        buf
+    }
 
-        buf[0] = b'0' + (self.year / 1000) as u8;
-        buf[1] = b'0' + (self.year / 100 % 10) as u8;
-        buf[2] = b'0' + (self.year / 10 % 10) as u8;
-        buf[3] = b'0' + (self.year % 10) as u8;
+    /// `to_string` into an inline string, without allocating
+    #[cfg(feature = "arrayvec")]
+    pub fn to_array_string(&self) -> arrayvec::ArrayString<10> {
+        arrayvec::ArrayString::from_byte_string(&self.to_ascii()).unwrap()
+    }
+}
+
+impl Display for Date{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(core::str::from_utf8(&self.to_ascii()).unwrap())
+    }
+}
 
-        buf[5] = b'0' + (self.mon / 10) as u8;
-        buf[6] = b'0' + (self.mon % 10) as u8;
+/// serialized as an RFC 3339 full-date string, "2006-01-02"
+#[cfg(feature = "serde")]
+impl Serialize for Date {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&self.to_string())
+        } else {
+            serializer.serialize_i32(self.to_unix_day() as i32)
+        }
+    }
+}
 
-        buf[8] = b'0' + (self.day / 10) as u8;
-        buf[9] = b'0' + (self.day % 10) as u8;
-        f.write_str(std::str::from_utf8(&buf[..]).unwrap())
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Date {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de> {
+        use serde::de::Error;
+        if !deserializer.is_human_readable() {
+            let days = i32::deserialize(deserializer)? as i64;
+            return Date::from_unix_day(days).ok_or_else(|| D::Error::custom("OutOfRangeDay"));
+        }
+        let s = String::deserialize(deserializer)?;
+        Date::from_str(&s).map_err(D::Error::custom)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
-    use crate::Date;
+    use std::time::Duration;
+    use crate::{days_in_month, days_in_year, is_leap_year, BusinessDayConvention, Date, DayRounding, MonthOverflow, Period, Validation, Weekday, Weekend};
+    use crate::error::{Error, Field};
 
     #[test]
     fn test_date() {
@@ -133,4 +998,468 @@ mod tests {
         println!("{}", d);
         assert_eq!("1234-12-13".to_string(), d.to_string());
     }
+
+    #[test]
+    fn test_date_checked_add() {
+        let d = Date::from_str("2022-12-31").unwrap();
+        assert_eq!(d.checked_add(Duration::from_secs(86400)).unwrap().to_string(), "2023-01-01");
+        assert_eq!(d.checked_sub(Duration::from_secs(1)).unwrap().to_string(), "2022-12-30");
+        assert_eq!(Date::from_str("9999-12-31").unwrap().checked_add(Duration::from_secs(86400)), None);
+        assert_eq!(Date::from_str("1970-01-01").unwrap().checked_sub(Duration::from_secs(1)).unwrap().to_string(), "1969-12-31");
+        assert_eq!(Date::from_str("0001-01-01").unwrap().checked_sub(Duration::from_secs(1)), None);
+    }
+
+    #[test]
+    fn test_date_saturating() {
+        let d = Date::from_str("2022-12-31").unwrap();
+        assert_eq!(d.saturating_add(Duration::from_secs(86400)).to_string(), "2023-01-01");
+        assert_eq!(d.saturating_add(Duration::from_secs(u64::MAX)), Date::MAX);
+        assert_eq!(d.saturating_sub(Duration::from_secs(u64::MAX)), Date::MIN);
+    }
+
+    #[test]
+    fn test_date_add_months() {
+        let d = Date::from_str("2024-01-31").unwrap();
+        assert_eq!(d.add_months(1, MonthOverflow::Clamp).unwrap().to_string(), "2024-02-29");
+        assert_eq!(d.add_months(13, MonthOverflow::Clamp).unwrap().to_string(), "2025-02-28");
+        assert_eq!(d.add_months(1, MonthOverflow::Rollover).unwrap().to_string(), "2024-03-02");
+        assert!(d.add_months(1, MonthOverflow::Error).is_err());
+        assert_eq!(d.add_months(-1, MonthOverflow::Error).unwrap().to_string(), "2023-12-31");
+        assert_eq!(d.add_months(-2, MonthOverflow::Clamp).unwrap().to_string(), "2023-11-30");
+        assert_eq!(d.add_months(-11, MonthOverflow::Clamp).unwrap().to_string(), "2023-02-28");
+        assert!(Date::MAX.add_months(1, MonthOverflow::Clamp).is_err());
+    }
+
+    #[test]
+    fn test_date_add_duration() {
+        let d = Date::from_str("2022-12-31").unwrap();
+        assert_eq!((d + Duration::from_secs(86400 * 2)).to_string(), "2023-01-02");
+        assert_eq!((d - Duration::from_secs(86400 * 2)).to_string(), "2022-12-29");
+        let h36 = Duration::from_secs(36 * 3600);
+        assert_eq!(d.checked_add_rounded(h36, DayRounding::Floor).unwrap().to_string(), "2023-01-01");
+        assert_eq!(d.checked_add_rounded(h36, DayRounding::Ceil).unwrap().to_string(), "2023-01-02");
+        assert_eq!(d.checked_add_rounded(h36, DayRounding::Nearest).unwrap().to_string(), "2023-01-02");
+        assert_eq!(d.checked_sub_rounded(h36, DayRounding::Floor).unwrap().to_string(), "2022-12-29");
+        assert_eq!(d.checked_sub_rounded(h36, DayRounding::Ceil).unwrap().to_string(), "2022-12-30");
+        let h1 = Duration::from_secs(3600);
+        assert_eq!(d.checked_add_rounded(h1, DayRounding::Nearest).unwrap().to_string(), "2022-12-31");
+        assert_eq!(d.checked_add_rounded(Duration::from_secs(86400), DayRounding::Ceil).unwrap().to_string(), "2023-01-01");
+    }
+
+    #[test]
+    fn test_date_diff() {
+        let a = Date::from_str("2023-01-31").unwrap();
+        let b = Date::from_str("2023-02-28").unwrap();
+        assert_eq!(b.diff_days(a), 28);
+        assert_eq!(b.diff_weeks(a), 4);
+        assert_eq!(b.diff_months(a), 1);
+        assert_eq!(a.diff_months(b), -1);
+        assert_eq!(b.diff_years(a), 0);
+        assert_eq!(Date::from_str("2024-02-29").unwrap().diff_years(Date::from_str("2020-02-29").unwrap()), 4);
+    }
+
+    #[test]
+    fn test_date_business_days() {
+        // 2023-09-01 is a friday
+        let fri = Date::from_str("2023-09-01").unwrap();
+        let mon = Date::from_str("2023-09-04").unwrap();
+        assert_eq!(Date::working_days_between(fri, mon), 1);
+        assert_eq!(Date::working_days_between(mon, fri), -1);
+        assert_eq!(Date::working_days_between(fri, Date::from_str("2023-09-15").unwrap()), 10);
+        assert_eq!(Date::working_days_between(fri, fri), 0);
+
+        let sat = Date::from_str("2023-09-02").unwrap();
+        assert_eq!(sat.shift_to_business_day(BusinessDayConvention::Following), mon);
+        assert_eq!(sat.shift_to_business_day(BusinessDayConvention::Preceding), fri);
+        assert_eq!(sat.shift_to_business_day(BusinessDayConvention::ModifiedFollowing), mon);
+        assert_eq!(mon.shift_to_business_day(BusinessDayConvention::Preceding), mon);
+        // 2023-09-30 is a saturday, the following monday is in october
+        let month_end = Date::from_str("2023-09-30").unwrap();
+        assert_eq!(month_end.shift_to_business_day(BusinessDayConvention::ModifiedFollowing).to_string(), "2023-09-29");
+    }
+
+    #[test]
+    fn test_date_nth_weekday_of_month() {
+        // us dst starts on the second sunday of march
+        assert_eq!(Date::nth_weekday_of_month(2024, 3, Weekday::Sunday, 2).unwrap().to_string(), "2024-03-10");
+        assert_eq!(Date::nth_weekday_of_month(2024, 3, Weekday::Friday, 1).unwrap().to_string(), "2024-03-01");
+        assert_eq!(Date::nth_weekday_of_month(2024, 3, Weekday::Friday, 5).unwrap().to_string(), "2024-03-29");
+        assert_eq!(Date::nth_weekday_of_month(2024, 3, Weekday::Friday, -1).unwrap().to_string(), "2024-03-29");
+        assert_eq!(Date::nth_weekday_of_month(2024, 2, Weekday::Thursday, -1).unwrap().to_string(), "2024-02-29");
+        assert_eq!(Date::nth_weekday_of_month(2024, 2, Weekday::Thursday, -5).unwrap().to_string(), "2024-02-01");
+        assert_eq!(Date::nth_weekday_of_month(2023, 2, Weekday::Thursday, -5), None);
+        assert_eq!(Date::nth_weekday_of_month(2024, 4, Weekday::Friday, 5), None);
+        assert_eq!(Date::nth_weekday_of_month(2024, 13, Weekday::Friday, 1), None);
+        assert_eq!(Date::nth_weekday_of_month(u16::MAX, 12, Weekday::Monday, 1), None);
+        assert_eq!(Date::nth_weekday_of_month(10000, 1, Weekday::Monday, 1), None);
+        assert_eq!(Date::nth_weekday_of_month(0, 12, Weekday::Monday, -1), None);
+        assert_eq!(Date::nth_weekday_of_month(9999, 12, Weekday::Friday, -1).unwrap().to_string(), "9999-12-31");
+        assert_eq!(Date::nth_weekday_of_month(1, 1, Weekday::Monday, 1).unwrap(), Date::MIN);
+    }
+
+    #[test]
+    fn test_date_add_assign() {
+        let mut d = Date::from_str("2024-01-31").unwrap();
+        d += Duration::from_secs(86400);
+        assert_eq!(d.to_string(), "2024-02-01");
+        d -= Duration::from_secs(86400 * 2);
+        assert_eq!(d.to_string(), "2024-01-30");
+        d += Period { months: 1, ..Default::default() };
+        assert_eq!(d.to_string(), "2024-02-29");
+        d -= Period { hours: 1, ..Default::default() };
+        assert_eq!(d.to_string(), "2024-02-28");
+    }
+
+    #[test]
+    fn test_date_clamp() {
+        let min = Date::from_str("2023-01-01").unwrap();
+        let max = Date::from_str("2023-12-31").unwrap();
+        assert_eq!(Date::from_str("2024-06-01").unwrap().clamp(min, max).unwrap(), max);
+        assert_eq!(Date::from_str("2022-06-01").unwrap().clamp(min, max).unwrap(), min);
+        assert_eq!(Date::from_str("2023-06-01").unwrap().clamp(min, max).unwrap().to_string(), "2023-06-01");
+        assert!(min.clamp(max, min).is_err());
+    }
+
+    #[test]
+    fn test_date_with() {
+        let d = Date::from_str("2024-01-30").unwrap();
+        assert_eq!(d.with_month(2, MonthOverflow::Clamp).unwrap().to_string(), "2024-02-29");
+        assert_eq!(d.with_month(2, MonthOverflow::Rollover).unwrap().to_string(), "2024-03-01");
+        assert!(d.with_month(2, MonthOverflow::Error).is_err());
+        assert!(d.with_month(13, MonthOverflow::Clamp).is_err());
+        assert_eq!(d.with_day(1).unwrap().to_string(), "2024-01-01");
+        assert!(d.with_day(32).is_err());
+        let leap = Date::from_str("2024-02-29").unwrap();
+        assert_eq!(leap.with_year(2023, MonthOverflow::Clamp).unwrap().to_string(), "2023-02-28");
+        assert!(leap.with_year(2023, MonthOverflow::Error).is_err());
+        assert!(leap.with_year(10000, MonthOverflow::Clamp).is_err());
+    }
+
+    #[test]
+    fn test_date_day_of_year() {
+        assert_eq!(Date::from_str("2023-01-01").unwrap().day_of_year(), 1);
+        assert_eq!(Date::from_str("2023-03-01").unwrap().day_of_year(), 60);
+        assert_eq!(Date::from_str("2024-03-01").unwrap().day_of_year(), 61);
+        assert_eq!(Date::from_str("2023-12-31").unwrap().day_of_year(), 365);
+        assert_eq!(Date::from_str("2024-12-31").unwrap().day_of_year(), 366);
+    }
+
+    #[test]
+    fn test_date_iso_week() {
+        assert_eq!(Date::from_str("2023-01-01").unwrap().iso_week(), (2022, 52));
+        assert_eq!(Date::from_str("2023-01-02").unwrap().iso_week(), (2023, 1));
+        assert_eq!(Date::from_str("2021-01-03").unwrap().iso_week(), (2020, 53));
+        assert_eq!(Date::from_str("2024-12-30").unwrap().iso_week(), (2025, 1));
+        assert_eq!(Date::from_str("2026-12-31").unwrap().iso_week(), (2026, 53));
+        assert_eq!(Date::from_str("2023-08-20").unwrap().iso_week(), (2023, 33));
+    }
+
+    #[test]
+    fn test_date_weekday() {
+        assert_eq!(Date::from_str("1970-01-01").unwrap().weekday(), Weekday::Thursday);
+        assert_eq!(Date::from_str("1969-12-28").unwrap().weekday(), Weekday::Sunday);
+        assert_eq!(Date::from_str("2000-02-29").unwrap().weekday(), Weekday::Tuesday);
+        assert_eq!(Date::from_str("2023-08-21").unwrap().weekday(), Weekday::Monday);
+        assert_eq!(Date::from_str("9999-12-31").unwrap().weekday(), Weekday::Friday);
+    }
+
+    #[test]
+    fn test_date_next_prev_weekday() {
+        // a sunday
+        let d = Date::from_str("2023-08-20").unwrap();
+        assert_eq!(d.next_weekday(Weekday::Monday).unwrap().to_string(), "2023-08-21");
+        assert_eq!(d.next_weekday(Weekday::Sunday).unwrap().to_string(), "2023-08-27");
+        assert_eq!(d.next_weekday(Weekday::Saturday).unwrap().to_string(), "2023-08-26");
+        assert_eq!(d.prev_weekday(Weekday::Monday).unwrap().to_string(), "2023-08-14");
+        assert_eq!(d.prev_weekday(Weekday::Sunday).unwrap().to_string(), "2023-08-13");
+        assert_eq!(d.prev_weekday(Weekday::Saturday).unwrap().to_string(), "2023-08-19");
+        for n in 0..7 {
+            let day = Weekday::Monday.nth_next(n);
+            assert_eq!(d.next_weekday(day).unwrap().weekday(), day);
+            assert_eq!(d.prev_weekday(day).unwrap().weekday(), day);
+        }
+        assert_eq!(Date::MAX.next_weekday(Weekday::Monday), None);
+        assert_eq!(Date::MAX.prev_weekday(Weekday::Friday).unwrap().to_string(), "9999-12-24");
+        assert_eq!(Date::MIN.prev_weekday(Weekday::Monday), None);
+    }
+
+    #[test]
+    fn test_days_in_month() {
+        assert_eq!(days_in_month(2023, 1), 31);
+        assert_eq!(days_in_month(2023, 2), 28);
+        assert_eq!(days_in_month(2024, 2), 29);
+        assert_eq!(days_in_month(1900, 2), 28);
+        assert_eq!(days_in_month(2000, 2), 29);
+        assert_eq!(days_in_month(2023, 4), 30);
+        assert_eq!(days_in_month(2023, 0), 0);
+        assert_eq!(days_in_month(2023, 13), 0);
+        assert_eq!(days_in_year(2023), 365);
+        assert_eq!(days_in_year(2024), 366);
+        assert!(Date::from_str("2023-13-01").is_err());
+        assert!(Date::from_str("2023-02-29").is_err());
+    }
+
+    #[test]
+    fn test_is_leap_year() {
+        assert!(is_leap_year(2024));
+        assert!(is_leap_year(2000));
+        assert!(is_leap_year(1600));
+        assert!(is_leap_year(0));
+        assert!(!is_leap_year(2023));
+        assert!(!is_leap_year(1900));
+        assert!(!is_leap_year(2100));
+        let leap_years = (1970..2400).filter(|y| is_leap_year(*y)).count();
+        assert_eq!(leap_years, 104);
+        assert!(Date::from_str("2024-06-01").unwrap().is_leap_year());
+        assert!(!Date::from_str("2023-06-01").unwrap().is_leap_year());
+    }
+
+    #[test]
+    fn test_date_quarter() {
+        let quarters: Vec<u8> = (1..=12).map(|mon| Date { day: 1, mon, year: 2023 }.quarter()).collect();
+        assert_eq!(quarters, [1, 1, 1, 2, 2, 2, 3, 3, 3, 4, 4, 4]);
+        let d = Date::from_str("2023-08-20").unwrap();
+        assert_eq!(d.start_of_quarter().to_string(), "2023-07-01");
+        assert_eq!(d.end_of_quarter().to_string(), "2023-09-30");
+        assert_eq!(Date::from_str("2023-11-20").unwrap().end_of_quarter().to_string(), "2023-12-31");
+    }
+
+    #[test]
+    fn test_date_julian_day() {
+        assert_eq!(Date::from_str("1970-01-01").unwrap().to_julian_day(), 2440588);
+        assert_eq!(Date::from_str("2000-01-01").unwrap().to_julian_day(), 2451545);
+        assert_eq!(Date::from_str("1858-11-17").unwrap().to_julian_day(), 2400001);
+        assert_eq!(Date::from_julian_day(2451545).unwrap().to_string(), "2000-01-01");
+        assert_eq!(Date::from_julian_day(Date::MAX.to_julian_day()), Some(Date::MAX));
+        assert_eq!(Date::from_julian_day(Date::MAX.to_julian_day() + 1), None);
+        assert_eq!(Date::from_julian_day(i64::MIN), None);
+    }
+
+    #[test]
+    fn test_date_proleptic_gregorian() {
+        use crate::Weekday;
+        let weekday = |s: &str| Date::from_str(s).unwrap().weekday();
+        assert_eq!(weekday("0001-01-01"), Weekday::Monday);
+        assert_eq!(weekday("1582-10-15"), Weekday::Friday);
+        assert_eq!(weekday("1582-10-04"), Weekday::Monday);
+        assert_eq!(weekday("1000-01-01"), Weekday::Wednesday);
+        for (year, leap) in [(4, true), (100, false), (400, true), (1000, false), (1200, true), (1500, false)] {
+            assert_eq!(Date::new(year, 2, 29).is_some(), leap, "{}", year);
+        }
+        assert_eq!(Date::from_str("1582-10-10").unwrap().to_unix_day() + 5, Date::from_str("1582-10-15").unwrap().to_unix_day());
+
+        let julian = |y, m, d| Date::from_julian_calendar(y, m, d).map(|d| d.to_string());
+        assert_eq!(julian(1582, 10, 5).unwrap(), "1582-10-15");
+        assert_eq!(julian(1582, 10, 4).unwrap(), "1582-10-14");
+        assert_eq!(julian(1900, 2, 29).unwrap(), "1900-03-13");
+        assert_eq!(julian(1, 1, 3).unwrap(), "0001-01-01");
+        assert_eq!(julian(1, 1, 2).unwrap_err().to_string(), "Rbatis Error: OutOfRangeDate");
+        assert_eq!(julian(1900, 2, 30).unwrap_err().to_string(), "Rbatis Error: OutOfRangeDay");
+        assert!(julian(1900, 13, 1).is_err());
+        assert_eq!(Date::MIN.to_julian_calendar(), (1, 1, 3));
+        assert_eq!(Date::MAX.to_julian_calendar(), (9999, 10, 19));
+        assert_eq!(Date::from_str("1582-10-15").unwrap().to_julian_calendar(), (1582, 10, 5));
+        assert_eq!(Date::from_str("2023-08-20").unwrap().to_julian_calendar(), (2023, 8, 7));
+        for days in (Date::MIN.to_unix_day()..=Date::MAX.to_unix_day()).step_by(97) {
+            let d = Date::from_unix_day(days).unwrap();
+            let (y, m, day) = d.to_julian_calendar();
+            assert_eq!(Date::from_julian_calendar(y, m, day).unwrap(), d);
+        }
+    }
+
+    #[test]
+    fn test_date_bits() {
+        let dates = ["1970-01-01", "1999-12-31", "2000-01-01", "2000-02-29", "2023-08-20", "9999-12-31"];
+        let dates: Vec<Date> = dates.iter().map(|s| Date::from_str(s).unwrap()).collect();
+        for w in dates.windows(2) {
+            assert!(w[0].to_bits() < w[1].to_bits());
+        }
+        for d in dates {
+            assert_eq!(Date::from_bits(d.to_bits()), Some(d));
+        }
+        assert_eq!(Date::from_str("2023-08-20").unwrap().to_bits(), 2023 << 9 | 8 << 5 | 20);
+        assert_eq!(Date::from_bits(2023 << 9 | 2 << 5 | 29), None);
+        assert_eq!(Date::from_bits(2023 << 9 | 13 << 5 | 1), None);
+        assert_eq!(Date::from_bits(10000 << 9 | 1 << 5 | 1), None);
+        assert_eq!(Date::from_bits(1 << 5 | 1), None);
+    }
+
+    #[test]
+    fn test_date_parse_with() {
+        assert_eq!(Date::parse_with("2024-02-29", Validation::Strict).unwrap(), Date::from_str("2024-02-29").unwrap());
+        assert!(Date::parse_with("2023-02-29", Validation::Strict).is_err());
+        let d = Date::parse_with("2023-02-30", Validation::Relaxed).unwrap();
+        assert_eq!((d.mon, d.day), (2, 30));
+        assert!(Date::parse_with("2023-02-32", Validation::Relaxed).is_err());
+        assert!(Date::parse_with("2023-13-01", Validation::Relaxed).is_err());
+        assert!(Date::parse_with("2023-02-3x", Validation::Relaxed).is_err());
+    }
+
+    #[test]
+    fn test_date_parse_unchecked() {
+        for v in ["1970-01-01", "2000-02-29", "9999-12-31"] {
+            assert_eq!(Date::parse_unchecked(v).unwrap(), Date::from_str(v).unwrap());
+        }
+        let d = Date::parse_unchecked("2023-13-45").unwrap();
+        assert_eq!((d.year, d.mon, d.day), (2023, 13, 45));
+        assert!(Date::parse_unchecked("2023-08").is_err());
+        // garbage gives unspecified fields, never a panic
+        let _ = Date::parse_unchecked("+2023-08-2-T13:M0");
+        for c in (0..128u8).map(char::from).chain(['é', '€']) {
+            let _ = Date::parse_unchecked(&c.to_string().repeat(10));
+        }
+    }
+
+    #[test]
+    fn test_date_civil_days() {
+        // every day of 0001...9999 follows the previous one
+        let mut prev = Date::from_days_since_epoch(-719162);
+        assert_eq!(prev.to_string(), "0001-01-01");
+        assert_eq!(prev.day_of_year(), 1);
+        for days in -719161..=2932896 {
+            let d = Date::from_days_since_epoch(days);
+            assert_eq!(d.to_unix_day(), days);
+            if prev.day < days_in_month(prev.year, prev.mon) {
+                assert_eq!((d.year, d.mon, d.day), (prev.year, prev.mon, prev.day + 1));
+                assert_eq!(d.day_of_year(), prev.day_of_year() + 1);
+            } else if prev.mon < 12 {
+                assert_eq!((d.year, d.mon, d.day), (prev.year, prev.mon + 1, 1));
+                assert_eq!(d.day_of_year(), prev.day_of_year() + 1);
+            } else {
+                assert_eq!((d.year, d.mon, d.day), (prev.year + 1, 1, 1));
+                assert_eq!(prev.day_of_year(), days_in_year(prev.year));
+                assert_eq!(d.day_of_year(), 1);
+            }
+            prev = d;
+        }
+        assert_eq!(prev, Date::MAX);
+    }
+
+    #[test]
+    fn test_date_unix_day() {
+        assert_eq!(Date::from_str("1970-01-01").unwrap().to_unix_day(), 0);
+        assert_eq!(Date::from_str("1969-12-31").unwrap().to_unix_day(), -1);
+        assert_eq!(Date::from_str("2023-08-20").unwrap().to_unix_day(), 19589);
+        assert_eq!(Date::from_unix_day(19589).unwrap().to_string(), "2023-08-20");
+        assert_eq!(Date::from_unix_day(0), Some(Date::UNIX_EPOCH));
+        assert_eq!(Date::from_unix_day(-1).unwrap().to_string(), "1969-12-31");
+        assert_eq!(Date::from_unix_day(Date::MIN.to_unix_day() - 1), None);
+        assert_eq!(Date::from_unix_day(Date::MAX.to_unix_day() + 1), None);
+    }
+
+    #[test]
+    fn test_date_week_of_month() {
+        // 2023-10-01 is a sunday
+        let d = |day| Date { day, mon: 10, year: 2023 };
+        assert_eq!(d(1).week_of_month(Weekday::Monday), 1);
+        assert_eq!(d(2).week_of_month(Weekday::Monday), 2);
+        assert_eq!(d(31).week_of_month(Weekday::Monday), 6);
+        assert_eq!(d(1).week_of_month(Weekday::Sunday), 1);
+        assert_eq!(d(7).week_of_month(Weekday::Sunday), 1);
+        assert_eq!(d(8).week_of_month(Weekday::Sunday), 2);
+        assert_eq!(d(31).week_of_month(Weekday::Sunday), 5);
+    }
+
+    #[test]
+    fn test_date_is_weekend() {
+        let fri = Date::from_str("2023-09-01").unwrap();
+        let sun = Date::from_str("2023-09-03").unwrap();
+        assert!(!fri.is_weekend());
+        assert!(fri.is_weekday());
+        assert!(sun.is_weekend());
+        assert!(fri.is_weekend_in(Weekend::FRIDAY_SATURDAY));
+        assert!(!sun.is_weekend_in(Weekend::FRIDAY_SATURDAY));
+    }
+
+    #[test]
+    fn test_date_easter() {
+        assert_eq!(Date::easter(2023).unwrap().to_string(), "2023-04-09");
+        assert_eq!(Date::easter(2024).unwrap().to_string(), "2024-03-31");
+        assert_eq!(Date::easter(2025).unwrap().to_string(), "2025-04-20");
+        assert_eq!(Date::easter(2038).unwrap().to_string(), "2038-04-25");
+        assert_eq!(Date::easter(1818).unwrap().to_string(), "1818-03-22");
+        assert_eq!(Date::orthodox_easter(2023).unwrap().to_string(), "2023-04-16");
+        assert_eq!(Date::orthodox_easter(2024).unwrap().to_string(), "2024-05-05");
+        assert_eq!(Date::orthodox_easter(2025).unwrap().to_string(), "2025-04-20");
+        assert_eq!(Date::orthodox_easter(2101).unwrap().to_string(), "2101-04-24");
+        assert_eq!(Date::easter(1).unwrap().to_string(), "0001-04-01");
+        assert_eq!(Date::easter(9999).unwrap().to_string(), "9999-03-28");
+        // julian 0001-03-27, 2 days ahead of the proleptic gregorian calendar
+        assert_eq!(Date::orthodox_easter(1).unwrap().to_string(), "0001-03-25");
+        assert_eq!(Date::orthodox_easter(9999).unwrap().to_string(), "9999-06-27");
+        for year in [0, 10000, u16::MAX] {
+            assert_eq!(Date::easter(year), None);
+            assert_eq!(Date::orthodox_easter(year), None);
+        }
+        for year in (1900..2100).chain([1, 9999]) {
+            assert_eq!(Date::easter(year).unwrap().weekday(), Weekday::Sunday);
+            assert_eq!(Date::orthodox_easter(year).unwrap().weekday(), Weekday::Sunday);
+        }
+    }
+
+    #[test]
+    fn test_date_ord() {
+        let mut dates: Vec<Date> = ["2023-08-20", "2022-12-31", "2023-01-31", "2023-02-01"]
+            .iter()
+            .map(|s| Date::from_str(s).unwrap())
+            .collect();
+        dates.sort();
+        let sorted: Vec<String> = dates.iter().map(|d| d.to_string()).collect();
+        assert_eq!(sorted, ["2022-12-31", "2023-01-31", "2023-02-01", "2023-08-20"]);
+        let mut map = std::collections::BTreeMap::new();
+        map.insert(dates[3], 'b');
+        map.insert(dates[0], 'a');
+        assert_eq!(map.values().collect::<String>(), "ab");
+        assert!(dates[1] < dates[2]);
+    }
+
+    #[test]
+    fn test_date_short_year() {
+        let d = Date::try_new(99, 1, 2).unwrap();
+        assert_eq!(d.to_string(), "0099-01-02");
+        assert_eq!(Date::from_str("0099-01-02").unwrap(), d);
+        assert_eq!(crate::DateTime::new(d, crate::Time::MIDNIGHT, 0).to_rfc3339(), "0099-01-02T00:00:00.000000Z");
+    }
+
+    #[test]
+    #[should_panic(expected = "year must be at most 9999")]
+    fn test_date_long_year() {
+        let _ = Date { day: 1, mon: 1, year: 12345 }.to_string();
+    }
+
+    #[test]
+    fn test_date_try_new() {
+        assert_eq!(Date::try_new(2024, 2, 29).unwrap(), Date::from_str("2024-02-29").unwrap());
+        assert_eq!(Date::try_new(2023, 2, 29).unwrap_err().to_string(), "Rbatis Error: OutOfRangeDay");
+        assert_eq!(Date::try_new(2023, 13, 1).unwrap_err().to_string(), "Rbatis Error: OutOfRangeMonth");
+        assert_eq!(Date::try_new(10000, 1, 1).unwrap_err().to_string(), "Rbatis Error: OutOfRangeYear");
+        assert_eq!(Date::try_new(0, 1, 1).unwrap_err(), Error::OutOfRange { field: Field::Year });
+        assert_eq!(Date::from_str("0000-01-01").unwrap_err(), Error::OutOfRange { field: Field::Year });
+        assert_eq!(Date::parse_with("0000-01-01", Validation::Relaxed).unwrap_err(), Error::OutOfRange { field: Field::Year });
+        assert_eq!(Date::try_new(1, 1, 1).unwrap(), Date::MIN);
+        assert!(Date::try_new(2023, 8, 0).is_err());
+    }
+
+    #[test]
+    fn test_date_const() {
+        const D: Date = Date::new(2024, 2, 29).unwrap();
+        const P: Option<Date> = Date::parse_const("2024-02-29");
+        assert_eq!(D, Date::from_str("2024-02-29").unwrap());
+        assert_eq!(P, Some(D));
+        assert_eq!(Date::new(2023, 2, 29), None);
+        assert_eq!(Date::new(2023, 13, 1), None);
+        assert_eq!(Date::new(2023, 1, 0), None);
+        assert_eq!(Date::new(0, 1, 1), None);
+        assert_eq!(Date::parse_const("0000-01-01"), None);
+        assert_eq!(Date::parse_const("2024-02-29 "), None);
+        assert_eq!(Date::parse_const("2024/02/29"), None);
+        assert_eq!(Date::parse_const("2024-0a-29"), None);
+    }
+
+    #[test]
+    #[cfg(feature = "arrayvec")]
+    fn test_date_array_string() {
+        let d = Date::from_str("2023-08-20").unwrap();
+        assert_eq!(d.to_array_string().as_str(), d.to_string());
+    }
 }
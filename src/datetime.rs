@@ -1,24 +1,156 @@
-use std::cmp;
-use std::fmt::{self, Display, Formatter, Pointer};
-use std::ops::{Add, Deref, Sub};
-use std::str::FromStr;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use core::cmp;
+use core::fmt::{self, Display, Formatter};
+use core::hash::{Hash, Hasher};
+use core::ops::{Add, AddAssign, Sub, SubAssign};
+use core::str::FromStr;
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::cell::Cell;
+use core::sync::atomic::{AtomicI32, AtomicPtr, Ordering as AtomicOrdering};
+#[cfg(feature = "std")]
+use std::sync::atomic::{AtomicI64, AtomicU64};
+#[cfg(feature = "std")]
+use std::time::{SystemTime, UNIX_EPOCH};
+use alloc::string::String;
+#[cfg(feature = "std")]
 use once_cell::sync::Lazy;
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use crate::{Date, Time};
-use crate::error::Error as Error;
+use crate::{Date, Every, MonthOverflow, Period, SignedDuration, Time, Validation, Weekday, Weekend, const_digits, get_digit};
+use crate::digits::{put2, put4, put6};
+use crate::error::{Error, Field};
+#[cfg(feature = "std")]
 use crate::sys::Timespec;
 
 /// Obtain the offset of Utc time and Local time in seconds, using Lazy only once to improve performance
+#[cfg(feature = "std")]
+#[deprecated(note = "never refreshed after the first use, use `DateTime::local_offset`")]
 pub static GLOBAL_OFFSET: Lazy<i32> = Lazy::new(|| Timespec::now().local().tm_utcoff);
 
+/// The local offset of `DateTime::now`, valid once `LOCAL_OFFSET_EXPIRES` is not 0
+#[cfg(feature = "std")]
+static LOCAL_OFFSET: AtomicI32 = AtomicI32::new(0);
+
+/// The coarse clock nanos at which the local offset is queried again, 0 before the first query
+/// and `u64::MAX` without a refresh interval
+#[cfg(feature = "std")]
+static LOCAL_OFFSET_EXPIRES: AtomicU64 = AtomicU64::new(0);
+
+/// How often the local offset is queried again in nanoseconds, `u64::MAX` for never
+#[cfg(feature = "std")]
+static OFFSET_REFRESH_NANOS: AtomicU64 = AtomicU64::new(u64::MAX);
+
+/// How long `DateTime::now_coarse` reuses a reading, in nanoseconds
+#[cfg(feature = "std")]
+static COARSE_INTERVAL_NANOS: AtomicU64 = AtomicU64::new(1_000_000);
+
+/// The unix micros `DateTime::now_monotonic` returned last, `i64::MIN` before the first call
+#[cfg(feature = "std")]
+static MONOTONIC_LAST: AtomicI64 = AtomicI64::new(i64::MIN);
+
+#[cfg(feature = "std")]
+thread_local! {
+    /// the last reading of `now_coarse` on this thread and the coarse clock nanos it expires at
+    static COARSE_NOW: Cell<Option<(u64, DateTime)>> = const { Cell::new(None) };
+}
+
+/// The offset of `set_default_offset`, `NO_DEFAULT_OFFSET` when there is none
+static DEFAULT_OFFSET: AtomicI32 = AtomicI32::new(NO_DEFAULT_OFFSET);
+
+const NO_DEFAULT_OFFSET: i32 = i32::MIN;
+
+/// make `DateTime::now` and `Date::today` use `offset` seconds east of utc instead of the
+/// local offset of the os, and `from_str` and `parse_with` assume it for input without an
+/// offset instead of utc, for services that run in a fixed business zone wherever they are
+/// hosted. fails with `OutOfRangeOffset` as `with_offset`.
+///
+/// the setting is global to the process: every `DateTime::from_str` changes with it, those
+/// of serde and of the text decoders of the database integrations too, also in other
+/// libraries. set it once at startup in a binary, never from a library, and use
+/// `parse_in_offset` or `now_in_offset` for a single call instead
+pub fn set_default_offset(offset: i32) -> Result<(), Error> {
+    DEFAULT_OFFSET.store(DateTime::check_offset(offset)?, AtomicOrdering::Relaxed);
+    Ok(())
+}
+
+/// go back to the local offset for `now` and utc for parsing, see `set_default_offset`
+pub fn clear_default_offset() {
+    DEFAULT_OFFSET.store(NO_DEFAULT_OFFSET, AtomicOrdering::Relaxed);
+}
+
+/// the offset of `set_default_offset`, `None` if it was not set
+pub fn default_offset() -> Option<i32> {
+    match DEFAULT_OFFSET.load(AtomicOrdering::Relaxed) {
+        NO_DEFAULT_OFFSET => None,
+        offset => Some(offset),
+    }
+}
+
+/// The `fn() -> i128` of `set_now_provider`, null when there is none
+static NOW_PROVIDER: AtomicPtr<()> = AtomicPtr::new(core::ptr::null_mut());
+
+/// read the current time from `provider`, nanoseconds since the epoch, instead of the system
+/// clock. for embedded targets, kernels and wasm hosts without `SystemTime`, where it makes
+/// `DateTime::now`, `now_utc` and `Date::today` usable without the `std` feature
+pub fn set_now_provider(provider: fn() -> i128) {
+    NOW_PROVIDER.store(provider as *mut (), AtomicOrdering::Release);
+}
+
+/// go back to the system clock, see `set_now_provider`
+pub fn clear_now_provider() {
+    NOW_PROVIDER.store(core::ptr::null_mut(), AtomicOrdering::Release);
+}
+
+fn now_provider() -> Option<fn() -> i128> {
+    let p = NOW_PROVIDER.load(AtomicOrdering::Acquire);
+    // SAFETY: only `set_now_provider` stores a non-null pointer, a `fn() -> i128`
+    (!p.is_null()).then(|| unsafe { core::mem::transmute::<*mut (), fn() -> i128>(p) })
+}
+
+/// Seconds from the epoch to 10000-01-01 00:00:00, the first instant that can not be represented
+const MAX_SECS: u64 = 253402300800;
+
+/// Seconds from 0001-01-01 00:00:00 to the epoch, negative
+pub(crate) const MIN_SECS: i64 = -62135596800;
+
+/// Seconds from 1900-01-01 00:00:00, the start of ntp era 0, to the epoch
+const NTP_UNIX_SECS: i64 = 2208988800;
+
+/// Seconds from 1601-01-01 00:00:00, the start of windows FILETIME, to the epoch
+const FILETIME_UNIX_SECS: i64 = 11644473600;
+
+/// Whether the end of a period is its last representable instant or the start of the next period
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Boundary {
+    /// the last representable instant, for `<=` comparisons: 2023-08-31 23:59:59.999999
+    Inclusive,
+    /// the first instant of the next period, for `<` comparisons: 2023-09-01 00:00:00.000000
+    Exclusive,
+}
+
 /// Log timestamp type.
 ///
+/// The fields hold microseconds, the precision of every `DateTime`. Nanosecond sources,
+/// `SystemTime`, protobuf and msgpack timestamps or `from_unix_timestamp_nanos`, lose the
+/// nanoseconds past the last microsecond, rounded toward the past;
+/// `from_timestamp_with(.., PrecisionLoss::Reject)` fails instead.
+///
 /// Parse using `FromStr` impl.
 /// Format using the `Display` trait.
 /// Convert timestamp into/from `SytemTime` to use.
 /// Supports comparsion and sorting.
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+/// `==`, `<` and `Hash` compare the instant, so 09:00+01:00 equals 08:00Z and the two are
+/// the same `HashMap` key; `eq_fields` also compares the offset the fields are in.
+/// The fields take 15 bytes, `size_of::<DateTime>()` is 16 with the padding.
+/// With the `rkyv` feature the archived form `ArchivedDateTime` holds the same fields
+/// little-endian in 16 bytes, `rkyv::access` rejects fields out of their ranges.
+/// Serde writes an RFC 3339 string to human-readable formats and the tuple
+/// `(i64 microseconds since the epoch, i32 offset seconds)` to binary ones, 12 bytes in bincode.
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "diesel", derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow))]
+#[cfg_attr(feature = "diesel", diesel(sql_type = diesel::sql_types::Timestamptz))]
+#[cfg_attr(feature = "diesel", diesel(sql_type = diesel::sql_types::Timestamp))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize), rkyv(derive(Debug), bytecheck(verify)))]
 pub struct DateTime {
     /// 0...999999
     pub micro: u32,
@@ -32,33 +164,1114 @@ pub struct DateTime {
     pub day: u8,
     /// 1...12
     pub mon: u8,
-    /// 1970...9999
+    /// 1...9999
     pub year: u16,
+    /// the utc offset the fields are expressed in, in seconds east of utc, e.g. 28800 for +08:00
+    pub offset: i32,
 }
 
 impl DateTime {
-    ///utc time
+    /// the earliest representable datetime, 0001-01-01 00:00:00.000000
+    pub const MIN: DateTime = DateTime {
+        micro: 0,
+        sec: 0,
+        min: 0,
+        hour: 0,
+        day: 1,
+        mon: 1,
+        year: 1,
+        offset: 0,
+    };
+
+    /// the unix epoch, 1970-01-01 00:00:00.000000 at utc
+    pub const UNIX_EPOCH: DateTime = DateTime {
+        micro: 0,
+        sec: 0,
+        min: 0,
+        hour: 0,
+        day: 1,
+        mon: 1,
+        year: 1970,
+        offset: 0,
+    };
+
+    /// the largest offset from utc in seconds `with_offset` accepts either way, 14:59:59
+    pub const MAX_OFFSET: i32 = 15 * 3600 - 1;
+
+    /// the latest representable datetime, 9999-12-31 23:59:59.999999
+    pub const MAX: DateTime = DateTime {
+        micro: 999999,
+        sec: 59,
+        min: 59,
+        hour: 23,
+        day: 31,
+        mon: 12,
+        year: 9999,
+        offset: 0,
+    };
+
+    /// the datetime of `date` and `time` as wall-clock fields at `offset` seconds east of utc.
+    /// usable in consts together with `Date::new` and `Time::new`
+    pub const fn new(date: Date, time: Time, offset: i32) -> Self {
+        Self {
+            micro: time.micro,
+            sec: time.sec,
+            min: time.min,
+            hour: time.hour,
+            day: date.day,
+            mon: date.mon,
+            year: date.year,
+            offset,
+        }
+    }
+
+    /// the utc datetime of the fields, checked as `Date::try_new` and `Time::try_new`.
+    /// `DateTime::new(Date::try_new(..)?, Time::try_new(..)?, offset)` for another offset
+    pub fn try_new(year: u16, mon: u8, day: u8, hour: u8, min: u8, sec: u8, micro: u32) -> Result<Self, Error> {
+        Ok(Self::new(Date::try_new(year, mon, day)?, Time::try_new(hour, min, sec, micro)?, 0))
+    }
+
+    /// parse exactly "YYYY-MM-DDTHH:MM:SS" in a const context, `T` may also be `t` or a space,
+    /// followed by an optional fraction of 1 to 9 digits, truncated to microseconds, and an
+    /// optional `Z` or `±hh:mm` offset that defaults to utc. `None` if `s` is anything else
+    ///
+    /// ```
+    /// use fastdate::DateTime;
+    /// const LAUNCH: DateTime = DateTime::parse_const("2023-08-20T13:02:03+08:00").unwrap();
+    /// assert_eq!(LAUNCH.offset, 28800);
+    /// ```
+    pub const fn parse_const(s: &str) -> Option<Self> {
+        let bytes = s.as_bytes();
+        let date = match Date::parse_const_partial(bytes) {
+            Some(date) => date,
+            None => return None,
+        };
+        if bytes.len() < 11 || !matches!(bytes[10], b'T' | b't' | b' ') {
+            return None;
+        }
+        let (time, len) = match Time::parse_const_partial(bytes, 11) {
+            Some(t) => t,
+            None => return None,
+        };
+        let rest = bytes.split_at(11 + len).1;
+        let offset = match rest {
+            [] | [b'Z'] | [b'z'] => 0,
+            [sign @ (b'+' | b'-'), _, _, b':', _, _] | [sign @ (b'+' | b'-'), _, _, b':', _, _, b':', _, _] => {
+                let s = if rest.len() == 9 { const_digits(rest, 7, 2) } else { Some(0) };
+                match (const_digits(rest, 1, 2), const_digits(rest, 4, 2), s) {
+                    (Some(h), Some(m), Some(s)) if h <= 14 && m <= 59 && s <= 59 => {
+                        let secs = (h * 3600 + m * 60 + s) as i32;
+                        if *sign == b'-' { -secs } else { secs }
+                    }
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        };
+        Some(Self::new(date, time, offset))
+    }
+
+    ///utc time, same as `now_utc`
     pub fn utc() -> Self {
-        Self::from(SystemTime::now())
+        Self::now_utc()
     }
-    ///local zone time
+    ///local zone time, in the offset of `set_default_offset` if there is one
     pub fn now() -> Self {
-        let offset = GLOBAL_OFFSET.deref().clone();
-        if offset > 0 {
-            Self::from(SystemTime::now() + Duration::from_secs(offset as u64))
-        } else {
-            Self::from(SystemTime::now() - Duration::from_secs(offset as u64))
+        Self::now_utc().to_offset_secs(Self::now_offset())
+    }
+
+    /// the current time in `offset` seconds east of utc, whatever the default offset.
+    /// fails with `OutOfRangeOffset` as `with_offset`
+    pub fn now_in_offset(offset: i32) -> Result<Self, Error> {
+        Self::now_utc().with_offset(offset)
+    }
+
+    /// the offset of `now`, that of `set_default_offset` or else `local_offset`, utc without
+    /// the `std` feature
+    pub(crate) fn now_offset() -> i32 {
+        #[cfg(feature = "std")]
+        return default_offset().unwrap_or_else(Self::local_offset);
+        #[cfg(not(feature = "std"))]
+        default_offset().unwrap_or(0)
+    }
+
+    /// the current time with a zero offset, that of an installed `MockClock` or else of the
+    /// `set_now_provider` hook. without the `std` feature there is no system clock to fall
+    /// back to and it panics if no provider is set
+    pub fn now_utc() -> Self {
+        #[cfg(feature = "std")]
+        if let Some(now) = crate::clock::mocked_now() {
+            return now.to_offset_secs(0);
+        }
+        if let Some(provider) = now_provider() {
+            return Self::from_unix_nanos(provider()).expect("date must be between the years 0001 and 9999");
+        }
+        #[cfg(feature = "std")]
+        return Self::from(crate::sys::system_now());
+        #[cfg(not(feature = "std"))]
+        panic!("no clock without the std feature, see fastdate::set_now_provider")
+    }
+
+    /// the current time in the offset of `local_offset`
+    #[cfg(feature = "std")]
+    pub fn now_local() -> Self {
+        Self::now_utc().to_offset_secs(Self::local_offset())
+    }
+
+    /// midnight of `Date::today` in the offset of `now`
+    pub fn start_of_today() -> Self {
+        Self::now().start_of_day()
+    }
+
+    /// the local utc offset in seconds, queried from the os on the first call and then cached.
+    /// the cache is only queried again by `refresh_offset` or past the interval of
+    /// `set_offset_refresh_interval`, e.g. to pick up a daylight saving change
+    #[cfg(feature = "std")]
+    pub fn local_offset() -> i32 {
+        let expires = LOCAL_OFFSET_EXPIRES.load(AtomicOrdering::Acquire);
+        if expires == 0 || (expires != u64::MAX && crate::sys::coarse_monotonic_nanos() >= expires) {
+            return Self::refresh_offset();
         }
+        LOCAL_OFFSET.load(AtomicOrdering::Relaxed)
+    }
+
+    /// query the local utc offset from the os again and cache it for `local_offset` and `now`
+    #[cfg(feature = "std")]
+    pub fn refresh_offset() -> i32 {
+        let offset = Timespec::now().local().tm_utcoff;
+        LOCAL_OFFSET.store(offset, AtomicOrdering::Relaxed);
+        let interval = OFFSET_REFRESH_NANOS.load(AtomicOrdering::Relaxed);
+        let expires = match interval {
+            u64::MAX => u64::MAX,
+            _ => crate::sys::coarse_monotonic_nanos().saturating_add(interval).max(1),
+        };
+        LOCAL_OFFSET_EXPIRES.store(expires, AtomicOrdering::Release);
+        offset
+    }
+
+    /// query the cached local offset again once it is `interval` old, `None` to only refresh
+    /// with `refresh_offset`, the default
+    #[cfg(feature = "std")]
+    pub fn set_offset_refresh_interval(interval: Option<Duration>) {
+        let nanos = interval.map_or(u64::MAX, |d| u64::try_from(d.as_nanos()).unwrap_or(u64::MAX));
+        OFFSET_REFRESH_NANOS.store(nanos, AtomicOrdering::Relaxed);
+        Self::refresh_offset();
+    }
+
+    /// local zone time that may be up to the coarse interval old, 1ms unless changed with
+    /// `set_coarse_interval`. each thread reuses its last `now()` until a monotonic clock that
+    /// is read without a syscall passes the interval, for loggers that stamp many lines and do
+    /// not need exact timestamps. that clock may only advance every few milliseconds on linux,
+    /// which then bounds how fresh a reading is
+    #[cfg(feature = "std")]
+    pub fn now_coarse() -> Self {
+        let tick = crate::sys::coarse_monotonic_nanos();
+        COARSE_NOW.with(|cached| match cached.get() {
+            Some((expires, now)) if tick < expires => now,
+            _ => {
+                let now = Self::now();
+                let interval = COARSE_INTERVAL_NANOS.load(AtomicOrdering::Relaxed);
+                cached.set(Some((tick.saturating_add(interval), now)));
+                now
+            }
+        })
     }
 
+    /// local zone time from the system clock read once, advanced by a monotonic clock since.
+    /// successive calls on any thread are strictly ordered, a call within the same microsecond
+    /// as the last one returns it plus 1us, and ntp slews or changes of the system clock
+    /// after the first call are not seen
+    #[cfg(feature = "std")]
+    pub fn now_monotonic() -> Self {
+        let micros = crate::sys::monotonic_unix_micros();
+        let last = MONOTONIC_LAST
+            .fetch_update(AtomicOrdering::Relaxed, AtomicOrdering::Relaxed, |last| {
+                Some(micros.max(last.saturating_add(1)))
+            })
+            .unwrap_or_else(|last| last);
+        let micros = micros.max(last.saturating_add(1));
+        let offset = Self::now_offset();
+        Self::from_wall_micros(micros + offset as i64 * 1_000_000, offset)
+    }
+
+    /// set how long `now_coarse` reuses a reading on every thread, `Duration::ZERO` reads the
+    /// clock on each call
+    #[cfg(feature = "std")]
+    pub fn set_coarse_interval(interval: Duration) {
+        let nanos = u64::try_from(interval.as_nanos()).unwrap_or(u64::MAX);
+        COARSE_INTERVAL_NANOS.store(nanos, AtomicOrdering::Relaxed);
+    }
+
+    #[allow(clippy::should_implement_trait)]
     pub fn add(self, d: Duration) -> Self {
-        let systime = SystemTime::from(self) + d;
-        Self::from(systime)
+        let micros = self.wall_micros() as i128 + d.as_micros() as i128;
+        self.with_wall_micros(micros).expect("date must be between the years 0001 and 9999")
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn sub(self, d: Duration) -> Self {
-        let systime = SystemTime::from(self) - d;
-        Self::from(systime)
+        // round toward the past, as for a SystemTime 0.5us before the epoch
+        let micros = self.wall_micros() as i128 - d.as_nanos().div_ceil(1000) as i128;
+        self.with_wall_micros(micros).expect("date must be between the years 0001 and 9999")
+    }
+
+    /// add a duration, returning `None` instead of panicking
+    /// if the result is out of the range 0001...9999
+    pub fn checked_add(self, d: Duration) -> Option<Self> {
+        self.with_wall_micros(self.wall_micros() as i128 + d.as_micros() as i128)
+    }
+
+    /// sub a duration, returning `None` instead of panicking
+    /// if the result is out of the range 0001...9999
+    pub fn checked_sub(self, d: Duration) -> Option<Self> {
+        self.with_wall_micros(self.wall_micros() as i128 - d.as_nanos().div_ceil(1000) as i128)
+    }
+
+    /// add a signed duration, sub-microsecond parts rounded toward the past, `None` if the
+    /// result is out of the range 0001...9999
+    pub fn checked_add_signed(self, d: SignedDuration) -> Option<Self> {
+        self.with_wall_micros(self.wall_micros() as i128 + d.as_nanos().div_euclid(1000))
+    }
+
+    /// add a duration, clamping to `DateTime::MAX` instead of overflowing
+    pub fn saturating_add(self, d: Duration) -> Self {
+        self.checked_add(d).unwrap_or(Self::MAX)
+    }
+
+    /// sub a duration, clamping to `DateTime::MIN` instead of overflowing
+    pub fn saturating_sub(self, d: Duration) -> Self {
+        self.checked_sub(d).unwrap_or(Self::MIN)
+    }
+
+    /// add `n` months (negative to go back) keeping the time of day, resolving a day
+    /// past the end of the target month with `policy`
+    pub fn add_months(self, n: i32, policy: MonthOverflow) -> Result<Self, Error> {
+        Ok(self.with_date(Date::from(self).add_months(n, policy)?))
+    }
+
+    /// the calendar difference `self - other` in years, months, days and time of day,
+    /// negative when `self` is earlier than `other`. `other` is first converted to the offset of `self`
+    pub fn period_since(self, other: DateTime) -> Period {
+        let other = other.to_offset_secs(self.offset);
+        if self >= other {
+            Period::between(other, self)
+        } else {
+            Period::between(self, other).negate()
+        }
+    }
+
+    /// whole days from `other` to `self`, truncated toward zero,
+    /// so 23 hours is 0 days and -25 hours is -1 day
+    pub fn diff_days(self, other: DateTime) -> i64 {
+        (self - other).as_secs() / 86400
+    }
+
+    /// whole weeks from `other` to `self`, truncated toward zero
+    pub fn diff_weeks(self, other: DateTime) -> i64 {
+        self.diff_days(other) / 7
+    }
+
+    /// whole calendar months from `other` to `self`, truncated toward zero.
+    /// a month is complete once the same day of month and time of day is reached,
+    /// clamped to the end of shorter months, so 01-31 to 02-28 is one month
+    pub fn diff_months(self, other: DateTime) -> i64 {
+        let p = self.period_since(other);
+        p.years as i64 * 12 + p.months as i64
+    }
+
+    /// whole calendar years from `other` to `self`, truncated toward zero
+    pub fn diff_years(self, other: DateTime) -> i64 {
+        self.period_since(other).years as i64
+    }
+
+    /// midnight of the same day.
+    ///
+    /// the start_of_* helpers truncate the wall-clock fields, so the result stays in the
+    /// offset `self` is expressed in: `DateTime::now()` truncates at local midnight,
+    /// `DateTime::utc()` at UTC midnight
+    pub fn start_of_day(self) -> Self {
+        Self {
+            micro: 0,
+            sec: 0,
+            min: 0,
+            hour: 0,
+            ..self
+        }
+    }
+
+    /// midnight of the monday of the same week
+    pub fn start_of_week(self) -> Self {
+        let date = Date::from(self);
+        let monday = Date::from_days_since_epoch(date.to_unix_day() - date.weekday_from_monday() as i64);
+        Self {
+            day: monday.day,
+            mon: monday.mon,
+            year: monday.year,
+            ..self.start_of_day()
+        }
+    }
+
+    /// midnight of the first day of the same month
+    pub fn start_of_month(self) -> Self {
+        Self {
+            day: 1,
+            ..self.start_of_day()
+        }
+    }
+
+    /// midnight of the first day of the same quarter
+    pub fn start_of_quarter(self) -> Self {
+        Self {
+            mon: self.quarter() * 3 - 2,
+            ..self.start_of_month()
+        }
+    }
+
+    /// midnight of january 1st of the same year
+    pub fn start_of_year(self) -> Self {
+        Self {
+            mon: 1,
+            ..self.start_of_month()
+        }
+    }
+
+    /// the end of the same day, see `Boundary`.
+    ///
+    /// like the start_of_* helpers the result stays in the offset `self` is expressed in.
+    /// the exclusive end of the last period of year 9999 is past `DateTime::MAX` and `None`,
+    /// the inclusive end is always `Some`
+    pub fn end_of_day(self, boundary: Boundary) -> Option<Self> {
+        let date = Date::from(self);
+        self.end_before(Date::from_days_since_epoch(date.to_unix_day() + 1), boundary)
+    }
+
+    /// the end of the same month, see `Boundary` and `end_of_day`
+    pub fn end_of_month(self, boundary: Boundary) -> Option<Self> {
+        let next = if self.mon == 12 {
+            Date { day: 1, mon: 1, year: self.year + 1 }
+        } else {
+            Date { day: 1, mon: self.mon + 1, year: self.year }
+        };
+        self.end_before(next, boundary)
+    }
+
+    /// the end of the same quarter, see `Boundary` and `end_of_day`
+    pub fn end_of_quarter(self, boundary: Boundary) -> Option<Self> {
+        Self {
+            mon: self.quarter() * 3,
+            ..self
+        }
+        .end_of_month(boundary)
+    }
+
+    /// the end of the same year, see `Boundary` and `end_of_day`
+    pub fn end_of_year(self, boundary: Boundary) -> Option<Self> {
+        self.end_before(Date { day: 1, mon: 1, year: self.year + 1 }, boundary)
+    }
+
+    /// whether `other` falls on the same calendar day as `self` in the offset of `self`,
+    /// so 2023-08-20T23:30:00Z and 2023-08-21T01:00:00+02:00 are the same day
+    pub fn is_same_day(&self, other: &DateTime) -> bool {
+        let other = other.to_offset_secs(self.offset);
+        (self.year, self.mon, self.day) == (other.year, other.mon, other.day)
+    }
+
+    /// whether `other` falls in the same month as `self` in the offset of `self`
+    pub fn is_same_month(&self, other: &DateTime) -> bool {
+        let other = other.to_offset_secs(self.offset);
+        (self.year, self.mon) == (other.year, other.mon)
+    }
+
+    /// whether `other` falls in the same year as `self` in the offset of `self`
+    pub fn is_same_year(&self, other: &DateTime) -> bool {
+        self.year == other.to_offset_secs(self.offset).year
+    }
+
+    /// whether `self` is an earlier instant than `other`, whatever their offsets
+    pub fn before(&self, other: &DateTime) -> bool {
+        self < other
+    }
+
+    /// whether `self` is a later instant than `other`, whatever their offsets
+    pub fn after(&self, other: &DateTime) -> bool {
+        self > other
+    }
+
+    /// whether `start <= self <= end`, as instants
+    pub fn is_between(&self, start: &DateTime, end: &DateTime) -> bool {
+        self.is_between_with(start, end, Boundary::Inclusive)
+    }
+
+    /// whether `self` is in the range from `start` to `end`, which `end` only belongs to
+    /// with `Boundary::Inclusive`. `start` always does, so consecutive ranges with
+    /// `Boundary::Exclusive` cover every instant once
+    pub fn is_between_with(&self, start: &DateTime, end: &DateTime, boundary: Boundary) -> bool {
+        start <= self
+            && match boundary {
+                Boundary::Inclusive => self <= end,
+                Boundary::Exclusive => self < end,
+            }
+    }
+
+    /// round down to a multiple of `granularity` counted from 1970-01-01 00:00:00
+    /// in the offset `self` is expressed in, so 15 minutes or 1 day align the way you
+    /// would expect, while 1 week aligns to thursdays. `None` for a granularity below 1us
+    /// or beyond `i64::MAX` microseconds, or if the result is out of the range 0001...9999
+    pub fn floor_to(self, granularity: Duration) -> Option<Self> {
+        let g = granularity_micros(granularity)?;
+        let micros = self.wall_micros() as i128;
+        self.with_wall_micros(micros - micros.rem_euclid(g))
+    }
+
+    /// round up to a multiple of `granularity`, see `floor_to`
+    pub fn ceil_to(self, granularity: Duration) -> Option<Self> {
+        let g = granularity_micros(granularity)?;
+        let micros = self.wall_micros() as i128;
+        let rem = micros.rem_euclid(g);
+        if rem == 0 {
+            return Some(self);
+        }
+        self.with_wall_micros(micros - rem + g)
+    }
+
+    /// round to the nearest multiple of `granularity`, halfway rounds up, see `floor_to`
+    pub fn round_to(self, granularity: Duration) -> Option<Self> {
+        let g = granularity_micros(granularity)?;
+        let micros = self.wall_micros() as i128;
+        let rem = micros.rem_euclid(g);
+        if rem * 2 >= g {
+            self.with_wall_micros(micros - rem + g)
+        } else {
+            self.with_wall_micros(micros - rem)
+        }
+    }
+
+    /// add a calendar period, the months first (clamped to the end of shorter months)
+    /// and then the days and time of day. returns `None` if the result is out of the range 0001...9999
+    pub fn checked_add_period(self, p: Period) -> Option<Self> {
+        let months = p.years.checked_mul(12)?.checked_add(p.months)?;
+        let d = self.add_months(months, MonthOverflow::Clamp).ok()?;
+        // in i128, i32 days of microseconds do not fit an i64
+        let micros = p.days as i128 * 86_400_000_000
+            + p.hours as i128 * 3_600_000_000
+            + p.minutes as i128 * 60_000_000
+            + p.seconds as i128 * 1_000_000
+            + p.micros as i128;
+        d.with_wall_micros(d.wall_micros() as i128 + micros)
+    }
+
+    /// subtract a calendar period as `checked_add_period` adds it, `None` if the result is out
+    /// of the range 0001...9999 or a component is `i32::MIN`
+    pub fn checked_sub_period(self, p: Period) -> Option<Self> {
+        self.checked_add_period(p.checked_neg()?)
+    }
+
+    /// 1...9999
+    pub const fn year(&self) -> u16 {
+        self.year
+    }
+
+    /// 1...12
+    pub const fn month(&self) -> u8 {
+        self.mon
+    }
+
+    /// 1...31
+    pub const fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// 0...23
+    pub const fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    /// 0...59
+    pub const fn minute(&self) -> u8 {
+        self.min
+    }
+
+    /// 0...59
+    pub const fn second(&self) -> u8 {
+        self.sec
+    }
+
+    /// 0...999999
+    pub const fn micro(&self) -> u32 {
+        self.micro
+    }
+
+    /// the utc offset the fields are in, in seconds east of utc
+    pub const fn offset(&self) -> i32 {
+        self.offset
+    }
+
+    /// set the year, failing with `OutOfRangeDay` for 02-29 in a common year
+    pub fn set_year(&mut self, year: u16) -> Result<(), Error> {
+        *self = self.with_year(year, MonthOverflow::Error)?;
+        Ok(())
+    }
+
+    /// set the month, failing with `OutOfRangeDay` if the day does not exist in it
+    pub fn set_month(&mut self, mon: u8) -> Result<(), Error> {
+        *self = self.with_month(mon, MonthOverflow::Error)?;
+        Ok(())
+    }
+
+    /// set the day, which must exist in the month
+    pub fn set_day(&mut self, day: u8) -> Result<(), Error> {
+        *self = self.with_day(day)?;
+        Ok(())
+    }
+
+    /// set the hour, failing if it is past 23
+    pub fn set_hour(&mut self, hour: u8) -> Result<(), Error> {
+        *self = self.with_hour(hour)?;
+        Ok(())
+    }
+
+    /// set the minute, failing if it is past 59
+    pub fn set_minute(&mut self, min: u8) -> Result<(), Error> {
+        *self = self.with_minute(min)?;
+        Ok(())
+    }
+
+    /// set the second, failing if it is past 59
+    pub fn set_second(&mut self, sec: u8) -> Result<(), Error> {
+        *self = self.with_second(sec)?;
+        Ok(())
+    }
+
+    /// set the microsecond, failing if it is past 999999
+    pub fn set_micro(&mut self, micro: u32) -> Result<(), Error> {
+        *self = self.with_micro(micro)?;
+        Ok(())
+    }
+
+    /// the same datetime in `year`, resolving 02-29 in a common year with `policy`
+    pub fn with_year(self, year: u16, policy: MonthOverflow) -> Result<Self, Error> {
+        Ok(self.with_date(Date::from(self).with_year(year, policy)?))
+    }
+
+    /// the same datetime in month `mon` (1...12), resolving a day past the end of the month with `policy`
+    pub fn with_month(self, mon: u8, policy: MonthOverflow) -> Result<Self, Error> {
+        Ok(self.with_date(Date::from(self).with_month(mon, policy)?))
+    }
+
+    /// the same datetime on day `day`, which must exist in the month
+    pub fn with_day(self, day: u8) -> Result<Self, Error> {
+        Ok(self.with_date(Date::from(self).with_day(day)?))
+    }
+
+    /// the same datetime with hour `hour` (0...23)
+    pub fn with_hour(self, hour: u8) -> Result<Self, Error> {
+        Ok(self.with_time(Time::from(self).with_hour(hour)?))
+    }
+
+    /// the same datetime with minute `min` (0...59)
+    pub fn with_minute(self, min: u8) -> Result<Self, Error> {
+        Ok(self.with_time(Time::from(self).with_minute(min)?))
+    }
+
+    /// the same datetime with second `sec` (0...59)
+    pub fn with_second(self, sec: u8) -> Result<Self, Error> {
+        Ok(self.with_time(Time::from(self).with_second(sec)?))
+    }
+
+    /// the same datetime with microsecond `micro` (0...999999)
+    pub fn with_micro(self, micro: u32) -> Result<Self, Error> {
+        Ok(self.with_time(Time::from(self).with_micro(micro)?))
+    }
+
+    fn with_date(self, d: Date) -> Self {
+        Self {
+            day: d.day,
+            mon: d.mon,
+            year: d.year,
+            ..self
+        }
+    }
+
+    fn with_time(self, t: Time) -> Self {
+        Self {
+            micro: t.micro,
+            sec: t.sec,
+            min: t.min,
+            hour: t.hour,
+            ..self
+        }
+    }
+
+    /// the ordinal day of the year, 1...366
+    pub fn day_of_year(self) -> u16 {
+        Date::from(self).day_of_year()
+    }
+
+    /// the ISO 8601 week-based year and week number (1...53), see `Date::iso_week`
+    pub fn iso_week(self) -> (i32, u8) {
+        Date::from(self).iso_week()
+    }
+
+    /// the quarter of the year, 1...4
+    pub fn quarter(self) -> u8 {
+        Date::from(self).quarter()
+    }
+
+    /// the day of the week
+    pub fn weekday(self) -> Weekday {
+        Date::from(self).weekday()
+    }
+
+    /// whether this datetime is on a saturday or sunday
+    pub fn is_weekend(self) -> bool {
+        Date::from(self).is_weekend()
+    }
+
+    /// whether this datetime is on monday to friday
+    pub fn is_weekday(self) -> bool {
+        Date::from(self).is_weekday()
+    }
+
+    /// whether this datetime falls on `weekend`, e.g. `Weekend::FRIDAY_SATURDAY`
+    pub fn is_weekend_in(self, weekend: Weekend) -> bool {
+        Date::from(self).is_weekend_in(weekend)
+    }
+
+    /// an endless iterator of `self`, `self + step`, `self + 2 * step`, ...
+    pub fn every(self, step: Duration) -> Every {
+        Every::fixed(self, step)
+    }
+
+    /// like `every`, but stepping by a calendar period such as one month
+    pub fn every_period(self, step: Period) -> Every {
+        Every::calendar(self, step)
+    }
+
+    /// restrict `self` to `[min, max]` like `Ord::clamp`, but returns an
+    /// `InvalidClampRange` error instead of panicking when `min > max`
+    pub fn clamp(self, min: DateTime, max: DateTime) -> Result<Self, Error> {
+        if min > max {
+            return Err(Error::InvalidClampRange);
+        }
+        Ok(Ord::clamp(self, min, max))
+    }
+
+    /// the instant halfway between `self` and `other`, rounded toward `self`,
+    /// in the offset of `self`
+    pub fn midpoint(self, other: DateTime) -> Self {
+        let other = other.to_offset_secs(self.offset);
+        let (a, b) = (self.wall_micros() as i128, other.wall_micros() as i128);
+        Self::from_wall_micros((a + (b - a) / 2) as i64, self.offset)
+    }
+
+    /// linear interpolation, `t = 0.0` is `self` and `t = 1.0` is `other`.
+    /// values of `t` outside `0.0...1.0` extrapolate, returning `None` if
+    /// `t` is not finite or the result is out of the range 0001...9999
+    pub fn lerp(self, other: DateTime, t: f64) -> Option<Self> {
+        if !t.is_finite() {
+            return None;
+        }
+        let other = other.to_offset_secs(self.offset);
+        let (a, b) = (self.wall_micros(), other.wall_micros());
+        let micros = a as f64 + (b as f64 - a as f64) * t;
+        // the bounds of values that round half away from zero into the range
+        if micros <= Self::MIN.wall_micros() as f64 - 0.5 || micros >= Self::MAX.wall_micros() as f64 + 0.5 {
+            return None;
+        }
+        // f64::round needs std, the cast truncates toward zero
+        let truncated = micros as i64;
+        let rounded = match micros - truncated as f64 {
+            frac if frac >= 0.5 => truncated + 1,
+            frac if frac <= -0.5 => truncated - 1,
+            _ => truncated,
+        };
+        Some(Self::from_wall_micros(rounded, self.offset))
+    }
+
+    /// format as RFC 3339 with the offset, "2006-01-02T15:04:05.999999+08:00",
+    /// or a `Z` suffix at utc. an offset with seconds is truncated to minutes
+    pub fn to_rfc3339(&self) -> String {
+        let (buf, len) = self.to_rfc3339_ascii();
+        String::from(core::str::from_utf8(&buf[..len]).unwrap())
+    }
+
+    /// the lossless form `from_str` gives back with the same fields and offset, `eq_fields`
+    /// and not only `==`: `to_rfc3339` with the seconds of an offset that is not whole minutes,
+    /// e.g. "1900-01-01T00:00:00.000000+00:09:21". guaranteed for every valid datetime with an
+    /// offset within `MAX_OFFSET`, six fraction digits as the precision is microseconds
+    pub fn to_canonical_string(&self) -> String {
+        let (buf, len) = self.to_rfc3339_ascii();
+        let mut s = String::from(core::str::from_utf8(&buf[..len]).unwrap());
+        let secs = self.offset.unsigned_abs() % 60;
+        if secs != 0 {
+            let mut tail = *b":00";
+            put2(&mut tail, 1, secs);
+            s.push_str(core::str::from_utf8(&tail).unwrap());
+        }
+        s
+    }
+
+    /// `to_string` into an inline string, without allocating
+    #[cfg(feature = "arrayvec")]
+    pub fn to_array_string(&self) -> arrayvec::ArrayString<26> {
+        let mut buf: [u8; 26] = *b"0000-00-00 00:00:00.000000";
+        self.write_digits(&mut buf);
+        arrayvec::ArrayString::from_byte_string(&buf).unwrap()
+    }
+
+    /// `to_rfc3339` into an inline string, without allocating
+    #[cfg(feature = "arrayvec")]
+    pub fn to_rfc3339_array_string(&self) -> arrayvec::ArrayString<32> {
+        let (buf, len) = self.to_rfc3339_ascii();
+        arrayvec::ArrayString::from(core::str::from_utf8(&buf[..len]).unwrap()).unwrap()
+    }
+
+    /// the ascii bytes of `to_rfc3339` and their length
+    pub(crate) fn to_rfc3339_ascii(self) -> ([u8; 32], usize) {
+        let mut buf: [u8; 32] = *b"0000-00-00T00:00:00.000000+00:00";
+        self.write_digits(&mut buf);
+        let len = if self.offset == 0 {
+            buf[26] = b'Z';
+            27
+        } else {
+            if self.offset < 0 {
+                buf[26] = b'-';
+            }
+            let minutes = self.offset.unsigned_abs() / 60;
+            put2(&mut buf, 27, minutes / 60);
+            put2(&mut buf, 30, minutes % 60);
+            32
+        };
+        (buf, len)
+    }
+
+    /// write "YYYY-MM-DD?HH:MM:SS.ffffff" into the first 26 bytes of `buf`, leaving the
+    /// separators. panics for a year past 9999 as `Date` does
+    fn write_digits(&self, buf: &mut [u8]) {
+        assert!(self.year <= 9999, "{}", crate::date::YEAR_DIGITS);
+        put4(buf, 0, self.year as u32);
+        put2(buf, 5, self.mon as u32);
+        put2(buf, 8, self.day as u32);
+        put2(buf, 11, self.hour as u32);
+        put2(buf, 14, self.min as u32);
+        put2(buf, 17, self.sec as u32);
+        put6(buf, 20, self.micro);
+    }
+
+    /// seconds since the epoch, rounded toward the past, negative before it
+    pub fn unix_timestamp(&self) -> i64 {
+        self.unix_micros().div_euclid(1_000_000)
+    }
+
+    /// milliseconds since the epoch, rounded toward the past
+    pub fn unix_timestamp_millis(&self) -> i64 {
+        self.unix_micros().div_euclid(1_000)
+    }
+
+    /// microseconds since the epoch
+    pub fn unix_timestamp_micros(&self) -> i64 {
+        self.unix_micros()
+    }
+
+    /// nanoseconds since the epoch. an i128 as `i64` nanoseconds only reach the years 1677...2262,
+    /// use `i64::try_from` where an i64 is needed
+    pub fn unix_timestamp_nanos(&self) -> i128 {
+        self.unix_nanos()
+    }
+
+    /// same as `unix_timestamp`
+    pub fn timestamp(&self) -> i64 {
+        self.unix_timestamp()
+    }
+
+    /// same as `unix_timestamp_millis`
+    pub fn timestamp_millis(&self) -> i64 {
+        self.unix_timestamp_millis()
+    }
+
+    /// same as `unix_timestamp_micros`
+    pub fn timestamp_micros(&self) -> i64 {
+        self.unix_timestamp_micros()
+    }
+
+    /// same as `unix_timestamp_nanos`
+    pub fn timestamp_nanos(&self) -> i128 {
+        self.unix_timestamp_nanos()
+    }
+
+    /// the utc datetime `secs` seconds after the epoch, fails outside of the years 0001...9999
+    pub fn from_unix_timestamp(secs: i64) -> Result<Self, Error> {
+        Self::from_unix_micros(secs as i128 * 1_000_000).ok_or(Error::OutOfRange { field: Field::Timestamp })
+    }
+
+    /// as `from_unix_timestamp` in milliseconds
+    pub fn from_unix_timestamp_millis(millis: i64) -> Result<Self, Error> {
+        Self::from_unix_micros(millis as i128 * 1000).ok_or(Error::OutOfRange { field: Field::Timestamp })
+    }
+
+    /// as `from_unix_timestamp` in microseconds
+    pub fn from_unix_timestamp_micros(micros: i64) -> Result<Self, Error> {
+        Self::from_unix_micros(micros as i128).ok_or(Error::OutOfRange { field: Field::Timestamp })
+    }
+
+    /// as `from_unix_timestamp` in nanoseconds, rounded toward the past to microseconds.
+    /// `from_timestamp_with(.., PrecisionLoss::Reject)` fails for a lossy one instead
+    pub fn from_unix_timestamp_nanos(nanos: i128) -> Result<Self, Error> {
+        Self::from_unix_nanos(nanos).ok_or(Error::OutOfRange { field: Field::Timestamp })
+    }
+
+    /// same as `from_unix_timestamp`, `from_timestamp_with` for other units
+    pub fn from_timestamp(secs: i64) -> Result<Self, Error> {
+        Self::from_unix_timestamp(secs)
+    }
+
+    /// same as `from_unix_timestamp_millis`
+    pub fn from_timestamp_millis(millis: i64) -> Result<Self, Error> {
+        Self::from_unix_timestamp_millis(millis)
+    }
+
+    /// same as `from_unix_timestamp_micros`
+    pub fn from_timestamp_micros(micros: i64) -> Result<Self, Error> {
+        Self::from_unix_timestamp_micros(micros)
+    }
+
+    /// same as `from_unix_timestamp_nanos`
+    pub fn from_timestamp_nanos(nanos: i128) -> Result<Self, Error> {
+        Self::from_unix_timestamp_nanos(nanos)
+    }
+
+    /// the utc datetime of a 64-bit ntp timestamp, 32 bits of seconds and 32 of fraction.
+    /// as in rfc 4330 seconds with the high bit clear are in era 1, from 2036-02-07 06:28:16,
+    /// so the timestamps cover 1968-01-20 03:14:08 ... 2104-02-26 09:42:23
+    pub fn from_ntp(ntp: u64) -> Self {
+        let secs = (ntp >> 32) as i64;
+        let secs = if secs < 0x8000_0000 { secs + 0x1_0000_0000 } else { secs };
+        let micros = ((ntp & 0xffff_ffff) * 1_000_000) >> 32;
+        let unix_micros = (secs - NTP_UNIX_SECS) as i128 * 1_000_000 + micros as i128;
+        Self::from_unix_micros(unix_micros).expect("ntp timestamps are between 1968 and 2104")
+    }
+
+    /// the 64-bit ntp timestamp of the instant, the fraction rounded up so `from_ntp` gives it back.
+    /// fails outside of the range `from_ntp` covers
+    pub fn to_ntp(&self) -> Result<u64, Error> {
+        let micros = self.unix_micros();
+        let secs = micros.div_euclid(1_000_000) + NTP_UNIX_SECS;
+        if !(0x8000_0000..0x1_8000_0000).contains(&secs) {
+            return Err(Error::OutOfRange { field: Field::Ntp });
+        }
+        let frac = ((micros.rem_euclid(1_000_000) as u64) << 32).div_ceil(1_000_000);
+        Ok(((secs as u64 & 0xffff_ffff) << 32) | frac)
+    }
+
+    /// the utc datetime of a windows FILETIME, 100-nanosecond intervals since 1601-01-01 00:00:00,
+    /// truncated to microseconds. fails after the year 9999
+    pub fn from_filetime(filetime: u64) -> Result<Self, Error> {
+        let unix_micros = (filetime / 10) as i128 - FILETIME_UNIX_SECS as i128 * 1_000_000;
+        Self::from_unix_micros(unix_micros).ok_or(Error::OutOfRange { field: Field::Filetime })
+    }
+
+    /// the windows FILETIME of the instant. fails before 1601-01-01 00:00:00 utc
+    pub fn to_filetime(&self) -> Result<u64, Error> {
+        let micros = self.unix_micros() + FILETIME_UNIX_SECS * 1_000_000;
+        u64::try_from(micros)
+            .map(|micros| micros * 10)
+            .map_err(|_| Error::OutOfRange { field: Field::Filetime })
+    }
+
+    /// the utc datetime of .NET `DateTime.Ticks`, 100-nanosecond intervals since 0001-01-01 00:00:00,
+    /// truncated to microseconds. fails for negative ticks or after the year 9999
+    pub fn from_dotnet_ticks(ticks: i64) -> Result<Self, Error> {
+        if ticks < 0 {
+            return Err(Error::OutOfRange { field: Field::Ticks });
+        }
+        Self::from_unix_micros((ticks / 10) as i128 + MIN_SECS as i128 * 1_000_000)
+            .ok_or(Error::OutOfRange { field: Field::Ticks })
+    }
+
+    /// the .NET ticks of the instant, as `DateTimeOffset.UtcTicks`.
+    /// fails if the instant is before 0001-01-01 00:00:00 utc
+    pub fn to_dotnet_ticks(&self) -> Result<i64, Error> {
+        let micros = self.unix_micros() - MIN_SECS * 1_000_000;
+        if micros < 0 {
+            return Err(Error::OutOfRange { field: Field::Ticks });
+        }
+        Ok(micros * 10)
+    }
+
+    /// pack into the microseconds since the epoch in the high bits and the offset in the low 32,
+    /// lossless and in the order of the instants, equal instants ordered by offset.
+    /// columnar stores can sort and binary search them as plain integers
+    pub fn to_bits(&self) -> i128 {
+        (self.unix_micros() as i128) << 32 | (self.offset as u32 ^ 0x8000_0000) as i128
+    }
+
+    /// unpack `to_bits`, `None` if the wall-clock fields would be outside of the years 0001...9999
+    pub fn from_bits(bits: i128) -> Option<Self> {
+        let offset = (bits as u32 ^ 0x8000_0000) as i32;
+        let wall = (bits >> 32).checked_add(offset as i128 * 1_000_000)?;
+        Some(Self { offset, ..Self::from_unix_micros(wall)? })
+    }
+
+    /// whether the fields and the offset are all equal, where `==` only compares the instant:
+    /// 09:00+01:00 `==` 08:00Z but the two are not `eq_fields`
+    pub fn eq_fields(&self, other: &DateTime) -> bool {
+        (self.year, self.mon, self.day, self.hour, self.min, self.sec, self.micro, self.offset)
+            == (other.year, other.mon, other.day, other.hour, other.min, other.sec, other.micro, other.offset)
+    }
+
+    /// microseconds since the epoch, negative before it
+    pub(crate) fn unix_micros(self) -> i64 {
+        self.wall_micros() - self.offset as i64 * 1_000_000
+    }
+
+    /// microseconds since 1970-01-01 00:00:00 of the wall-clock fields
+    pub(crate) fn wall_micros(self) -> i64 {
+        let secs = Date::from(self).to_unix_day() * 86400
+            + self.hour as i64 * 3600
+            + self.min as i64 * 60
+            + self.sec as i64;
+        secs * 1_000_000 + self.micro as i64
+    }
+
+    /// the datetime whose wall-clock fields are `micros` after 1970-01-01 00:00:00, at `offset`
+    pub(crate) fn from_wall_micros(micros: i64, offset: i32) -> Self {
+        let date = Date::from_days_since_epoch(micros.div_euclid(86_400_000_000));
+        let micros_of_day = micros.rem_euclid(86_400_000_000);
+        let secs_of_day = micros_of_day / 1_000_000;
+        Self {
+            micro: (micros_of_day % 1_000_000) as u32,
+            sec: (secs_of_day % 60) as u8,
+            min: (secs_of_day % 3600 / 60) as u8,
+            hour: (secs_of_day / 3600) as u8,
+            day: date.day,
+            mon: date.mon,
+            year: date.year,
+            offset,
+        }
+    }
+
+    /// the utc datetime `micros` microseconds after the epoch, negative before it,
+    /// or `None` outside of the years 0001...9999
+    pub(crate) fn from_unix_micros(micros: i128) -> Option<Self> {
+        if micros < MIN_SECS as i128 * 1_000_000 || micros >= MAX_SECS as i128 * 1_000_000 {
+            return None;
+        }
+        Some(Self::from_wall_micros(micros as i64, 0))
+    }
+
+    /// nanoseconds since the epoch for nanosecond targets, always a multiple of 1000
+    pub(crate) fn unix_nanos(self) -> i128 {
+        self.unix_micros() as i128 * 1000
+    }
+
+    /// the utc datetime of `nanos` since the epoch, the nanoseconds past the last microsecond
+    /// dropped toward the past, `None` outside of the years 0001...9999
+    pub(crate) fn from_unix_nanos(nanos: i128) -> Option<Self> {
+        Self::from_unix_micros(nanos.div_euclid(1000))
+    }
+
+    /// `unix_nanos` as whole seconds and the nanoseconds `0...999999999` after them,
+    /// the form of protobuf and msgpack timestamps
+    pub(crate) fn unix_secs_nanos(self) -> (i64, u32) {
+        let nanos = self.unix_nanos();
+        (nanos.div_euclid(1_000_000_000) as i64, nanos.rem_euclid(1_000_000_000) as u32)
+    }
+
+    /// `offset` if it is within `MAX_OFFSET`, else `OutOfRangeOffset`, for offsets that come
+    /// from other libraries with a wider range
+    pub(crate) fn check_offset(offset: i32) -> Result<i32, Error> {
+        if offset.unsigned_abs() > Self::MAX_OFFSET as u32 {
+            return Err(Error::OutOfRange { field: Field::Offset });
+        }
+        Ok(offset)
+    }
+
+    /// the same instant with the fields in `offset` seconds east of utc. fails with
+    /// `OutOfRangeOffset` for ±15:00 and beyond, which no zone uses, or `OutOfRangeYear`
+    /// if the fields would be outside of the years 0001...9999
+    pub fn with_offset(self, offset: i32) -> Result<Self, Error> {
+        Self::check_offset(offset)?;
+        let wall = self.unix_micros() as i128 + offset as i128 * 1_000_000;
+        let v = Self::from_unix_micros(wall).ok_or(Error::OutOfRange { field: Field::Year })?;
+        Ok(Self { offset, ..v })
+    }
+
+    /// same as `with_offset`, the wall-clock fields change and the instant does not
+    pub fn to_offset(self, offset: i32) -> Result<Self, Error> {
+        self.with_offset(offset)
+    }
+
+    /// the same instant in a zone whose utc offset in seconds at a unix second is `offset_at`,
+    /// as for `from_zone`, e.g. to show a server time in the zone of a user
+    pub fn with_timezone(self, offset_at: impl Fn(i64) -> i32) -> Result<Self, Error> {
+        self.with_offset(offset_at(self.unix_timestamp()))
+    }
+
+    /// the same instant in the local zone of the os at that instant, which unlike
+    /// `local_offset` follows daylight saving changes between now and `self`
+    #[cfg(feature = "std")]
+    pub fn to_local(self) -> Result<Self, Error> {
+        self.with_timezone(|sec| Timespec { sec, nsec: 0 }.local().tm_utcoff)
+    }
+
+    /// the same instant expressed in `offset` seconds east of utc
+    pub(crate) fn to_offset_secs(self, offset: i32) -> Self {
+        let micros = self.wall_micros() + (offset as i64 - self.offset as i64) * 1_000_000;
+        Self::from_wall_micros(micros, offset)
+    }
+
+    /// the end of the period that is followed by `next`, in the offset of `self`,
+    /// `None` for the exclusive end 10000-01-01
+    fn end_before(self, next: Date, boundary: Boundary) -> Option<Self> {
+        if boundary == Boundary::Exclusive && next.year > Date::MAX.year {
+            return None;
+        }
+        Some(match boundary {
+            Boundary::Exclusive => Self {
+                micro: 0,
+                sec: 0,
+                min: 0,
+                hour: 0,
+                day: next.day,
+                mon: next.mon,
+                year: next.year,
+                offset: self.offset,
+            },
+            Boundary::Inclusive => {
+                let last = Date::from_days_since_epoch(next.to_unix_day() - 1);
+                Self {
+                    micro: 999999,
+                    sec: 59,
+                    min: 59,
+                    hour: 23,
+                    day: last.day,
+                    mon: last.mon,
+                    year: last.year,
+                    offset: self.offset,
+                }
+            }
+        })
+    }
+
+    /// the wall-clock fields `micros` after 1970-01-01 00:00:00 at the offset of `self`,
+    /// `None` outside of the years 0001...9999
+    fn with_wall_micros(self, micros: i128) -> Option<Self> {
+        let v = Self::from_unix_micros(micros)?;
+        Some(Self { offset: self.offset, ..v })
+    }
+
+    /// carry fields past their range into the next larger one, e.g. after setting `sec` to 75
+    /// or `mon` to 13 by hand: 2023-12-31 23:59:75 is 2024-01-01 00:00:15 and month 13 of 2023
+    /// is january 2024. a `day` past the end of the month runs into the next one, `day` 0 and
+    /// `mon` 0 are the last day of the previous month and december of the previous year.
+    /// the offset is kept, `None` if the result is outside of the years 0001...9999
+    pub fn normalize(self) -> Option<Self> {
+        let months = self.year as i64 * 12 + self.mon as i64 - 1;
+        let days = crate::date::days_from_civil(months.div_euclid(12), months.rem_euclid(12) as u32 + 1, 1)
+            + self.day as i64
+            - 1;
+        let secs = ((days * 24 + self.hour as i64) * 60 + self.min as i64) * 60 + self.sec as i64;
+        self.with_wall_micros(secs as i128 * 1_000_000 + self.micro as i128)
     }
 }
 
@@ -79,126 +1292,183 @@ impl Sub<Duration> for DateTime{
 }
 
 
-impl From<SystemTime> for DateTime {
-    fn from(v: SystemTime) -> DateTime {
-        let dur = v
-            .duration_since(UNIX_EPOCH)
-            .expect("all times should be after the epoch");
-        let secs_since_epoch = dur.as_secs();
+impl AddAssign<Duration> for DateTime {
+    fn add_assign(&mut self, rhs: Duration) {
+        *self = self.add(rhs);
+    }
+}
 
-        if secs_since_epoch >= 253402300800 {
-            // year 9999
-            panic!("date must be before year 9999");
-        }
+impl SubAssign<Duration> for DateTime {
+    fn sub_assign(&mut self, rhs: Duration) {
+        *self = self.sub(rhs);
+    }
+}
+
+impl Add<SignedDuration> for DateTime {
+    type Output = DateTime;
+
+    fn add(self, rhs: SignedDuration) -> Self::Output {
+        self.checked_add_signed(rhs).expect("date must be between the years 0001 and 9999")
+    }
+}
+
+impl Sub<SignedDuration> for DateTime {
+    type Output = DateTime;
+
+    fn sub(self, rhs: SignedDuration) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl AddAssign<SignedDuration> for DateTime {
+    fn add_assign(&mut self, rhs: SignedDuration) {
+        *self = *self + rhs;
+    }
+}
 
-        /* 2000-03-01 (mod 400 year, immediately after feb29 */
-        const LEAPOCH: i64 = 11017;
-        const DAYS_PER_400Y: i64 = 365 * 400 + 97;
-        const DAYS_PER_100Y: i64 = 365 * 100 + 24;
-        const DAYS_PER_4Y: i64 = 365 * 4 + 1;
+impl SubAssign<SignedDuration> for DateTime {
+    fn sub_assign(&mut self, rhs: SignedDuration) {
+        *self = *self - rhs;
+    }
+}
+
+impl Add<Period> for DateTime {
+    type Output = DateTime;
+
+    fn add(self, rhs: Period) -> Self::Output {
+        self.checked_add_period(rhs).expect("overflow when adding period to datetime")
+    }
+}
+
+impl Sub<Period> for DateTime {
+    type Output = DateTime;
 
-        let days = (secs_since_epoch / 86400) as i64 - LEAPOCH;
-        let secs_of_day = secs_since_epoch % 86400;
+    fn sub(self, rhs: Period) -> Self::Output {
+        self.checked_sub_period(rhs).expect("overflow when subtracting period from datetime")
+    }
+}
 
-        let mut qc_cycles = days / DAYS_PER_400Y;
-        let mut remdays = days % DAYS_PER_400Y;
+impl AddAssign<Period> for DateTime {
+    fn add_assign(&mut self, rhs: Period) {
+        *self = *self + rhs;
+    }
+}
 
-        if remdays < 0 {
-            remdays += DAYS_PER_400Y;
-            qc_cycles -= 1;
-        }
+impl SubAssign<Period> for DateTime {
+    fn sub_assign(&mut self, rhs: Period) {
+        *self = *self - rhs;
+    }
+}
 
-        let mut c_cycles = remdays / DAYS_PER_100Y;
-        if c_cycles == 4 {
-            c_cycles -= 1;
-        }
-        remdays -= c_cycles * DAYS_PER_100Y;
+impl Sub<DateTime> for DateTime {
+    type Output = SignedDuration;
 
-        let mut q_cycles = remdays / DAYS_PER_4Y;
-        if q_cycles == 25 {
-            q_cycles -= 1;
-        }
-        remdays -= q_cycles * DAYS_PER_4Y;
+    /// the signed duration from `rhs` to `self`, negative when `self` is earlier
+    fn sub(self, rhs: DateTime) -> Self::Output {
+        SignedDuration::from_micros(self.unix_micros() - rhs.unix_micros())
+    }
+}
 
-        let mut remyears = remdays / 365;
-        if remyears == 4 {
-            remyears -= 1;
-        }
-        remdays -= remyears * 365;
+/// midnight of the date at utc
+impl From<Date> for DateTime {
+    fn from(arg: Date) -> Self {
+        arg.and_time(Time::MIDNIGHT)
+    }
+}
 
-        let mut year = 2000 + remyears + 4 * q_cycles + 100 * c_cycles + 400 * qc_cycles;
+#[cfg(feature = "std")]
+impl From<SystemTime> for DateTime {
+    /// the utc fields of `v`, which may be before the epoch.
+    /// panics outside of the years 0001...9999
+    fn from(v: SystemTime) -> DateTime {
+        DateTime::try_from_system_time(v).expect("date must be between the years 0001 and 9999")
+    }
+}
 
-        let months = [31, 30, 31, 30, 31, 31, 30, 31, 30, 31, 31, 29];
-        let mut mon = 0;
-        for mon_len in months.iter() {
-            mon += 1;
-            if remdays < *mon_len {
-                break;
-            }
-            remdays -= *mon_len;
-        }
-        let mday = remdays + 1;
-        let mon = if mon + 2 > 12 {
-            year += 1;
-            mon - 10
-        } else {
-            mon + 2
+#[cfg(feature = "std")]
+impl DateTime {
+    /// the utc fields of `v` as `From<SystemTime>`, which may be before the epoch as a clock
+    /// of a misconfigured rtc or container is. fails instead of panicking outside of the years
+    /// 0001...9999
+    pub fn try_from_system_time(v: SystemTime) -> Result<Self, Error> {
+        // rounded toward the past so 0.5us before the epoch is 1969-12-31 23:59:59.999999
+        let nanos = match v.duration_since(UNIX_EPOCH) {
+            Ok(d) => d.as_nanos() as i128,
+            Err(e) => -(e.duration().as_nanos() as i128),
         };
-
-        DateTime {
-            micro: (dur - Duration::from_secs(dur.as_secs())).as_micros() as u32,
-            sec: (secs_of_day % 60) as u8,
-            min: ((secs_of_day % 3600) / 60) as u8,
-            hour: (secs_of_day / 3600) as u8,
-            day: mday as u8,
-            mon: mon as u8,
-            year: year as u16,
-        }
+        DateTime::from_unix_nanos(nanos).ok_or(Error::OutOfRange { field: Field::Timestamp })
     }
 }
 
+#[cfg(feature = "std")]
 impl From<DateTime> for SystemTime {
+    /// the instant of `v`, which may be before the epoch
     fn from(v: DateTime) -> SystemTime {
-        let leap_years =
-            ((v.year - 1) - 1968) / 4 - ((v.year - 1) - 1900) / 100 + ((v.year - 1) - 1600) / 400;
-        let mut ydays = match v.mon {
-            1 => 0,
-            2 => 31,
-            3 => 59,
-            4 => 90,
-            5 => 120,
-            6 => 151,
-            7 => 181,
-            8 => 212,
-            9 => 243,
-            10 => 273,
-            11 => 304,
-            12 => 334,
-            _ => unreachable!(),
-        } + v.day as u64
-            - 1;
-        if is_leap_year(v.year) && v.mon > 2 {
-            ydays += 1;
-        }
-        let days = (v.year as u64 - 1970) * 365 + leap_years as u64 + ydays;
-        let sec = Duration::from_secs(
-            v.sec as u64 + v.min as u64 * 60 + v.hour as u64 * 3600 + days * 86400,
-        );
-        if v.micro > 0 {
-            UNIX_EPOCH
-                + sec + Duration::from_micros(v.micro as u64)
+        let micros = v.unix_micros();
+        if micros >= 0 {
+            UNIX_EPOCH + Duration::from_micros(micros as u64)
         } else {
-            UNIX_EPOCH
-                + sec - Duration::from_micros(v.micro as u64)
+            UNIX_EPOCH - Duration::from_micros(micros.unsigned_abs())
+        }
+    }
+}
+
+impl DateTime {
+    /// check the syntax and ranges of `s` for input sanitization that only rejects bad rows,
+    /// accepting exactly what `from_str` does. the fields are only checked, the calendar
+    /// conversions a `DateTime` needs afterwards are skipped
+    pub fn validate_str(s: &str) -> Result<(), Error> {
+        let bytes = s.as_bytes();
+        Date::parse_bytes_partial(bytes)?;
+        if bytes.len() > 10 {
+            check_date_time_sep(bytes)?;
+            let (_, len) = Time::parse_bytes_partial(bytes, 11)?;
+            parse_offset(bytes, 11 + len)?;
         }
+        Ok(())
+    }
+
+    /// parse "YYYY-MM-DD?HH:MM:SS" with an optional fraction and offset, as written by `Display`
+    /// and `to_rfc3339`, from trusted input such as our own output in etl loops. the digit and
+    /// range checks are skipped, only the length and the offset suffix are checked. the fields
+    /// of invalid input are unspecified and may panic in later calendar arithmetic
+    pub fn parse_unchecked(s: &str) -> Result<Self, Error> {
+        let bytes = s.as_bytes();
+        if bytes.len() < 19 {
+            return Err(Error::TooShort);
+        }
+        let d = Date::parse_unchecked_partial(bytes);
+        let (t, len) = Time::parse_unchecked_partial(bytes, 11);
+        let offset = parse_offset(bytes, 11 + len)?;
+        Ok(Self::new(d, t, offset))
     }
 }
 
 impl FromStr for DateTime {
     type Err = Error;
 
-    /// from RFC3339Nano = "2006-01-02T15:04:05.999999999"
+    /// from RFC3339Nano = "2006-01-02T15:04:05.999999999Z", the offset is optional
+    /// (`Z`, `+08:00` or `-05:00`) and defaults to utc. fraction digits past microseconds
+    /// are truncated
     fn from_str(s: &str) -> Result<DateTime, Error> {
+        DateTime::parse_with(s, Validation::Strict)
+    }
+}
+
+impl DateTime {
+    /// parse as `from_str`, checking the day as `validation` says. `Validation::Relaxed` is
+    /// for etl loops reading the rows of a database that already checked them
+    pub fn parse_with(s: &str, validation: Validation) -> Result<DateTime, Error> {
+        Self::parse_assuming(s, validation, default_offset().unwrap_or(0))
+    }
+
+    /// parse as `from_str`, input without an offset at `offset` seconds east of utc rather
+    /// than utc or the offset of `set_default_offset`
+    pub fn parse_in_offset(s: &str, offset: i32) -> Result<DateTime, Error> {
+        Self::parse_assuming(s, Validation::Strict, Self::check_offset(offset)?)
+    }
+
+    fn parse_assuming(s: &str, validation: Validation, offset: i32) -> Result<DateTime, Error> {
         //"0000-00-00 00:00:00.000000";
         let mut date = DateTime {
             micro: 0,
@@ -208,59 +1478,63 @@ impl FromStr for DateTime {
             day: 0,
             mon: 0,
             year: 0,
+            offset,
         };
         let bytes = s.as_bytes();
-        if bytes.len() >= 10 {
-            let d = Date::parse_bytes_partial(&bytes)?;
-            date.year = d.year;
-            date.mon = d.mon;
-            date.day = d.day;
-            if bytes.len() >= 20 {
-                let (t, _) = Time::parse_bytes_partial(&bytes, 11)?;
-                date.hour = t.hour;
-                date.min = t.min;
-                date.sec = t.sec;
-                date.micro = t.micro;
+        let d = Date::parse_bytes_partial_with(bytes, validation)?;
+        date.year = d.year;
+        date.mon = d.mon;
+        date.day = d.day;
+        // a time after the date is always checked, "2024-01-01 99:99:99" is not midnight
+        if bytes.len() > 10 {
+            check_date_time_sep(bytes)?;
+            let (t, len) = Time::parse_bytes_partial(bytes, 11)?;
+            date.hour = t.hour;
+            date.min = t.min;
+            date.sec = t.sec;
+            date.micro = t.micro;
+            if bytes.len() > 11 + len {
+                date.offset = parse_offset(bytes, 11 + len)?;
             }
         }
         // if bytes.len() > 20 {
-        //     if let Ok(year) = std::str::from_utf8(&bytes[0..4])
+        //     if let Ok(year) = core::str::from_utf8(&bytes[0..4])
         //         .unwrap_or_default()
         //         .parse::<u16>()
         //     {
         //         date.year = year;
         //     }
-        //     if let Ok(mon) = std::str::from_utf8(&bytes[5..7])
+        //     if let Ok(mon) = core::str::from_utf8(&bytes[5..7])
         //         .unwrap_or_default()
         //         .parse::<u8>()
         //     {
         //         date.mon = mon;
         //     }
-        //     if let Ok(day) = std::str::from_utf8(&bytes[8..10])
+        //     if let Ok(day) = core::str::from_utf8(&bytes[8..10])
         //         .unwrap_or_default()
         //         .parse::<u8>()
         //     {
         //         date.day = day;
         //     }
-        //     if let Ok(hour) = std::str::from_utf8(&bytes[11..13])
+        //     if let Ok(hour) = core::str::from_utf8(&bytes[11..13])
         //         .unwrap_or_default()
         //         .parse::<u8>()
         //     {
         //         date.hour = hour;
         //     }
-        //     if let Ok(min) = std::str::from_utf8(&bytes[14..16])
+        //     if let Ok(min) = core::str::from_utf8(&bytes[14..16])
         //         .unwrap_or_default()
         //         .parse::<u8>()
         //     {
         //         date.min = min;
         //     }
-        //     if let Ok(sec) = std::str::from_utf8(&bytes[17..19])
+        //     if let Ok(sec) = core::str::from_utf8(&bytes[17..19])
         //         .unwrap_or_default()
         //         .parse::<u8>()
         //     {
         //         date.sec = sec;
         //     }
-        //     if let Ok(ns) = std::str::from_utf8(&bytes[20..26])
+        //     if let Ok(ns) = core::str::from_utf8(&bytes[20..26])
         //         .unwrap_or_default()
         //         .parse::<u32>()
         //     {
@@ -271,43 +1545,112 @@ impl FromStr for DateTime {
     }
 }
 
+/// the whole microseconds of a rounding granularity, `None` if there are none or too many
+fn granularity_micros(granularity: Duration) -> Option<i128> {
+    let g = i64::try_from(granularity.as_micros()).ok()?;
+    (g > 0).then_some(g as i128)
+}
+
+/// check the byte between the date and the time, `T`, `t` or a space as in `parse_const`
+fn check_date_time_sep(bytes: &[u8]) -> Result<(), Error> {
+    if !matches!(bytes[10], b'T' | b't' | b' ') {
+        return Err(Error::InvalidChar { field: Field::DateTimeSep, pos: 10 });
+    }
+    Ok(())
+}
+
+/// parse an optional RFC 3339 offset suffix at `bytes[at..]`, `Z`, `+hh:mm` or `-hh:mm`,
+/// in seconds east of utc
+pub(crate) fn parse_offset(bytes: &[u8], at: usize) -> Result<i32, Error> {
+    let sign = match bytes.get(at) {
+        None => return Ok(0),
+        Some(b'Z') | Some(b'z') => return Ok(0),
+        Some(b'+') => 1,
+        Some(b'-') => -1,
+        Some(_) => return Err(Error::InvalidChar { field: Field::Offset, pos: at }),
+    };
+    let h1 = get_digit!(bytes, at + 1, Field::Offset) as i32;
+    let h2 = get_digit!(bytes, at + 2, Field::Offset) as i32;
+    if bytes.get(at + 3) != Some(&b':') {
+        return Err(Error::InvalidChar { field: Field::Offset, pos: at + 3 });
+    }
+    let m1 = get_digit!(bytes, at + 4, Field::Offset) as i32;
+    let m2 = get_digit!(bytes, at + 5, Field::Offset) as i32;
+    let mut secs = 0;
+    // the seconds `to_canonical_string` writes for an offset that is not whole minutes
+    if bytes.get(at + 6) == Some(&b':') {
+        let s1 = get_digit!(bytes, at + 7, Field::Offset) as i32;
+        let s2 = get_digit!(bytes, at + 8, Field::Offset) as i32;
+        secs = s1 * 10 + s2;
+        if secs > 59 {
+            return Err(Error::OutOfRange { field: Field::OffsetSecond });
+        }
+    }
+    Ok(offset_from_hm(sign, h1 * 10 + h2, m1 * 10 + m2)? + sign * secs)
+}
+
+/// the seconds of a `±hh:mm` offset, hours up to 14 as the real zones and minutes up to 59
+pub(crate) fn offset_from_hm(sign: i32, h: i32, m: i32) -> Result<i32, Error> {
+    if h > 14 {
+        return Err(Error::OutOfRange { field: Field::OffsetHour });
+    }
+    if m > 59 {
+        return Err(Error::OutOfRange { field: Field::OffsetMinute });
+    }
+    Ok(sign * (h * 3600 + m * 60))
+}
+
 impl Display for DateTime {
     /// fmt RFC3339Micro = "2006-01-02T15:04:05.999999"
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         let mut buf: [u8; 26] = *b"0000-00-00 00:00:00.000000";
+        self.write_digits(&mut buf);
+        f.write_str(core::str::from_utf8(&buf[..]).unwrap())
+    }
+}
 
-        buf[0] = b'0' + (self.year / 1000) as u8;
-        buf[1] = b'0' + (self.year / 100 % 10) as u8;
-        buf[2] = b'0' + (self.year / 10 % 10) as u8;
-        buf[3] = b'0' + (self.year % 10) as u8;
-
-        buf[5] = b'0' + (self.mon / 10) as u8;
-        buf[6] = b'0' + (self.mon % 10) as u8;
+/// 1970-01-01 00:00:00 utc
+impl Default for DateTime {
+    fn default() -> Self {
+        DateTime::UNIX_EPOCH
+    }
+}
 
-        buf[8] = b'0' + (self.day / 10) as u8;
-        buf[9] = b'0' + (self.day % 10) as u8;
+impl PartialEq for DateTime {
+    fn eq(&self, other: &DateTime) -> bool {
+        self.unix_micros() == other.unix_micros()
+    }
+}
 
-        buf[11] = b'0' + (self.hour / 10) as u8;
-        buf[12] = b'0' + (self.hour % 10) as u8;
-        buf[14] = b'0' + (self.min / 10) as u8;
-        buf[15] = b'0' + (self.min % 10) as u8;
-        buf[17] = b'0' + (self.sec / 10) as u8;
-        buf[18] = b'0' + (self.sec % 10) as u8;
+impl Eq for DateTime {}
 
-        buf[20] = b'0' + (self.micro / 100000 % 10) as u8;
-        buf[21] = b'0' + (self.micro / 10000 % 10) as u8;
-        buf[22] = b'0' + (self.micro / 1000 % 10) as u8;
-        buf[23] = b'0' + (self.micro / 100 % 10) as u8;
-        buf[24] = b'0' + (self.micro / 10 % 10) as u8;
-        buf[25] = b'0' + (self.micro % 10) as u8;
+impl Hash for DateTime {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.unix_micros().hash(state)
+    }
+}
 
-        f.write_str(std::str::from_utf8(&buf[..]).unwrap())
+/// compares the instant as `DateTime` does
+#[cfg(feature = "rkyv")]
+impl PartialEq<DateTime> for ArchivedDateTime {
+    fn eq(&self, other: &DateTime) -> bool {
+        let v = DateTime {
+            micro: self.micro.to_native(),
+            sec: self.sec,
+            min: self.min,
+            hour: self.hour,
+            day: self.day,
+            mon: self.mon,
+            year: self.year.to_native(),
+            offset: self.offset.to_native(),
+        };
+        v == *other
     }
 }
 
 impl Ord for DateTime {
     fn cmp(&self, other: &DateTime) -> cmp::Ordering {
-        SystemTime::from(*self).cmp(&SystemTime::from(*other))
+        self.unix_micros().cmp(&other.unix_micros())
     }
 }
 
@@ -317,22 +1660,32 @@ impl PartialOrd for DateTime {
     }
 }
 
-fn is_leap_year(y: u16) -> bool {
-    y % 4 == 0 && (y % 100 != 0 || y % 400 == 0)
-}
-
 
+/// serialized as an RFC 3339 string, see `to_rfc3339`
+#[cfg(feature = "serde")]
 impl Serialize for DateTime {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
-        serializer.serialize_str(&format!("{}", self))
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&self.to_rfc3339())
+        } else {
+            (self.unix_micros(), self.offset).serialize(serializer)
+        }
     }
 }
 
+#[cfg(feature = "serde")]
 impl<'de> Deserialize<'de> for DateTime {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de> {
         use serde::de::Error;
+        if !deserializer.is_human_readable() {
+            let (micros, offset) = <(i64, i32)>::deserialize(deserializer)?;
+            return DateTime::from_unix_micros(micros as i128)
+                .ok_or_else(|| D::Error::custom("OutOfRangeTimestamp"))?
+                .with_offset(offset)
+                .map_err(D::Error::custom);
+        }
         let s = String::deserialize(deserializer)?;
-        DateTime::from_str(&s).map_err(|e| D::Error::custom(e))
+        DateTime::from_str(&s).map_err(D::Error::custom)
     }
 }
 
@@ -340,7 +1693,420 @@ impl<'de> Deserialize<'de> for DateTime {
 mod tests {
     use std::str::FromStr;
     use std::time::Duration;
-    use crate::DateTime;
+    use crate::{Boundary, Date, DateTime, MonthOverflow, Period, SignedDuration, Time, Validation};
+    use crate::error::{Error, Field};
+
+    #[test]
+    fn test_datetime_try_new() {
+        let d = DateTime::try_new(2023, 8, 20, 13, 2, 3, 123456).unwrap();
+        assert_eq!(d.to_rfc3339(), "2023-08-20T13:02:03.123456Z");
+        assert_eq!(DateTime::try_new(2023, 2, 29, 0, 0, 0, 0).unwrap_err().to_string(), "Rbatis Error: OutOfRangeDay");
+        assert_eq!(DateTime::try_new(2023, 8, 20, 13, 60, 0, 0).unwrap_err().to_string(), "Rbatis Error: OutOfRangeMinute");
+        assert_eq!(DateTime::try_new(2023, 8, 20, 13, 2, 3, 1_000_000).unwrap_err().to_string(), "Rbatis Error: OutOfRangeMicro");
+        assert_eq!(DateTime::try_new(0, 6, 1, 0, 0, 0, 0).unwrap_err(), Error::OutOfRange { field: Field::Year });
+        assert_eq!(DateTime::from_str("0000-06-01 00:00:00").unwrap_err(), Error::OutOfRange { field: Field::Year });
+        assert_eq!(DateTime::validate_str("0000-06-01 00:00:00").unwrap_err(), Error::OutOfRange { field: Field::Year });
+    }
+
+    #[test]
+    fn test_datetime_eq_instant() {
+        let a = DateTime::from_str("2023-08-20T09:00:00.000000+01:00").unwrap();
+        let b = DateTime::from_str("2023-08-20T08:00:00.000000Z").unwrap();
+        assert_eq!(a, b);
+        assert_eq!(a.cmp(&b), core::cmp::Ordering::Equal);
+        assert!(!a.eq_fields(&b));
+        assert!(a.eq_fields(&a));
+        let set: std::collections::HashSet<DateTime> = [a, b].into_iter().collect();
+        assert_eq!(set.len(), 1);
+        assert_ne!(a, DateTime::from_str("2023-08-20T09:00:00.000000Z").unwrap());
+    }
+
+    #[test]
+    fn test_datetime_unix_timestamp() {
+        let d = DateTime::from_str("2023-08-20T13:02:03.123456+08:00").unwrap();
+        assert_eq!(d.unix_timestamp(), 1692507723);
+        assert_eq!(d.unix_timestamp_millis(), 1692507723123);
+        assert_eq!(d.unix_timestamp_micros(), 1692507723123456);
+        assert_eq!(d.unix_timestamp_nanos(), 1692507723123456000);
+        assert_eq!((d.timestamp(), d.timestamp_millis()), (1692507723, 1692507723123));
+        assert_eq!((d.timestamp_micros(), d.timestamp_nanos()), (1692507723123456, 1692507723123456000));
+        assert_eq!(DateTime::from_unix_timestamp_nanos(-1).unwrap().timestamp_millis(), -1);
+        assert_eq!(DateTime::from_timestamp(d.timestamp()).unwrap(), d.floor_to(Duration::from_secs(1)).unwrap());
+        assert_eq!(DateTime::from_timestamp_millis(d.timestamp_millis()).unwrap(), d.floor_to(Duration::from_millis(1)).unwrap());
+        assert_eq!(DateTime::from_timestamp_micros(d.timestamp_micros()).unwrap(), d);
+        assert_eq!(DateTime::from_timestamp_nanos(d.timestamp_nanos()).unwrap(), d);
+        assert_eq!(DateTime::from_timestamp(i64::MAX).unwrap_err(), Error::OutOfRange { field: Field::Timestamp });
+        assert!(DateTime::from_timestamp_millis(i64::MIN).is_err());
+        assert!(DateTime::from_timestamp_micros(i64::MIN).is_err());
+        assert!(DateTime::from_timestamp_nanos(i128::MIN).is_err());
+        assert_eq!(DateTime::from_unix_timestamp_nanos(d.unix_timestamp_nanos() + 999).unwrap(), d);
+        assert_eq!(DateTime::from_unix_timestamp(-1).unwrap().to_string(), "1969-12-31 23:59:59.000000");
+        assert_eq!(DateTime::from_unix_timestamp_nanos(-1).unwrap().to_string(), "1969-12-31 23:59:59.999999");
+        assert_eq!(DateTime::from_unix_timestamp_millis(1692507723123).unwrap().micro, 123000);
+        assert_eq!(DateTime::from_unix_timestamp_micros(0).unwrap(), DateTime::UNIX_EPOCH);
+        // past the i64 nanoseconds of 2262
+        assert_eq!(DateTime::MAX.unix_timestamp_nanos(), 253402300799999999000);
+        assert!(i64::try_from(DateTime::MAX.unix_timestamp_nanos()).is_err());
+        assert_eq!(DateTime::from_unix_timestamp_nanos(DateTime::MAX.unix_timestamp_nanos()).unwrap(), DateTime::MAX);
+        assert_eq!(DateTime::MIN.unix_timestamp(), -62135596800);
+        assert_eq!(
+            DateTime::from_unix_timestamp(i64::MAX).unwrap_err().to_string(),
+            "Rbatis Error: OutOfRangeTimestamp"
+        );
+        assert!(DateTime::from_unix_timestamp(-62135596801).is_err());
+        assert!(DateTime::from_unix_timestamp_millis(i64::MIN).is_err());
+        assert!(DateTime::from_unix_timestamp_nanos(i128::MAX).is_err());
+        let before = DateTime::from_unix_timestamp_nanos(-1_500).unwrap();
+        assert_eq!(before.unix_secs_nanos(), (-1, 999_998_000));
+        assert_eq!(DateTime::MIN.unix_secs_nanos(), (-62135596800, 0));
+        assert_eq!(DateTime::MAX.unix_secs_nanos(), (253402300799, 999_999_000));
+    }
+
+    #[test]
+    fn test_datetime_offset_range() {
+        assert_eq!(DateTime::from_str("2023-08-20T13:02:03.000000+14:00").unwrap().offset, 50400);
+        assert_eq!(DateTime::from_str("2023-08-20T13:02:03.000000-12:59").unwrap().offset, -46740);
+        let e = DateTime::from_str("2023-08-20T13:02:03.000000+15:00").unwrap_err();
+        assert_eq!(e.to_string(), "Rbatis Error: OutOfRangeOffsetHour");
+        let e = DateTime::from_str("2023-08-20T13:02:03.000000-02:60").unwrap_err();
+        assert_eq!(e.to_string(), "Rbatis Error: OutOfRangeOffsetMinute");
+        assert!(DateTime::parse_const("2023-08-20T13:02:03+15:00").is_none());
+        assert!(DateTime::parse_const("2023-08-20T13:02:03+01:60").is_none());
+        assert!(DateTime::parse_from_str("2023-08-20 +9900", "%Y-%m-%d %z").is_err());
+        let d = DateTime::from_str("2023-08-20T13:02:03.000000Z").unwrap();
+        let v = d.with_offset(-9000).unwrap();
+        assert_eq!(v.to_rfc3339(), "2023-08-20T10:32:03.000000-02:30");
+        assert_eq!(v, d);
+        assert_eq!(d.with_offset(DateTime::MAX_OFFSET).unwrap().offset, 53999);
+        assert_eq!(d.with_offset(54000).unwrap_err().to_string(), "Rbatis Error: OutOfRangeOffset");
+        assert!(d.with_offset(-54000).is_err());
+        assert_eq!(DateTime::MAX.with_offset(3600).unwrap_err().to_string(), "Rbatis Error: OutOfRangeYear");
+    }
+
+    #[test]
+    fn test_datetime_normalize() {
+        let mut d = DateTime::from_str("2023-12-31T23:59:59.000000+08:00").unwrap();
+        d.sec = 75;
+        assert_eq!(d.normalize().unwrap().to_rfc3339(), "2024-01-01T00:00:15.000000+08:00");
+        d.mon = 13;
+        d.sec = 0;
+        assert_eq!(d.normalize().unwrap().to_rfc3339(), "2024-01-31T23:59:00.000000+08:00");
+        let mut f = DateTime::from_str("2023-02-28T00:00:00.000000Z").unwrap();
+        f.day = 30;
+        f.micro = 1_500_000;
+        assert_eq!(f.normalize().unwrap().to_rfc3339(), "2023-03-02T00:00:01.500000Z");
+        f.mon = 0;
+        f.day = 0;
+        f.micro = 0;
+        assert_eq!(f.normalize().unwrap().to_rfc3339(), "2022-11-30T00:00:00.000000Z");
+        assert_eq!(DateTime::MAX.normalize(), Some(DateTime::MAX));
+        let mut max = DateTime::MAX;
+        max.sec = 60;
+        assert_eq!(max.normalize(), None);
+    }
+
+    #[test]
+    fn test_datetime_hash_offsets() {
+        use std::collections::HashMap;
+        use std::hash::{BuildHasher, RandomState};
+        let utc = DateTime::from_str("2023-08-20T05:02:03.123456Z").unwrap();
+        let hasher = RandomState::new();
+        let mut counts = HashMap::new();
+        for offset in (-14 * 3600..=14 * 3600).step_by(900) {
+            let v = utc.with_offset(offset).unwrap();
+            assert_eq!(v, utc);
+            assert_eq!(hasher.hash_one(v), hasher.hash_one(utc));
+            *counts.entry(v).or_insert(0) += 1;
+        }
+        assert_eq!(counts.len(), 1);
+        assert_eq!(counts[&utc], 113);
+        // the key kept is the first one inserted, at -14:00
+        assert_eq!(counts.keys().next().unwrap().offset, -14 * 3600);
+    }
+
+    #[test]
+    fn test_datetime_canonical_string() {
+        let values = [
+            "2023-08-20T13:02:03.123456+08:00",
+            "2023-08-20T13:02:03.000000Z",
+            "0001-01-01T00:00:00.000000Z",
+            "9999-12-31T23:59:59.999999Z",
+            "1899-12-31T23:50:39.000000-00:09:21",
+            "2024-02-29T00:00:00.000001+14:59:59",
+            "2024-02-29T00:00:00.000001-14:59:59",
+        ];
+        for s in values {
+            let v = DateTime::from_str(s).unwrap();
+            assert_eq!(v.to_canonical_string(), s);
+            assert!(DateTime::from_str(&v.to_canonical_string()).unwrap().eq_fields(&v));
+            assert!(DateTime::parse_const(s).unwrap().eq_fields(&v));
+        }
+        let lmt = DateTime::from_str("1900-01-01T00:00:00.000000Z").unwrap().with_offset(561).unwrap();
+        assert_eq!(lmt.to_canonical_string(), "1900-01-01T00:09:21.000000+00:09:21");
+        assert_eq!(lmt.to_rfc3339(), "1900-01-01T00:09:21.000000+00:09");
+        assert_eq!(
+            DateTime::from_str("2023-08-20T13:02:03.000000+01:00:60").unwrap_err().to_string(),
+            "Rbatis Error: OutOfRangeOffsetSecond"
+        );
+        assert!(DateTime::parse_const("2023-08-20T13:02:03+01:00:60").is_none());
+        // nine fraction digits are truncated to microseconds, ten are too many
+        let nano = DateTime::from_str("2023-08-20T13:02:03.123456789Z").unwrap();
+        assert_eq!(nano.to_canonical_string(), "2023-08-20T13:02:03.123456Z");
+        assert!(DateTime::parse_const("2023-08-20T13:02:03.123456789Z").unwrap().eq_fields(&nano));
+        assert!(DateTime::parse_rfc3339("2023-08-20T13:02:03.123456789Z").unwrap().eq_fields(&nano));
+        assert_eq!(DateTime::from_str("2023-08-20T13:02:03.1234567Z").unwrap().micro, 123456);
+        assert_eq!(DateTime::from_str("2023-08-20T13:02:03.1234567890Z").unwrap_err(), Error::SecondFractionTooLong);
+        assert!(DateTime::parse_const("2023-08-20T13:02:03.1234567890Z").is_none());
+    }
+
+    #[test]
+    fn test_datetime_canonical_round_trip() {
+        // every 97th day of 0001...9999 at a different time of day and offset each, with micros
+        // and offsets of 0, whole minutes, seconds and the extremes
+        let offsets = [0, 1, -1, 59, 561, -561, 3600, -12600, 28800, 45900, DateTime::MAX_OFFSET, -DateTime::MAX_OFFSET];
+        let micros = [0, 1, 999999, 123456, 500000];
+        let mut n = 0usize;
+        let mut day = Date::MIN.to_unix_day();
+        while day <= Date::MAX.to_unix_day() {
+            let wall = day * 86_400_000_000 + (n as i64 * 7_919_000_001) % 86_400_000_000;
+            let v = DateTime::from_wall_micros(wall, offsets[n % offsets.len()]);
+            let v = DateTime { micro: micros[n % micros.len()], ..v };
+            let s = v.to_canonical_string();
+            let back = DateTime::from_str(&s).unwrap();
+            assert!(back.eq_fields(&v), "{} {:?}", s, back);
+            assert!(DateTime::parse_const(&s).unwrap().eq_fields(&v), "{}", s);
+            n += 1;
+            day += 97;
+        }
+        for offset in -DateTime::MAX_OFFSET..=DateTime::MAX_OFFSET {
+            for v in [DateTime::MIN, DateTime::MAX] {
+                let v = DateTime { offset, ..v };
+                assert!(DateTime::from_str(&v.to_canonical_string()).unwrap().eq_fields(&v));
+            }
+        }
+    }
+
+    #[test]
+    fn test_datetime_accessors() {
+        let mut d = DateTime::from_str("2024-02-29T13:02:03.123456+08:00").unwrap();
+        assert_eq!((d.year(), d.month(), d.day()), (2024, 2, 29));
+        assert_eq!((d.hour(), d.minute(), d.second(), d.micro(), d.offset()), (13, 2, 3, 123456, 28800));
+        assert_eq!(d.set_year(2023).unwrap_err().to_string(), "Rbatis Error: OutOfRangeDay");
+        assert_eq!(d.year(), 2024);
+        d.set_year(2028).unwrap();
+        d.set_day(31).unwrap_err();
+        d.set_day(1).unwrap();
+        d.set_month(12).unwrap();
+        assert!(d.set_month(13).is_err());
+        d.set_hour(23).unwrap();
+        assert!(d.set_hour(24).is_err());
+        d.set_minute(59).unwrap();
+        assert!(d.set_minute(60).is_err());
+        d.set_second(0).unwrap();
+        assert!(d.set_second(60).is_err());
+        d.set_micro(5).unwrap();
+        assert!(d.set_micro(1_000_000).is_err());
+        assert_eq!(d.to_rfc3339(), "2028-12-01T23:59:00.000005+08:00");
+        let mut date = crate::Date::from(d);
+        assert_eq!((date.year(), date.month(), date.day()), (2028, 12, 1));
+        date.set_month(2).unwrap();
+        date.set_day(29).unwrap();
+        assert!(date.set_year(2027).is_err());
+        assert!(date.set_year(0).is_err());
+        assert_eq!(date.to_string(), "2028-02-29");
+        let mut t = crate::Time::from(d);
+        assert_eq!((t.hour(), t.minute(), t.second(), t.micro()), (23, 59, 0, 5));
+        t.set_hour(0).unwrap();
+        t.set_micro(999999).unwrap();
+        assert!(t.set_minute(60).is_err());
+        assert_eq!(t.to_string(), "00:59:00.999999");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_datetime_now_utc_local() {
+        let (utc, local) = (DateTime::now_utc(), DateTime::now_local());
+        assert_eq!(utc.offset, 0);
+        assert_eq!(local.offset, DateTime::local_offset());
+        assert!((local.unix_micros() - utc.unix_micros()).abs() < 60_000_000);
+        let today = crate::Date::today_utc();
+        assert!(today == crate::Date::from(utc) || today == crate::Date::from(DateTime::now_utc()));
+        let today = crate::Date::today();
+        assert!(today == crate::Date::from(local) || today == crate::Date::from(DateTime::now_local()));
+        let start = DateTime::start_of_today();
+        assert_eq!(start.offset, DateTime::local_offset());
+        assert_eq!(Time::from(start), Time::MIDNIGHT);
+        let today = Date::from(start);
+        assert_eq!(Date::yesterday().to_unix_day(), today.to_unix_day() - 1);
+        assert_eq!(Date::tomorrow().to_unix_day(), today.to_unix_day() + 1);
+    }
+
+    #[test]
+    fn test_date_and_time() {
+        let date = Date::from_str("2023-08-20").unwrap();
+        let time = Time::from_str("13:02:03.5").unwrap();
+        assert_eq!(DateTime::from(date).to_rfc3339(), "2023-08-20T00:00:00.000000Z");
+        assert_eq!(date.and_time(time).to_rfc3339(), "2023-08-20T13:02:03.500000Z");
+        assert_eq!(date.and_hms(23, 59, 59).unwrap().to_rfc3339(), "2023-08-20T23:59:59.000000Z");
+        assert_eq!(date.and_hms(24, 0, 0).unwrap_err(), Error::OutOfRange { field: Field::Hour });
+        assert!(date.and_hms(0, 60, 0).is_err());
+        assert_eq!(Date::from(date.and_time(time)), date);
+        assert_eq!(Time::from(date.and_time(time)), time);
+    }
+
+    #[test]
+    fn test_datetime_is_same() {
+        let d = DateTime::from_str("2023-08-20T23:30:00Z").unwrap();
+        assert!(d.is_same_day(&DateTime::from_str("2023-08-21T01:00:00+02:00").unwrap()));
+        let paris = DateTime::from_str("2023-08-20T02:00:00+02:00").unwrap();
+        assert!(d.is_same_day(&paris));
+        assert!(!paris.is_same_day(&d));
+        assert!(!d.is_same_day(&DateTime::from_str("2023-08-21T00:00:00Z").unwrap()));
+        assert!(d.is_same_day(&DateTime::from_str("2023-08-20T00:00:00Z").unwrap()));
+        assert!(!d.is_same_day(&DateTime::from_str("2022-08-20T23:30:00Z").unwrap()));
+        let d = DateTime::from_str("2023-12-31T23:30:00Z").unwrap();
+        let tokyo = DateTime::from_str("2023-12-31T09:00:00+09:00").unwrap();
+        assert!(d.is_same_month(&tokyo) && d.is_same_year(&tokyo));
+        assert!(!tokyo.is_same_month(&d) && !tokyo.is_same_year(&d));
+        assert!(d.is_same_year(&DateTime::from_str("2023-01-01T00:00:00Z").unwrap()));
+        assert!(!d.is_same_month(&DateTime::from_str("2023-11-30T23:30:00Z").unwrap()));
+    }
+
+    #[test]
+    fn test_datetime_between() {
+        let start = DateTime::from_str("2023-08-20T00:00:00Z").unwrap();
+        let end = DateTime::from_str("2023-08-21T00:00:00Z").unwrap();
+        let d = DateTime::from_str("2023-08-20T12:00:00+08:00").unwrap();
+        assert!(d.after(&start) && d.before(&end));
+        assert!(!d.before(&start) && !d.after(&end));
+        assert!(!d.before(&d) && !d.after(&d));
+        assert!(d.is_between(&start, &end));
+        assert!(end.is_between(&start, &end) && start.is_between(&start, &end));
+        assert!(!end.is_between_with(&start, &end, Boundary::Exclusive));
+        assert!(start.is_between_with(&start, &end, Boundary::Exclusive));
+        let paris_end = DateTime::from_str("2023-08-21T02:00:00+02:00").unwrap();
+        assert!(paris_end.is_between(&start, &end));
+        assert!(!paris_end.is_between_with(&start, &end, Boundary::Exclusive));
+        assert!(!d.is_between(&end, &start));
+    }
+
+    #[test]
+    fn test_datetime_to_offset() {
+        let d = DateTime::from_str("2023-08-20T05:02:03Z").unwrap();
+        let shanghai = d.to_offset(8 * 3600).unwrap();
+        assert_eq!(shanghai.to_rfc3339(), "2023-08-20T13:02:03.000000+08:00");
+        assert_eq!(shanghai, d);
+        assert!(d.to_offset(15 * 3600).is_err());
+        // +01:00, +02:00 from 2023-03-26 01:00 utc until 2023-10-29 01:00 utc
+        let paris = |sec: i64| if (1679792400..1698541200).contains(&sec) { 7200 } else { 3600 };
+        assert_eq!(d.with_timezone(paris).unwrap().to_rfc3339(), "2023-08-20T07:02:03.000000+02:00");
+        let winter = DateTime::from_str("2023-12-20T05:02:03Z").unwrap();
+        assert_eq!(winter.with_timezone(paris).unwrap().to_rfc3339(), "2023-12-20T06:02:03.000000+01:00");
+        assert_eq!(DateTime::MAX.with_timezone(paris).unwrap_err(), Error::OutOfRange { field: Field::Year });
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_datetime_to_local() {
+        let now = DateTime::now_utc();
+        let local = now.to_local().unwrap();
+        assert_eq!(local, now);
+        assert_eq!(local.offset, DateTime::now_local().offset);
+    }
+
+    #[test]
+    fn test_datetime_default_offset() {
+        // the global is left alone, the tests run in parallel. tests/default_offset.rs sets it
+        // in a process of its own
+        assert_eq!(crate::set_default_offset(15 * 3600), Err(Error::OutOfRange { field: Field::Offset }));
+        assert_eq!(crate::default_offset(), None);
+        let d = DateTime::parse_in_offset("2023-08-20 13:02:03", 8 * 3600).unwrap();
+        assert_eq!(d.to_rfc3339(), "2023-08-20T13:02:03.000000+08:00");
+        assert_eq!(DateTime::parse_in_offset("2023-08-20", -3600).unwrap().to_rfc3339(), "2023-08-20T00:00:00.000000-01:00");
+        assert_eq!(DateTime::parse_in_offset("2023-08-20T13:02:03Z", 8 * 3600).unwrap().offset, 0);
+        assert_eq!(DateTime::parse_in_offset("2023-08-20T13:02:03-02:00", 8 * 3600).unwrap().offset, -7200);
+        assert!(DateTime::parse_in_offset("2023-08-20T13:02:03", 15 * 3600).is_err());
+        assert!(DateTime::parse_in_offset("2023-08-20T13:02:03+", 0).is_err());
+        assert_eq!(DateTime::from_str("2023-08-20T13:02:03").unwrap().offset, 0);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_datetime_now_provider() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        // the tests run in parallel, so the provider tells the real time
+        fn provider() -> i128 {
+            CALLS.fetch_add(1, Ordering::Relaxed);
+            std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_nanos() as i128
+        }
+        crate::set_now_provider(provider);
+        let before = CALLS.load(Ordering::Relaxed);
+        let now = DateTime::now_utc();
+        let today = Date::today_utc();
+        assert!(CALLS.load(Ordering::Relaxed) >= before + 2);
+        crate::clear_now_provider();
+        assert_eq!(now.offset, 0);
+        assert!(today == Date::from(now) || today == Date::from(DateTime::now_utc()));
+        let mock = crate::MockClock::new(DateTime::UNIX_EPOCH);
+        let _guard = mock.install();
+        crate::set_now_provider(provider);
+        assert_eq!(DateTime::now_utc(), DateTime::UNIX_EPOCH);
+        crate::clear_now_provider();
+    }
+
+    #[cfg(not(feature = "std"))]
+    #[test]
+    fn test_datetime_now_provider_no_std() {
+        crate::set_now_provider(|| 1_692_536_523_000_000_000);
+        assert_eq!(DateTime::now().to_rfc3339(), "2023-08-20T13:02:03.000000Z");
+        assert_eq!(Date::tomorrow().to_string(), "2023-08-21");
+        crate::clear_now_provider();
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_datetime_now_in_offset() {
+        let now = DateTime::now_in_offset(-5 * 3600).unwrap();
+        assert_eq!(now.offset, -5 * 3600);
+        assert!((DateTime::now_utc() - now).unsigned_abs() < Duration::from_secs(60));
+        assert!(DateTime::now_in_offset(-15 * 3600).is_err());
+    }
+
+    #[test]
+    fn test_datetime_default() {
+        #[derive(Default)]
+        struct Config {
+            since: crate::Date,
+            at: DateTime,
+        }
+        let c = Config::default();
+        assert_eq!(c.since.to_string(), "1970-01-01");
+        assert_eq!(c.at.to_rfc3339(), "1970-01-01T00:00:00.000000Z");
+        assert!(c.at.eq_fields(&DateTime::UNIX_EPOCH));
+        assert_eq!(c.at.unix_timestamp(), 0);
+    }
+
+    #[test]
+    fn test_datetime_const() {
+        const D: DateTime = DateTime::parse_const("2023-08-20T13:02:03.123456+08:00").unwrap();
+        assert_eq!(D, DateTime::from_str("2023-08-20T13:02:03.123456+08:00").unwrap());
+        const N: DateTime = DateTime::new(
+            crate::Date::new(2023, 8, 20).unwrap(),
+            crate::Time::new(13, 2, 3, 0).unwrap(),
+            -18000,
+        );
+        assert_eq!(N.to_rfc3339(), "2023-08-20T13:02:03.000000-05:00");
+        assert_eq!(DateTime::parse_const("2023-08-20 13:02:03").unwrap().offset, 0);
+        assert_eq!(DateTime::parse_const("2023-08-20t13:02:03.5Z").unwrap().micro, 500000);
+        assert_eq!(DateTime::parse_const("2023-08-20T13:02:03-05:30").unwrap().offset, -19800);
+        assert_eq!(DateTime::parse_const("2023-08-20"), None);
+        assert_eq!(DateTime::parse_const("2023-08-20X13:02:03Z"), None);
+        assert_eq!(DateTime::parse_const("2023-08-20T13:02:03+0800"), None);
+        assert_eq!(DateTime::parse_const("2023-08-20T13:02:03Zz"), None);
+        assert_eq!(DateTime::parse_const("2023-02-30T13:02:03Z"), None);
+        assert_eq!(DateTime::parse_const("0000-06-01T00:00:00Z"), None);
+    }
 
     #[test]
     fn test_date() {
@@ -350,16 +2116,558 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "std")]
     fn test_date_utc() {
         let d = DateTime::now();
         println!("{}", d);
     }
 
     #[test]
+    #[cfg(feature = "std")]
+    fn test_date_local_offset() {
+        let offset = DateTime::local_offset();
+        assert_eq!(DateTime::refresh_offset(), offset);
+        assert_eq!(DateTime::now().offset, offset);
+        DateTime::set_offset_refresh_interval(Some(Duration::ZERO));
+        assert_eq!(DateTime::local_offset(), offset);
+        DateTime::set_offset_refresh_interval(None);
+        assert_eq!(DateTime::local_offset(), offset);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_date_now_monotonic() {
+        let mut last = DateTime::now_monotonic();
+        assert_eq!(last.offset, DateTime::now().offset);
+        assert!((last.unix_micros() - DateTime::utc().unix_micros()).abs() < 1_000_000);
+        for _ in 0..1000 {
+            let now = DateTime::now_monotonic();
+            assert!(now > last);
+            last = now;
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_date_now_coarse() {
+        DateTime::set_coarse_interval(Duration::from_secs(3600));
+        let a = DateTime::now_coarse();
+        assert_eq!(DateTime::now_coarse(), a);
+        assert_eq!(a.offset, DateTime::now().offset);
+        DateTime::set_coarse_interval(Duration::ZERO);
+        let b = DateTime::now_coarse();
+        assert!(b >= a);
+        assert!(DateTime::now_coarse() >= b);
+        DateTime::set_coarse_interval(Duration::from_millis(1));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
     fn test_date_utc_add() {
         let d = DateTime::now();
         let added = d + Duration::from_secs(1);
         println!("{},{}", d, added);
         assert_eq!(d.add(Duration::from_secs(1)), added);
     }
+
+    #[test]
+    fn test_date_checked_add() {
+        let d = DateTime::from_str("2022-12-31 23:59:59.000000").unwrap();
+        assert_eq!(d.checked_add(Duration::from_secs(1)).unwrap().to_string(), "2023-01-01 00:00:00.000000");
+        let max = DateTime::from_str("9999-12-31 23:59:59.999999").unwrap();
+        assert_eq!(max.checked_add(Duration::from_micros(1)), None);
+        assert_eq!(max.checked_add(Duration::from_secs(u64::MAX)), None);
+    }
+
+    #[test]
+    fn test_date_checked_sub() {
+        let d = DateTime::from_str("2023-01-01 00:00:00.000000").unwrap();
+        assert_eq!(d.checked_sub(Duration::from_secs(1)).unwrap().to_string(), "2022-12-31 23:59:59.000000");
+        let epoch = DateTime::from_str("1970-01-01 00:00:00.000000").unwrap();
+        assert_eq!(epoch.checked_sub(Duration::from_micros(1)).unwrap().to_string(), "1969-12-31 23:59:59.999999");
+        let min = DateTime::from_str("0001-01-01 00:00:00.000000").unwrap();
+        assert_eq!(min.checked_sub(Duration::from_micros(1)), None);
+        assert_eq!(min.checked_add(Duration::from_secs(86400)).unwrap().to_string(), "0001-01-02 00:00:00.000000");
+    }
+
+    #[test]
+    fn test_date_saturating() {
+        let d = DateTime::from_str("2022-12-31 23:59:59.000000").unwrap();
+        assert_eq!(d.saturating_add(Duration::from_secs(1)).to_string(), "2023-01-01 00:00:00.000000");
+        assert_eq!(d.saturating_add(Duration::from_secs(u64::MAX)), DateTime::MAX);
+        assert_eq!(d.saturating_sub(Duration::from_secs(u64::MAX)), DateTime::MIN);
+        assert_eq!(DateTime::MAX.to_string(), "9999-12-31 23:59:59.999999");
+        assert_eq!(DateTime::MIN.to_string(), "0001-01-01 00:00:00.000000");
+        assert_eq!(DateTime::UNIX_EPOCH.to_string(), "1970-01-01 00:00:00.000000");
+    }
+
+    #[test]
+    fn test_date_add_months() {
+        let d = DateTime::from_str("2023-01-31 11:12:13.123456").unwrap();
+        assert_eq!(d.add_months(1, MonthOverflow::Clamp).unwrap().to_string(), "2023-02-28 11:12:13.123456");
+        assert_eq!(d.add_months(1, MonthOverflow::Rollover).unwrap().to_string(), "2023-03-03 11:12:13.123456");
+        assert!(d.add_months(1, MonthOverflow::Error).is_err());
+    }
+
+    #[test]
+    fn test_date_sub_date() {
+        let a = DateTime::from_str("2023-01-01 00:00:00.000000").unwrap();
+        let b = DateTime::from_str("2023-01-01 00:00:01.500000").unwrap();
+        assert_eq!(b - a, SignedDuration::from_millis(1500));
+        assert_eq!(a - b, SignedDuration::from_millis(-1500));
+        assert_eq!(a - a, SignedDuration::ZERO);
+    }
+
+    #[test]
+    fn test_date_diff() {
+        let a = DateTime::from_str("2023-01-31 12:00:00.000000").unwrap();
+        let b = DateTime::from_str("2024-02-29 11:59:59.000000").unwrap();
+        assert_eq!(b.diff_days(a), 393);
+        assert_eq!(a.diff_days(b), -393);
+        assert_eq!(b.diff_weeks(a), 56);
+        assert_eq!(b.diff_months(a), 12);
+        assert_eq!(a.diff_months(b), -12);
+        assert_eq!(b.diff_years(a), 1);
+        let c = DateTime::from_str("2023-01-31 11:00:00.000000").unwrap();
+        assert_eq!(a.diff_days(c), 0);
+    }
+
+    #[test]
+    fn test_date_start_of() {
+        // a sunday
+        let d = DateTime::from_str("2023-08-20 11:12:13.123456").unwrap();
+        assert_eq!(d.start_of_day().to_string(), "2023-08-20 00:00:00.000000");
+        assert_eq!(d.start_of_week().to_string(), "2023-08-14 00:00:00.000000");
+        assert_eq!(d.start_of_month().to_string(), "2023-08-01 00:00:00.000000");
+        assert_eq!(d.start_of_quarter().to_string(), "2023-07-01 00:00:00.000000");
+        assert_eq!(d.start_of_year().to_string(), "2023-01-01 00:00:00.000000");
+        let d = DateTime::from_str("1970-01-01 11:12:13.123456").unwrap();
+        assert_eq!(d.start_of_week().to_string(), "1969-12-29 00:00:00.000000");
+    }
+
+    #[test]
+    fn test_date_end_of() {
+        let d = DateTime::from_str("2024-02-20 11:12:13.123456").unwrap();
+        assert_eq!(d.end_of_day(Boundary::Inclusive).unwrap().to_string(), "2024-02-20 23:59:59.999999");
+        assert_eq!(d.end_of_day(Boundary::Exclusive).unwrap().to_string(), "2024-02-21 00:00:00.000000");
+        assert_eq!(d.end_of_month(Boundary::Inclusive).unwrap().to_string(), "2024-02-29 23:59:59.999999");
+        assert_eq!(d.end_of_month(Boundary::Exclusive).unwrap().to_string(), "2024-03-01 00:00:00.000000");
+        assert_eq!(d.end_of_quarter(Boundary::Inclusive).unwrap().to_string(), "2024-03-31 23:59:59.999999");
+        assert_eq!(d.end_of_year(Boundary::Inclusive).unwrap().to_string(), "2024-12-31 23:59:59.999999");
+        assert_eq!(d.end_of_year(Boundary::Exclusive).unwrap().to_string(), "2025-01-01 00:00:00.000000");
+        let d = DateTime::from_str("2023-12-31 11:12:13.123456").unwrap();
+        assert_eq!(d.end_of_month(Boundary::Exclusive).unwrap().to_string(), "2024-01-01 00:00:00.000000");
+        assert_eq!(d.end_of_day(Boundary::Exclusive).unwrap().to_string(), "2024-01-01 00:00:00.000000");
+        assert_eq!(DateTime::MAX.end_of_day(Boundary::Inclusive), Some(DateTime::MAX));
+        assert_eq!(DateTime::MAX.end_of_year(Boundary::Inclusive), Some(DateTime::MAX));
+        assert_eq!(DateTime::MAX.end_of_day(Boundary::Exclusive), None);
+        assert_eq!(DateTime::MAX.end_of_month(Boundary::Exclusive), None);
+        assert_eq!(DateTime::MAX.end_of_quarter(Boundary::Exclusive), None);
+        assert_eq!(DateTime::MAX.end_of_year(Boundary::Exclusive), None);
+        let d = DateTime::from_str("9999-11-15 12:00:00").unwrap();
+        assert_eq!(d.end_of_month(Boundary::Exclusive).unwrap().to_string(), "9999-12-01 00:00:00.000000");
+    }
+
+    #[test]
+    fn test_date_round_to() {
+        let quarter = Duration::from_secs(15 * 60);
+        let d = DateTime::from_str("2023-08-20 11:22:30.000000").unwrap();
+        assert_eq!(d.floor_to(quarter).unwrap().to_string(), "2023-08-20 11:15:00.000000");
+        assert_eq!(d.ceil_to(quarter).unwrap().to_string(), "2023-08-20 11:30:00.000000");
+        assert_eq!(d.round_to(quarter).unwrap().to_string(), "2023-08-20 11:30:00.000000");
+        let d = DateTime::from_str("2023-08-20 11:22:29.999999").unwrap();
+        assert_eq!(d.round_to(quarter).unwrap().to_string(), "2023-08-20 11:15:00.000000");
+        let d = DateTime::from_str("2023-12-31 23:50:00.000000+08:00").unwrap();
+        let day = d.ceil_to(Duration::from_secs(86400)).unwrap();
+        assert_eq!(day.to_rfc3339(), "2024-01-01T00:00:00.000000+08:00");
+        assert_eq!(d.ceil_to(Duration::from_secs(600)), Some(d));
+        let d = DateTime::from_str("1969-12-31 23:59:59.500000").unwrap();
+        assert_eq!(d.round_to(Duration::from_secs(1)).unwrap().to_string(), "1970-01-01 00:00:00.000000");
+        // no whole microsecond, or too many of them
+        assert_eq!(d.floor_to(Duration::ZERO), None);
+        assert_eq!(d.ceil_to(Duration::from_nanos(999)), None);
+        assert_eq!(d.round_to(Duration::MAX), None);
+        assert_eq!(d.floor_to(Duration::from_micros(i64::MAX as u64 + 1)), None);
+        // past the last representable instant
+        assert_eq!(DateTime::MAX.floor_to(quarter).unwrap().to_string(), "9999-12-31 23:45:00.000000");
+        assert_eq!(DateTime::MAX.ceil_to(quarter), None);
+        assert_eq!(DateTime::MAX.round_to(Duration::from_secs(1)), None);
+        assert_eq!(DateTime::MAX.ceil_to(Duration::from_micros(1)), Some(DateTime::MAX));
+        assert_eq!(DateTime::MIN.floor_to(Duration::from_secs(7 * 86400)), None);
+    }
+
+    #[test]
+    fn test_date_add_assign() {
+        let mut d = DateTime::from_str("2024-01-31 23:00:00.000000").unwrap();
+        d += Duration::from_secs(3600);
+        assert_eq!(d.to_string(), "2024-02-01 00:00:00.000000");
+        d -= Duration::from_secs(1);
+        assert_eq!(d.to_string(), "2024-01-31 23:59:59.000000");
+        d += Period { months: 1, hours: 1, ..Default::default() };
+        assert_eq!(d.to_string(), "2024-03-01 00:59:59.000000");
+        d -= Period { years: 1, days: 1, ..Default::default() };
+        assert_eq!(d.to_string(), "2023-02-28 00:59:59.000000");
+        assert_eq!(DateTime::MAX.checked_add_period(Period { micros: 1, ..Default::default() }), None);
+        // more microseconds than an i64 holds
+        let d = DateTime::from_str("2023-08-20T13:02:03Z").unwrap();
+        assert_eq!(d.checked_add_period(Period::from_str("P212809538D").unwrap()), None);
+        assert_eq!(d.checked_add_period(Period { days: i32::MIN, hours: i32::MIN, ..Default::default() }), None);
+        assert_eq!(d.checked_add_period(Period { hours: i32::MAX, ..Default::default() }), None);
+        assert_eq!(DateTime::UNIX_EPOCH.checked_sub_period(Period { days: i32::MIN, ..Default::default() }), None);
+        let p = Period { months: 1, days: 1, ..Default::default() };
+        assert_eq!(d.checked_sub_period(p).unwrap().to_rfc3339(), "2023-07-19T13:02:03.000000Z");
+    }
+
+    #[test]
+    fn test_date_clamp() {
+        let min = DateTime::from_str("2023-01-01 00:00:00.000000").unwrap();
+        let max = DateTime::from_str("2023-12-31 00:00:00.000000").unwrap();
+        let d = DateTime::from_str("2024-06-01 00:00:00.000000").unwrap();
+        assert_eq!(d.clamp(min, max).unwrap(), max);
+        assert_eq!(DateTime::MIN.clamp(min, max).unwrap(), min);
+        assert_eq!(min.clamp(min, max).unwrap(), min);
+        assert!(d.clamp(max, min).is_err());
+    }
+
+    #[test]
+    fn test_date_with() {
+        let d = DateTime::from_str("2023-01-30 11:12:13.123456").unwrap();
+        assert_eq!(d.with_month(2, MonthOverflow::Clamp).unwrap().to_string(), "2023-02-28 11:12:13.123456");
+        assert!(d.with_month(2, MonthOverflow::Error).is_err());
+        assert_eq!(d.with_year(2020, MonthOverflow::Error).unwrap().to_string(), "2020-01-30 11:12:13.123456");
+        assert_eq!(d.with_day(1).unwrap().to_string(), "2023-01-01 11:12:13.123456");
+        assert_eq!(d.with_hour(0).unwrap().to_string(), "2023-01-30 00:12:13.123456");
+        assert_eq!(d.with_minute(59).unwrap().to_string(), "2023-01-30 11:59:13.123456");
+        assert_eq!(d.with_second(0).unwrap().to_string(), "2023-01-30 11:12:00.123456");
+        assert_eq!(d.with_micro(0).unwrap().to_string(), "2023-01-30 11:12:13.000000");
+        assert!(d.with_hour(24).is_err());
+    }
+
+    #[test]
+    fn test_date_midpoint() {
+        let a = DateTime::from_str("2023-01-01 00:00:00.000000").unwrap();
+        let b = DateTime::from_str("2023-01-02 00:00:00.000001").unwrap();
+        assert_eq!(a.midpoint(b).to_string(), "2023-01-01 12:00:00.000000");
+        assert_eq!(b.midpoint(a).to_string(), "2023-01-01 12:00:00.000001");
+        assert_eq!(DateTime::MIN.midpoint(DateTime::MAX).to_string(), "5000-07-02 11:59:59.999999");
+        assert_eq!(a.lerp(b, 0.25).unwrap().to_string(), "2023-01-01 06:00:00.000000");
+        assert_eq!(a.lerp(b, 0.0).unwrap(), a);
+        assert_eq!(a.lerp(b, 1.0).unwrap(), b);
+        assert_eq!(a.lerp(b, -1.0).unwrap().to_string(), "2022-12-30 23:59:59.999999");
+        assert_eq!(a.lerp(b, f64::NAN), None);
+        assert_eq!(DateTime::MIN.lerp(DateTime::MAX, 2.0), None);
+    }
+
+    #[test]
+    fn test_date_ntp() {
+        assert_eq!(DateTime::from_ntp(0x83AA_7E80_0000_0000), DateTime::UNIX_EPOCH);
+        assert_eq!(DateTime::UNIX_EPOCH.to_ntp().unwrap(), 0x83AA_7E80_0000_0000);
+        assert_eq!(DateTime::from_ntp(0x8000_0000_0000_0000).to_string(), "1968-01-20 03:14:08.000000");
+        // era 1
+        assert_eq!(DateTime::from_ntp(0).to_string(), "2036-02-07 06:28:16.000000");
+        assert_eq!(DateTime::from_ntp(0x7FFF_FFFF_FFFF_FFFF).to_string(), "2104-02-26 09:42:23.999999");
+        let d = DateTime::from_str("2036-02-07T14:28:16.5+08:00").unwrap();
+        assert_eq!(d.to_ntp().unwrap(), 0x0000_0000_8000_0000);
+        let d = DateTime::from_str("2023-08-20T05:02:03.123457Z").unwrap();
+        assert_eq!(DateTime::from_ntp(d.to_ntp().unwrap()), d);
+        assert!(DateTime::from_str("2104-02-26T09:42:24Z").unwrap().to_ntp().is_err());
+        assert!(DateTime::from_str("1968-01-20T03:14:07.999999Z").unwrap().to_ntp().is_err());
+    }
+
+    #[test]
+    fn test_date_filetime() {
+        assert_eq!(DateTime::from_filetime(116444736000000000).unwrap(), DateTime::UNIX_EPOCH);
+        assert_eq!(DateTime::UNIX_EPOCH.to_filetime().unwrap(), 116444736000000000);
+        let d = DateTime::from_str("2023-08-20T13:02:03.123456+08:00").unwrap();
+        assert_eq!(d.to_filetime().unwrap(), 133369813231234560);
+        assert_eq!(DateTime::from_filetime(133369813231234567).unwrap().to_rfc3339(), "2023-08-20T05:02:03.123456Z");
+        assert_eq!(DateTime::from_filetime(0).unwrap().to_string(), "1601-01-01 00:00:00.000000");
+        assert!(DateTime::from_filetime(u64::MAX).is_err());
+        assert!(DateTime::from_str("1600-12-31T23:59:59Z").unwrap().to_filetime().is_err());
+    }
+
+    #[test]
+    fn test_date_bits() {
+        let times = [
+            "0001-01-01T00:00:00Z",
+            "1969-12-31T23:59:59.999999Z",
+            "2023-08-20T05:02:03.123456Z",
+            "2023-08-20T13:02:03.123456+08:00",
+            "2023-08-20T00:02:03.123457-05:00",
+            "9999-12-31T23:59:59.999999Z",
+        ];
+        let times: Vec<DateTime> = times.iter().map(|s| DateTime::from_str(s).unwrap()).collect();
+        for w in times.windows(2) {
+            assert!(w[0] <= w[1]);
+            assert!(w[0].to_bits() < w[1].to_bits());
+        }
+        for d in times {
+            let back = DateTime::from_bits(d.to_bits()).unwrap();
+            assert_eq!((back, back.offset), (d, d.offset));
+            assert_eq!(back.to_string(), d.to_string());
+        }
+        // 9999-12-31 23:59:59 at utc is the next year at +01:00
+        let max = DateTime::from_str("9999-12-31T23:59:59Z").unwrap().to_bits();
+        assert_eq!(DateTime::from_bits(max + 3600), None);
+    }
+
+    #[test]
+    fn test_date_parse_with() {
+        let v = "2023-08-20T13:02:03.123456+08:00";
+        assert_eq!(DateTime::parse_with(v, Validation::Relaxed).unwrap(), DateTime::from_str(v).unwrap());
+        let d = DateTime::parse_with("2023-02-30 13:02:03.000000", Validation::Relaxed).unwrap();
+        assert_eq!((d.mon, d.day, d.hour), (2, 30, 13));
+        assert!(DateTime::parse_with("2023-02-30 13:02:03.000000", Validation::Strict).is_err());
+        assert!(DateTime::parse_with("2023-02-28 24:02:03.000000", Validation::Relaxed).is_err());
+    }
+
+    #[test]
+    fn test_date_parse_unchecked() {
+        for v in ["2023-08-20 13:02:03.123456", "2023-08-20T13:02:03Z", "2023-08-20T13:02:03.5-05:00"] {
+            let d = DateTime::parse_unchecked(v).unwrap();
+            assert_eq!((d, d.offset), (DateTime::from_str(v).unwrap(), DateTime::from_str(v).unwrap().offset));
+        }
+        let d = DateTime::from_str("2023-08-20T13:02:03.123456+08:00").unwrap();
+        assert_eq!(DateTime::parse_unchecked(&d.to_rfc3339()).unwrap().to_rfc3339(), d.to_rfc3339());
+        assert_eq!(DateTime::parse_unchecked("2023-08-20 13:02:03").unwrap().sec, 3);
+        assert_eq!(DateTime::parse_unchecked("2023-02-31 13:02:03").unwrap().day, 31);
+        assert!(DateTime::parse_unchecked("2023-08-20 13:02").is_err());
+        assert!(DateTime::parse_unchecked("2023-08-20 13:02:03 UTC").is_err());
+        // garbage gives unspecified fields or an error for the offset, never a panic
+        let _ = DateTime::parse_unchecked("+2023-08-2-T13:M0:~~~~~~");
+        for c in (0..128u8).map(char::from).chain(['é', '€']) {
+            let _ = DateTime::parse_unchecked(&c.to_string().repeat(26));
+            let _ = crate::Time::parse_unchecked(&c.to_string().repeat(8));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "arrayvec")]
+    fn test_date_array_string() {
+        for v in ["2023-08-20T13:02:03.123456+08:00", "2023-08-20T13:02:03Z", "2023-08-20T13:02:03-05:30"] {
+            let d = DateTime::from_str(v).unwrap();
+            assert_eq!(d.to_array_string().as_str(), d.to_string());
+            assert_eq!(d.to_rfc3339_array_string().as_str(), d.to_rfc3339());
+        }
+    }
+
+    #[test]
+    fn test_date_size() {
+        // time-series indexes hold millions of these, keep them from growing
+        assert_eq!(core::mem::size_of::<DateTime>(), 16);
+        assert_eq!(core::mem::size_of::<crate::Date>(), 4);
+        assert_eq!(core::mem::size_of::<crate::Time>(), 8);
+    }
+
+    #[test]
+    fn test_date_error_pos() {
+        use crate::error::{Error, Field};
+        let e = DateTime::from_str("2023-08-20 13:02:0x.000000").unwrap_err();
+        assert_eq!(e, Error::InvalidChar { field: Field::Second, pos: 18 });
+        let e = DateTime::from_str("2023-08-20 13:02:03.000000+08-00").unwrap_err();
+        assert_eq!(e, Error::InvalidChar { field: Field::Offset, pos: 29 });
+        let e = DateTime::parse_rfc3339("2023-08-2xT13:02:03Z").unwrap_err();
+        assert_eq!(e, Error::InvalidChar { field: Field::Date, pos: 9 });
+        assert_eq!(DateTime::from_str("2023-02-30 13:02:03.000000").unwrap_err(), Error::OutOfRange { field: Field::Day });
+    }
+
+    #[test]
+    fn test_date_time_ranges() {
+        use crate::error::{Error, Field};
+        let out_of_range = |s: &str| DateTime::from_str(s).unwrap_err();
+        assert_eq!(out_of_range("2024-01-01 99:99:99"), Error::OutOfRange { field: Field::Hour });
+        assert_eq!(out_of_range("2024-01-01 24:00:00"), Error::OutOfRange { field: Field::Hour });
+        assert_eq!(out_of_range("2024-01-01 23:60:00"), Error::OutOfRange { field: Field::Minute });
+        assert_eq!(out_of_range("2024-01-01 23:59:60"), Error::OutOfRange { field: Field::Second });
+        assert_eq!(out_of_range("2024-01-01T23:59:60Z"), Error::OutOfRange { field: Field::Second });
+        assert!(DateTime::validate_str("2024-01-01 99:99:99").is_err());
+        assert_eq!(DateTime::from_str("2024-01-01 23:59:59").unwrap().to_string(), "2024-01-01 23:59:59.000000");
+        assert_eq!(DateTime::from_str("2024-01-01T13:02").unwrap().min, 2);
+        assert_eq!(DateTime::parse_rfc3339("2024-01-01 99:99:99").unwrap_err(), Error::OutOfRange { field: Field::Hour });
+        assert_eq!(
+            DateTime::parse_from_str("2024-01-01 23:59:60", "%F %T").unwrap_err(),
+            Error::OutOfRange { field: Field::Second }
+        );
+        assert!(DateTime::parse_const("2024-01-01 23:60:00").is_none());
+        assert!(crate::Time::from_str("24:00:00").is_err());
+        // shorter than a date is not the zero datetime
+        assert_eq!(DateTime::from_str("yesterday").unwrap_err(), Error::TooShort);
+        assert_eq!(DateTime::validate_str("").unwrap_err(), Error::TooShort);
+        let sep = Error::InvalidChar { field: Field::DateTimeSep, pos: 10 };
+        assert_eq!(DateTime::from_str("2023-08-20X13:02:03").unwrap_err(), sep);
+        assert_eq!(DateTime::validate_str("2023-08-20X13:02:03").unwrap_err(), sep);
+        assert!(DateTime::from_str("2023-08-20t13:02:03").is_ok());
+    }
+
+    #[test]
+    fn test_date_parse_every_bad_byte() {
+        use crate::error::{Error, Field};
+        let fields = [
+            Field::Year, Field::Year, Field::Year, Field::Year, Field::DateSep, Field::Month, Field::Month,
+            Field::DateSep, Field::Day, Field::Day, Field::DateTimeSep, Field::Hour, Field::Hour, Field::TimeSep,
+            Field::Minute, Field::Minute, Field::TimeSep, Field::Second, Field::Second,
+        ];
+        for (pos, field) in fields.into_iter().enumerate() {
+            for bad in ['/', 'a', ';', 'é'] {
+                let mut s = String::from("2023-08-20 13:02:03.000000");
+                s.replace_range(pos..pos + 1, bad.encode_utf8(&mut [0; 4]));
+                let e = DateTime::from_str(&s).unwrap_err();
+                match pos {
+                    // a time without the time separator, checked as its offset
+                    16 => assert_eq!(e, Error::InvalidChar { field: Field::Offset, pos }, "{}", s),
+                    _ => assert_eq!(e, Error::InvalidChar { field, pos }, "{}", s),
+                }
+            }
+        }
+        assert!(crate::Date::from_str("2023-08-2").is_err());
+        assert!(crate::Date::from_str("2023-08-€").is_err());
+        assert!(crate::Time::from_str("13:0").is_err());
+        assert!(crate::Time::from_str("1€:02").is_err());
+    }
+
+    #[test]
+    fn test_date_validate_str() {
+        for v in ["2023-08-20T13:02:03.123456+08:00", "2023-08-20 13:02:03Z", "2024-02-29T00:00:00.000000Z"] {
+            assert!(DateTime::validate_str(v).is_ok());
+        }
+        for v in ["2023-02-29T13:02:03.000000Z", "2023-08-20T24:02:03.000000Z", "2023-08-20T13:02:03.123456+08", "2023/08/20 13:02:03.000000"] {
+            assert!(DateTime::validate_str(v).is_err());
+            assert_eq!(
+                DateTime::validate_str(v).unwrap_err().to_string(),
+                DateTime::from_str(v).unwrap_err().to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn test_date_dotnet_ticks() {
+        assert_eq!(DateTime::UNIX_EPOCH.to_dotnet_ticks().unwrap(), 621355968000000000);
+        assert_eq!(DateTime::from_dotnet_ticks(621355968000000000).unwrap(), DateTime::UNIX_EPOCH);
+        assert_eq!(DateTime::MIN.to_dotnet_ticks().unwrap(), 0);
+        assert_eq!(DateTime::MAX.to_dotnet_ticks().unwrap(), 3155378975999999990);
+        assert_eq!(DateTime::from_dotnet_ticks(3155378975999999999).unwrap(), DateTime::MAX);
+        assert_eq!(DateTime::from_dotnet_ticks(0).unwrap().to_string(), "0001-01-01 00:00:00.000000");
+        let d = DateTime::from_str("2023-08-20T13:02:03.123456+08:00").unwrap();
+        assert_eq!(d.to_dotnet_ticks().unwrap(), 638281045231234560);
+        assert!(DateTime::from_dotnet_ticks(-1).is_err());
+        assert!(DateTime::from_dotnet_ticks(3155378976000000000).is_err());
+        assert!(DateTime::from_str("0001-01-01T00:00:00+01:00").unwrap().to_dotnet_ticks().is_err());
+    }
+
+    #[test]
+    fn test_date_rfc3339() {
+        let d = DateTime::from_str("2023-08-20T11:12:13.5+08:00").unwrap();
+        assert_eq!(d.offset, 8 * 3600);
+        assert_eq!(d.to_string(), "2023-08-20 11:12:13.500000");
+        assert_eq!(d.to_rfc3339(), "2023-08-20T11:12:13.500000+08:00");
+        let utc = DateTime::from_str("2023-08-20T03:12:13.500000Z").unwrap();
+        assert_eq!(utc.offset, 0);
+        assert_eq!(utc.to_rfc3339(), "2023-08-20T03:12:13.500000Z");
+        assert_eq!(d - utc, SignedDuration::ZERO);
+        assert_eq!(d.cmp(&utc), std::cmp::Ordering::Equal);
+        let west = DateTime::from_str("2023-08-19 23:12:13.500000-04:00").unwrap();
+        assert_eq!(west.to_rfc3339(), "2023-08-19T23:12:13.500000-04:00");
+        assert_eq!(west - utc, SignedDuration::ZERO);
+        assert_eq!((d + Duration::from_secs(3600)).to_rfc3339(), "2023-08-20T12:12:13.500000+08:00");
+        assert_eq!(d.midpoint(utc), d);
+        assert!(DateTime::from_str("2023-08-20T11:12:13+0800").is_err());
+        assert!(DateTime::from_str("2023-08-20T11:12:13 UTC").is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_date_serde() {
+        use crate::{Date, Time};
+        let d = DateTime::from_str("2023-08-20T11:12:13.123456+08:00").unwrap();
+        let json = serde_json::to_string(&d).unwrap();
+        assert_eq!(json, "\"2023-08-20T11:12:13.123456+08:00\"");
+        assert_eq!(serde_json::from_str::<DateTime>(&json).unwrap(), d);
+        let date = Date::from(d);
+        assert_eq!(serde_json::to_string(&date).unwrap(), "\"2023-08-20\"");
+        assert_eq!(serde_json::from_str::<Date>("\"2023-08-20\"").unwrap(), date);
+        let time = Time::from(d);
+        assert_eq!(serde_json::to_string(&time).unwrap(), "\"11:12:13.123456\"");
+        assert_eq!(serde_json::from_str::<Time>("\"11:12:13.123456\"").unwrap(), time);
+        let dur = SignedDuration::from_millis(-1500);
+        assert_eq!(serde_json::to_string(&dur).unwrap(), "\"-PT1.5S\"");
+        assert_eq!(serde_json::from_str::<SignedDuration>("\"-PT1.5S\"").unwrap(), dur);
+        assert!(serde_json::from_str::<DateTime>("\"not a date\"").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_date_serde_binary() {
+        use crate::{Date, Time};
+        let d = DateTime::from_str("2023-08-20T11:12:13.123456+08:00").unwrap();
+        let bytes = bincode::serialize(&d).unwrap();
+        assert_eq!(bytes.len(), 12);
+        assert_eq!(bytes[..8], 1692501133123456i64.to_le_bytes());
+        assert_eq!(bytes[8..], (8 * 3600i32).to_le_bytes());
+        assert_eq!(bincode::deserialize::<DateTime>(&bytes).unwrap().to_rfc3339(), d.to_rfc3339());
+        let date = Date::from_str("1900-03-01").unwrap();
+        let bytes = bincode::serialize(&date).unwrap();
+        assert_eq!(bytes, (-25508i32).to_le_bytes());
+        assert_eq!(bincode::deserialize::<Date>(&bytes).unwrap(), date);
+        let time = Time::from(d);
+        let bytes = bincode::serialize(&time).unwrap();
+        assert_eq!(bytes, 40333123456u64.to_le_bytes());
+        assert_eq!(bincode::deserialize::<Time>(&bytes).unwrap(), time);
+        assert!(bincode::deserialize::<Time>(&86_400_000_000u64.to_le_bytes()).is_err());
+        assert!(bincode::deserialize::<Date>(&i32::MAX.to_le_bytes()).is_err());
+        assert!(bincode::deserialize::<DateTime>(&bincode::serialize(&(253402300799999999i64, 50000i32)).unwrap()).is_err());
+        assert!(bincode::deserialize::<DateTime>(&bincode::serialize(&(0i64, i32::MAX)).unwrap()).is_err());
+        assert!(bincode::deserialize::<DateTime>(&bincode::serialize(&(0i64, -DateTime::MAX_OFFSET - 1)).unwrap()).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_date_system_time_pre_epoch() {
+        use std::time::{SystemTime, UNIX_EPOCH};
+        let before = UNIX_EPOCH - Duration::from_millis(500);
+        let d = DateTime::from(before);
+        assert_eq!(d.to_string(), "1969-12-31 23:59:59.500000");
+        assert_eq!(SystemTime::from(d), before);
+        assert_eq!(DateTime::from(UNIX_EPOCH - Duration::from_nanos(1)).to_string(), "1969-12-31 23:59:59.999999");
+        let d = DateTime::from_str("1900-01-01T08:00:00+08:00").unwrap();
+        let t = SystemTime::from(d);
+        assert_eq!(UNIX_EPOCH.duration_since(t).unwrap().as_secs(), 2208988800);
+        assert_eq!(DateTime::from(t).to_string(), "1900-01-01 00:00:00.000000");
+        let first = DateTime::from_str("0001-01-01 00:00:00").unwrap();
+        assert_eq!(DateTime::from(SystemTime::from(first)), first);
+        let d = DateTime::from_str("1970-01-01T00:00:00+01:00").unwrap();
+        assert_eq!(DateTime::from(SystemTime::from(d)).to_string(), "1969-12-31 23:00:00.000000");
+        assert_eq!(DateTime::try_from_system_time(before).unwrap().micro, 500000);
+        let ancient = UNIX_EPOCH - Duration::from_secs(62135596801);
+        assert_eq!(DateTime::try_from_system_time(ancient).unwrap_err().to_string(), "Rbatis Error: OutOfRangeTimestamp");
+        // the local offset of a clock set before the epoch
+        let ts = crate::sys::Timespec::from(before);
+        assert_eq!((ts.sec, ts.nsec), (-1, 500_000_000));
+        let ts = crate::sys::Timespec::from(UNIX_EPOCH - Duration::from_secs(86400));
+        assert_eq!((ts.sec, ts.nsec), (-86400, 0));
+        let _ = ts.local().tm_utcoff;
+    }
+
+    #[cfg(feature = "rkyv")]
+    #[test]
+    fn test_date_rkyv() {
+        use rkyv::rancor::Error;
+        use crate::{ArchivedDateTime, Date, Time};
+        let d = DateTime::from_str("2023-08-20T13:02:03.123456+08:00").unwrap();
+        let bytes = rkyv::to_bytes::<Error>(&d).unwrap();
+        assert_eq!(bytes.len(), 16);
+        let archived = rkyv::access::<ArchivedDateTime, Error>(&bytes).unwrap();
+        assert_eq!(archived, &d);
+        assert_eq!(archived.year, 2023);
+        assert_eq!(archived, &d.with_offset(0).unwrap());
+        assert_eq!(rkyv::deserialize::<DateTime, Error>(archived).unwrap(), d);
+        let date = Date::from(d);
+        let bytes = rkyv::to_bytes::<Error>(&date).unwrap();
+        assert_eq!(rkyv::from_bytes::<Date, Error>(&bytes).unwrap(), date);
+        let time = Time::from(d);
+        let bytes = rkyv::to_bytes::<Error>(&time).unwrap();
+        assert_eq!(rkyv::from_bytes::<Time, Error>(&bytes).unwrap(), time);
+        let dur = SignedDuration::from_millis(-1500);
+        let bytes = rkyv::to_bytes::<Error>(&dur).unwrap();
+        assert_eq!(rkyv::from_bytes::<SignedDuration, Error>(&bytes).unwrap(), dur);
+    }
 }
@@ -0,0 +1,71 @@
+//! fixed-width decimal writers for the `Display` impls, two digits per table lookup,
+//! and the word-at-a-time digit checks of the parsers
+
+/// "00", "01", ... "99" back to back
+const PAIRS: [u8; 200] = {
+    let mut t = [0u8; 200];
+    let mut i = 0;
+    while i < 100 {
+        t[i * 2] = b'0' + (i / 10) as u8;
+        t[i * 2 + 1] = b'0' + (i % 10) as u8;
+        i += 1;
+    }
+    t
+};
+
+/// write the last two decimal digits of `v` at `buf[at..at + 2]`
+#[inline]
+pub(crate) fn put2(buf: &mut [u8], at: usize, v: u32) {
+    let i = (v % 100) as usize * 2;
+    buf[at..at + 2].copy_from_slice(&PAIRS[i..i + 2]);
+}
+
+/// write the last four decimal digits of `v` at `buf[at..at + 4]`
+#[inline]
+pub(crate) fn put4(buf: &mut [u8], at: usize, v: u32) {
+    put2(buf, at, v / 100);
+    put2(buf, at + 2, v);
+}
+
+/// write the last six decimal digits of `v` at `buf[at..at + 6]`
+#[inline]
+pub(crate) fn put6(buf: &mut [u8], at: usize, v: u32) {
+    put2(buf, at, v / 10000);
+    put2(buf, at + 2, v / 100);
+    put2(buf, at + 4, v);
+}
+
+/// ascii '0' in every byte
+pub(crate) const ZEROS: u64 = 0x3030_3030_3030_3030;
+
+/// whether the bytes of `word` under `mask` are all ascii digits, a swar check of the
+/// high nibble being 3 and the low one at most 9, adding 6 carries 0x3a...0x3f into 0x4_
+#[inline]
+pub(crate) fn all_digits(word: u64, mask: u64) -> bool {
+    let w = word & mask;
+    let high = 0xf0f0_f0f0_f0f0_f0f0 & mask;
+    (w & high) == (ZEROS & mask) && ((w + (0x0606_0606_0606_0606 & mask)) & high) == (ZEROS & mask)
+}
+
+/// the two-digit numbers of digit values `d`: byte `i` becomes `d[i] * 10 + d[i + 1]`,
+/// at most 99 so no byte carries into the next
+#[inline]
+pub(crate) fn pairs(d: u64) -> u64 {
+    d * 10 + (d >> 8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{put2, put4, put6};
+
+    #[test]
+    fn test_digits_put() {
+        let mut buf = *b"............";
+        put2(&mut buf, 0, 7);
+        put4(&mut buf, 2, 2023);
+        put6(&mut buf, 6, 4509);
+        assert_eq!(&buf, b"072023004509");
+        put2(&mut buf, 0, 123);
+        assert_eq!(&buf[..2], b"23");
+    }
+}
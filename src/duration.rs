@@ -0,0 +1,419 @@
+use core::fmt::{self, Display, Formatter};
+use core::ops::{Div, Mul, Neg};
+use core::str::FromStr;
+use core::time::Duration;
+use alloc::format;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "serde")]
+use alloc::string::{String, ToString};
+use crate::error::{Error, Field};
+
+/// A signed span of time with nanosecond precision.
+///
+/// Returned by `DateTime - DateTime`, negative when the left side is the earlier one.
+/// Convert into `std::time::Duration` with `TryFrom` or `unsigned_abs`.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize), rkyv(compare(PartialEq), derive(Debug)))]
+pub struct SignedDuration {
+    nanos: i128,
+}
+
+impl SignedDuration {
+    pub const ZERO: SignedDuration = SignedDuration { nanos: 0 };
+
+    pub const fn from_secs(secs: i64) -> Self {
+        Self { nanos: secs as i128 * 1_000_000_000 }
+    }
+
+    pub const fn from_millis(millis: i64) -> Self {
+        Self { nanos: millis as i128 * 1_000_000 }
+    }
+
+    pub const fn from_micros(micros: i64) -> Self {
+        Self { nanos: micros as i128 * 1_000 }
+    }
+
+    pub const fn from_nanos(nanos: i128) -> Self {
+        Self { nanos }
+    }
+
+    /// whole seconds, truncated toward zero
+    pub const fn as_secs(&self) -> i64 {
+        (self.nanos / 1_000_000_000) as i64
+    }
+
+    /// whole milliseconds, truncated toward zero
+    pub const fn as_millis(&self) -> i128 {
+        self.nanos / 1_000_000
+    }
+
+    /// whole microseconds, truncated toward zero
+    pub const fn as_micros(&self) -> i128 {
+        self.nanos / 1_000
+    }
+
+    pub const fn as_nanos(&self) -> i128 {
+        self.nanos
+    }
+
+    pub const fn is_negative(&self) -> bool {
+        self.nanos < 0
+    }
+
+    /// the ratio `self / other`, e.g. how far into a window an elapsed time is.
+    /// dividing by zero gives an infinite or NaN result like any float division
+    pub fn div_duration(self, other: SignedDuration) -> f64 {
+        self.nanos as f64 / other.nanos as f64
+    }
+
+    /// the magnitude as an unsigned `Duration`, saturating at `Duration::MAX`
+    pub fn unsigned_abs(&self) -> Duration {
+        let nanos = self.nanos.unsigned_abs();
+        let secs = nanos / 1_000_000_000;
+        if secs > u64::MAX as u128 {
+            return Duration::MAX;
+        }
+        Duration::new(secs as u64, (nanos % 1_000_000_000) as u32)
+    }
+}
+
+/// `SignedDuration` literals, `now + 2.days()` or `3.hours()`. import the trait to use them
+pub trait DurationExt {
+    fn weeks(self) -> SignedDuration;
+    fn days(self) -> SignedDuration;
+    fn hours(self) -> SignedDuration;
+    fn minutes(self) -> SignedDuration;
+    fn secs(self) -> SignedDuration;
+    fn millis(self) -> SignedDuration;
+    fn micros(self) -> SignedDuration;
+    fn nanos(self) -> SignedDuration;
+}
+
+impl DurationExt for i64 {
+    fn weeks(self) -> SignedDuration {
+        SignedDuration::from_nanos(self as i128 * 604_800_000_000_000)
+    }
+
+    fn days(self) -> SignedDuration {
+        SignedDuration::from_nanos(self as i128 * 86_400_000_000_000)
+    }
+
+    fn hours(self) -> SignedDuration {
+        SignedDuration::from_nanos(self as i128 * 3_600_000_000_000)
+    }
+
+    fn minutes(self) -> SignedDuration {
+        SignedDuration::from_nanos(self as i128 * 60_000_000_000)
+    }
+
+    fn secs(self) -> SignedDuration {
+        SignedDuration::from_secs(self)
+    }
+
+    fn millis(self) -> SignedDuration {
+        SignedDuration::from_millis(self)
+    }
+
+    fn micros(self) -> SignedDuration {
+        SignedDuration::from_micros(self)
+    }
+
+    fn nanos(self) -> SignedDuration {
+        SignedDuration::from_nanos(self as i128)
+    }
+}
+
+/// A duration broken down into days, hours, minutes, seconds and nanoseconds.
+///
+/// The components hold the magnitude and `negative` the sign, so `-90s` is
+/// `{ negative: true, minutes: 1, seconds: 30, .. }`. Converting back into a
+/// `SignedDuration` accepts components past their usual range, `minutes: 90` is 1h30m.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct DurationParts {
+    pub negative: bool,
+    pub days: u64,
+    /// 0...23
+    pub hours: u64,
+    /// 0...59
+    pub minutes: u64,
+    /// 0...59
+    pub seconds: u64,
+    /// 0...999999999
+    pub nanos: u64,
+}
+
+impl SignedDuration {
+    /// break the duration down into days, hours, minutes, seconds and nanoseconds
+    pub fn to_parts(&self) -> DurationParts {
+        let nanos = self.nanos.unsigned_abs();
+        let secs = nanos / 1_000_000_000;
+        DurationParts {
+            negative: self.is_negative(),
+            days: (secs / 86400) as u64,
+            hours: (secs % 86400 / 3600) as u64,
+            minutes: (secs % 3600 / 60) as u64,
+            seconds: (secs % 60) as u64,
+            nanos: (nanos % 1_000_000_000) as u64,
+        }
+    }
+}
+
+impl From<DurationParts> for SignedDuration {
+    fn from(p: DurationParts) -> Self {
+        let secs = p.days as i128 * 86400 + p.hours as i128 * 3600 + p.minutes as i128 * 60 + p.seconds as i128;
+        let nanos = secs * 1_000_000_000 + p.nanos as i128;
+        Self { nanos: if p.negative { -nanos } else { nanos } }
+    }
+}
+
+impl From<Duration> for DurationParts {
+    fn from(d: Duration) -> Self {
+        SignedDuration::from(d).to_parts()
+    }
+}
+
+impl From<Duration> for SignedDuration {
+    fn from(d: Duration) -> Self {
+        Self { nanos: d.as_nanos() as i128 }
+    }
+}
+
+impl TryFrom<SignedDuration> for Duration {
+    type Error = Error;
+
+    fn try_from(d: SignedDuration) -> Result<Self, Self::Error> {
+        if d.is_negative() {
+            return Err(Error::NegativeDuration);
+        }
+        Ok(d.unsigned_abs())
+    }
+}
+
+impl Mul<u32> for SignedDuration {
+    type Output = SignedDuration;
+
+    fn mul(self, rhs: u32) -> Self::Output {
+        Self { nanos: self.nanos * rhs as i128 }
+    }
+}
+
+impl Div<u32> for SignedDuration {
+    type Output = SignedDuration;
+
+    /// divide, truncating toward zero. panics if `rhs` is zero
+    fn div(self, rhs: u32) -> Self::Output {
+        Self { nanos: self.nanos / rhs as i128 }
+    }
+}
+
+impl Neg for SignedDuration {
+    type Output = SignedDuration;
+
+    fn neg(self) -> Self::Output {
+        Self { nanos: -self.nanos }
+    }
+}
+
+impl Display for SignedDuration {
+    /// ISO 8601 duration in days and time, "PT1.5S", "-P1DT2H3M4.000000005S", zero is "PT0S"
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let p = self.to_parts();
+        if p.negative {
+            f.write_str("-")?;
+        }
+        f.write_str("P")?;
+        if p.days > 0 {
+            write!(f, "{}D", p.days)?;
+        }
+        if p.hours == 0 && p.minutes == 0 && p.seconds == 0 && p.nanos == 0 {
+            if p.days == 0 {
+                f.write_str("T0S")?;
+            }
+            return Ok(());
+        }
+        f.write_str("T")?;
+        if p.hours > 0 {
+            write!(f, "{}H", p.hours)?;
+        }
+        if p.minutes > 0 {
+            write!(f, "{}M", p.minutes)?;
+        }
+        if p.seconds > 0 || p.nanos > 0 {
+            write!(f, "{}", p.seconds)?;
+            if p.nanos > 0 {
+                let frac = format!("{:09}", p.nanos);
+                write!(f, ".{}", frac.trim_end_matches('0'))?;
+            }
+            f.write_str("S")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for SignedDuration {
+    type Err = Error;
+
+    /// parse an ISO 8601 duration of weeks, days, hours, minutes and seconds, "-P1DT2H3M4.5S".
+    /// years and months have no fixed length and are rejected, use `Period` for those.
+    /// only the seconds may have a fraction, of up to 9 digits
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::InvalidDuration;
+        let (negative, rest) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        let rest = rest.strip_prefix('P').ok_or_else(invalid)?;
+        let bytes = rest.as_bytes();
+        let mut nanos: i128 = 0;
+        let mut time = false;
+        let mut components = 0;
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] == b'T' && !time {
+                time = true;
+                i += 1;
+                continue;
+            }
+            let start = i;
+            while i < bytes.len() && bytes[i].is_ascii_digit() {
+                i += 1;
+            }
+            let whole: i128 = rest[start..i].parse().map_err(|_| invalid())?;
+            let mut frac: Option<i128> = None;
+            if i < bytes.len() && (bytes[i] == b'.' || bytes[i] == b',') {
+                i += 1;
+                let start = i;
+                while i < bytes.len() && bytes[i].is_ascii_digit() {
+                    i += 1;
+                }
+                if i == start || i - start > 9 {
+                    return Err(invalid());
+                }
+                let digits: i128 = rest[start..i].parse().map_err(|_| invalid())?;
+                frac = Some(digits * 10i128.pow((9 - (i - start)) as u32));
+            }
+            let unit: i128 = match (time, bytes.get(i)) {
+                (false, Some(b'W')) => 604800,
+                (false, Some(b'D')) => 86400,
+                (true, Some(b'H')) => 3600,
+                (true, Some(b'M')) => 60,
+                (true, Some(b'S')) => 1,
+                _ => return Err(invalid()),
+            };
+            if frac.is_some() && unit != 1 {
+                return Err(invalid());
+            }
+            nanos = whole
+                .checked_mul(unit * 1_000_000_000)
+                .and_then(|n| n.checked_add(frac.unwrap_or(0)))
+                .and_then(|n| n.checked_add(nanos))
+                .ok_or(Error::OutOfRange { field: Field::Duration })?;
+            components += 1;
+            i += 1;
+        }
+        if components == 0 || bytes.last() == Some(&b'T') {
+            return Err(invalid());
+        }
+        Ok(Self { nanos: if negative { -nanos } else { nanos } })
+    }
+}
+
+/// serialized as an ISO 8601 duration string, see `Display`
+#[cfg(feature = "serde")]
+impl Serialize for SignedDuration {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for SignedDuration {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de> {
+        use serde::de::Error;
+        let s = String::deserialize(deserializer)?;
+        SignedDuration::from_str(&s).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use std::time::Duration;
+    use crate::{DurationParts, SignedDuration};
+
+    #[test]
+    fn test_signed_duration() {
+        let d = SignedDuration::from_millis(-1500);
+        assert!(d.is_negative());
+        assert_eq!(d.as_secs(), -1);
+        assert_eq!(d.as_micros(), -1_500_000);
+        assert_eq!(d.unsigned_abs(), Duration::from_millis(1500));
+        assert!(Duration::try_from(d).is_err());
+        assert_eq!(Duration::try_from(-d).unwrap(), Duration::from_millis(1500));
+        assert_eq!(SignedDuration::from(Duration::from_secs(3)), SignedDuration::from_secs(3));
+    }
+
+    #[test]
+    fn test_duration_ext() {
+        use crate::{DateTime, DurationExt};
+        assert_eq!(2.days(), SignedDuration::from_secs(172800));
+        assert_eq!(1.weeks(), 7.days());
+        assert_eq!(3.hours(), 180.minutes());
+        assert_eq!(500.millis(), SignedDuration::from_micros(500_000));
+        assert_eq!(90.secs().to_string(), "PT1M30S");
+        assert_eq!(1.micros().as_nanos(), 1000);
+        assert_eq!((-5).nanos().as_nanos(), -5);
+        assert_eq!(i64::MAX.weeks().as_nanos(), i64::MAX as i128 * 604_800_000_000_000);
+        let d = DateTime::from_str("2023-08-20T13:02:03+08:00").unwrap();
+        assert_eq!((d + 2.days()).to_rfc3339(), "2023-08-22T13:02:03.000000+08:00");
+        assert_eq!((d - 90.minutes()).to_rfc3339(), "2023-08-20T11:32:03.000000+08:00");
+        assert_eq!((d + (-1).nanos()).to_rfc3339(), "2023-08-20T13:02:02.999999+08:00");
+        assert_eq!((d - 1.nanos()).to_rfc3339(), "2023-08-20T13:02:02.999999+08:00");
+        let mut e = d;
+        e += 1.hours();
+        e -= 30.minutes();
+        assert_eq!(e - d, 30.minutes());
+        assert_eq!(DateTime::MAX.checked_add_signed(1.micros()), None);
+        assert_eq!(DateTime::MIN.checked_add_signed((-1).micros()), None);
+    }
+
+    #[test]
+    fn test_duration_parts() {
+        let d = SignedDuration::from_nanos(-(((86400 + 2 * 3600 + 3 * 60 + 4) as i128) * 1_000_000_000 + 5));
+        let p = d.to_parts();
+        assert_eq!(p, DurationParts { negative: true, days: 1, hours: 2, minutes: 3, seconds: 4, nanos: 5 });
+        assert_eq!(SignedDuration::from(p), d);
+        let p = DurationParts { minutes: 90, ..Default::default() };
+        assert_eq!(SignedDuration::from(p).to_parts(), DurationParts { hours: 1, minutes: 30, ..Default::default() });
+        assert_eq!(DurationParts::from(Duration::from_secs(61)).minutes, 1);
+    }
+
+    #[test]
+    fn test_duration_scale() {
+        let d = SignedDuration::from_millis(1500);
+        assert_eq!(d * 3, SignedDuration::from_millis(4500));
+        assert_eq!(d / 2, SignedDuration::from_millis(750));
+        assert_eq!(-d / 4, SignedDuration::from_micros(-375000));
+        assert_eq!(d.div_duration(SignedDuration::from_secs(6)), 0.25);
+        assert_eq!((-d).div_duration(d), -1.0);
+    }
+
+    #[test]
+    fn test_duration_iso8601() {
+        let d = SignedDuration::from_nanos(-(((86400 + 2 * 3600 + 3 * 60 + 4) as i128) * 1_000_000_000 + 5));
+        assert_eq!(d.to_string(), "-P1DT2H3M4.000000005S");
+        assert_eq!(SignedDuration::from_str("-P1DT2H3M4.000000005S").unwrap(), d);
+        assert_eq!(SignedDuration::from_millis(1500).to_string(), "PT1.5S");
+        assert_eq!(SignedDuration::from_secs(86400).to_string(), "P1D");
+        assert_eq!(SignedDuration::ZERO.to_string(), "PT0S");
+        assert_eq!(SignedDuration::from_str("P1W").unwrap(), SignedDuration::from_secs(7 * 86400));
+        assert_eq!(SignedDuration::from_str("PT90M").unwrap(), SignedDuration::from_secs(5400));
+        assert_eq!(SignedDuration::from_str("PT0,25S").unwrap(), SignedDuration::from_millis(250));
+        assert!(SignedDuration::from_str("P1M").is_err());
+        assert!(SignedDuration::from_str("PT1.5H").is_err());
+        assert!(SignedDuration::from_str("PT").is_err());
+        assert!(SignedDuration::from_str("P").is_err());
+        assert!(SignedDuration::from_str("1S").is_err());
+    }
+}
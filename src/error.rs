@@ -1,45 +1,253 @@
-use std::fmt;
-use std::fmt::Display;
+use core::fmt;
+use core::fmt::Display;
 
-#[derive(Clone, Debug)]
-pub enum Error {
-    E(String),
+/// The part of a date or time an `Error` is about
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum Field {
+    Year,
+    Month,
+    Day,
+    Hour,
+    Minute,
+    Second,
+    Micro,
+    Offset,
+    /// the hours of an offset, at most 14
+    OffsetHour,
+    /// the minutes of an offset, at most 59
+    OffsetMinute,
+    /// the seconds of an offset, at most 59
+    OffsetSecond,
+    /// the date as a whole, e.g. a day count
+    Date,
+    /// the time of day as a whole
+    Time,
+    /// the `-` between the date fields
+    DateSep,
+    /// the `:` between the time fields
+    TimeSep,
+    /// the `T` or space between the date and the time
+    DateTimeSep,
+    /// a literal of a strftime-style pattern
+    Literal,
+    /// a count of seconds or smaller units since the epoch
+    Timestamp,
+    AmPm,
+    Weekday,
+    Duration,
+    /// a windows FILETIME
+    Filetime,
+    Nanos,
+    Ntp,
+    /// the delay of an http Retry-After header
+    RetryAfter,
+    /// .NET ticks
+    Ticks,
 }
 
-impl From<&str> for Error {
-    fn from(arg: &str) -> Self {
-        return Error::E(arg.to_string());
+impl Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
     }
 }
 
-impl From<std::string::String> for Error {
-    fn from(arg: String) -> Self {
-        return Error::E(arg);
+/// Why parsing or a conversion failed. `Display` writes the code of the variant,
+/// e.g. "Rbatis Error: OutOfRangeDay", nothing is allocated
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum Error {
+    /// not a digit or the expected separator at byte `pos` of the input
+    InvalidChar { field: Field, pos: usize },
+    /// a value past the range of `field`, e.g. a month 13
+    OutOfRange { field: Field },
+    /// the input ends before the value does
+    TooShort,
+    /// input left over after the value
+    TrailingInput,
+    SecondFractionMissing,
+    /// more than 9 digits of fraction
+    SecondFractionTooLong,
+    /// an offset of a form that is not supported
+    InvalidOffset,
+    InvalidDate,
+    InvalidTime,
+    InvalidDuration,
+    NegativeDuration,
+    InvalidLength,
+    InvalidClampRange,
+    /// digits past the precision of the target with `PrecisionLoss::Reject`
+    InexactConversion,
+    InvalidHttpDate,
+    /// not an ISO 8601 `Rn/start/duration` or `Rn/start/end`
+    InvalidRepeatingInterval,
+    InvalidIcalTzid,
+    InvalidTouchLength,
+    /// a strftime-style specifier `parse_from_str` does not know
+    InvalidFormatSpecifier,
+    /// a parsed weekday that is not the weekday of the parsed date
+    InconsistentWeekday,
+    /// the blank or all-zero exif value of an unknown time
+    UnknownExifDate,
+    InvalidAvroType,
+    InvalidMsgpackTimestamp,
+    NotTimestampArray,
+    /// postgres `infinity` or `-infinity`
+    InfiniteTimestamp,
+    InfiniteDate,
+}
+
+impl Error {
+    /// the code `Display` writes after the prefix, e.g. "InvalidCharYear"
+    pub fn code(&self) -> Code {
+        Code(*self)
     }
 }
 
+/// The code of an `Error` as written by `Display`, without the prefix
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Code(Error);
+
+impl Display for Code {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Error::InvalidChar { field, .. } => write!(f, "InvalidChar{}", field),
+            Error::OutOfRange { field } => write!(f, "OutOfRange{}", field),
+            other => fmt::Debug::fmt(&other, f),
+        }
+    }
+}
 
 impl Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        return match self {
-            Error::E(ref err) => {
-                write!(f, "Rbatis Error: {}", err)
-            }
-        };
+        write!(f, "Rbatis Error: {}", self.code())
     }
 }
 
-impl std::error::Error for Error {
-    fn description(&self) -> &str {
-        return match self {
-            Error::E(data) => data.as_str(),
-        };
+impl core::error::Error for Error {}
+
+impl Error {
+    /// this error together with a preview of the input it is about, for messages that show
+    /// what failed to parse: `DateTime::from_str(s).map_err(|e| e.with_input(s))`
+    pub fn with_input(self, input: &str) -> ParseError {
+        let mut len = input.len().min(PREVIEW_LEN);
+        while !input.is_char_boundary(len) {
+            len -= 1;
+        }
+        let mut preview = [0; PREVIEW_LEN];
+        preview[..len].copy_from_slice(&input.as_bytes()[..len]);
+        ParseError {
+            error: self,
+            preview,
+            len: len as u8,
+            truncated: len < input.len(),
+        }
     }
 }
 
-impl Default for Error {
-    fn default() -> Self {
-        Error::E(String::new())
+/// at most this many bytes of the input are kept by `ParseError`
+const PREVIEW_LEN: usize = 32;
+
+/// An `Error` with the first 32 bytes of the input that caused it, kept inline so it is
+/// still `Copy` and allocation free. `Display` writes both, e.g.
+/// `Rbatis Error: InvalidCharMonth at byte 5 of "2023-x1-01"`, and `source` is the `Error`
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ParseError {
+    error: Error,
+    preview: [u8; PREVIEW_LEN],
+    len: u8,
+    truncated: bool,
+}
+
+impl ParseError {
+    /// the error without the input
+    pub fn error(&self) -> Error {
+        self.error
+    }
+
+    /// the start of the input, cut at a char boundary after at most 32 bytes
+    pub fn input(&self) -> &str {
+        core::str::from_utf8(&self.preview[..self.len as usize]).unwrap_or_default()
+    }
+
+    /// whether the input was longer than `input`
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
     }
 }
 
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        e.error
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.error)?;
+        if let Error::InvalidChar { pos, .. } = self.error {
+            write!(f, " at byte {}", pos)?;
+        }
+        write!(f, " of {:?}", self.input())?;
+        if self.truncated {
+            f.write_str("...")?;
+        }
+        Ok(())
+    }
+}
+
+impl core::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+    use core::str::FromStr;
+    use crate::error::{Error, Field};
+
+    #[test]
+    fn test_error_display() {
+        let e = Error::InvalidChar { field: Field::DateSep, pos: 4 };
+        assert_eq!(e.to_string(), "Rbatis Error: InvalidCharDateSep");
+        assert_eq!(Error::OutOfRange { field: Field::Day }.code().to_string(), "OutOfRangeDay");
+        assert_eq!(Error::TooShort.to_string(), "Rbatis Error: TooShort");
+        assert_eq!(core::mem::size_of::<Error>(), 16);
+    }
+
+    #[test]
+    fn test_error_with_input() {
+        let input = "2023-x1-01";
+        let e = crate::Date::from_str(input).map_err(|e| e.with_input(input)).unwrap_err();
+        assert_eq!(e.to_string(), "Rbatis Error: InvalidCharMonth at byte 5 of \"2023-x1-01\"");
+        assert_eq!(e.error(), Error::InvalidChar { field: Field::Month, pos: 5 });
+        assert_eq!(e.input(), input);
+        assert!(!e.is_truncated());
+        let source = core::error::Error::source(&e).unwrap();
+        assert_eq!(source.to_string(), "Rbatis Error: InvalidCharMonth");
+        assert!(core::error::Error::source(source).is_none());
+        assert_eq!(Error::from(e), e.error());
+
+        let long = "2023-08-20T13:02:03.123456+08:00 and some trailing text";
+        let e = Error::TrailingInput.with_input(long);
+        assert_eq!(e.input(), &long[..32]);
+        assert!(e.is_truncated());
+        assert_eq!(e.to_string(), "Rbatis Error: TrailingInput of \"2023-08-20T13:02:03.123456+08:00\"...");
+        let wide = "2023年08月20日2023年08月20日2023年08月20日";
+        let e = Error::InvalidDate.with_input(wide);
+        assert!(e.input().len() <= 32 && wide.starts_with(e.input()));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_error_boxed() {
+        fn parse(s: &str) -> Result<crate::DateTime, Box<dyn std::error::Error + Send + Sync>> {
+            Ok(crate::DateTime::from_str(s).map_err(|e| e.with_input(s))?)
+        }
+        let e = parse("2023-08-20T25:00:00Z").unwrap_err();
+        assert_eq!(e.to_string(), "Rbatis Error: OutOfRangeHour of \"2023-08-20T25:00:00Z\"");
+        assert!(e.source().is_some());
+    }
+}
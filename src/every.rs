@@ -0,0 +1,92 @@
+use core::iter::FusedIterator;
+use core::time::Duration;
+use crate::{DateTime, Period};
+
+/// Iterator over instants spaced by a fixed duration or a calendar period, see `DateTime::every`.
+///
+/// Every item is computed from the start value (`start + n * step`) rather than the
+/// previous item, so monthly steps starting on the 31st come back to the 31st after
+/// shorter months. The iterator ends once the next instant is past `DateTime::MAX`.
+#[derive(Copy, Clone, Debug)]
+pub struct Every {
+    start: DateTime,
+    step: Step,
+    n: Option<u32>,
+}
+
+#[derive(Copy, Clone, Debug)]
+enum Step {
+    Fixed(Duration),
+    Calendar(Period),
+}
+
+impl Every {
+    pub(crate) fn fixed(start: DateTime, step: Duration) -> Self {
+        Self { start, step: Step::Fixed(step), n: Some(0) }
+    }
+
+    pub(crate) fn calendar(start: DateTime, step: Period) -> Self {
+        Self { start, step: Step::Calendar(step), n: Some(0) }
+    }
+
+    fn nth_tick(&self, n: u32) -> Option<DateTime> {
+        match self.step {
+            Step::Fixed(d) => self.start.checked_add(d.checked_mul(n)?),
+            Step::Calendar(p) => {
+                let n = i32::try_from(n).ok()?;
+                let p = Period {
+                    years: p.years.checked_mul(n)?,
+                    months: p.months.checked_mul(n)?,
+                    days: p.days.checked_mul(n)?,
+                    hours: p.hours.checked_mul(n)?,
+                    minutes: p.minutes.checked_mul(n)?,
+                    seconds: p.seconds.checked_mul(n)?,
+                    micros: p.micros.checked_mul(n)?,
+                };
+                self.start.checked_add_period(p)
+            }
+        }
+    }
+}
+
+impl Iterator for Every {
+    type Item = DateTime;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let n = self.n?;
+        let v = self.nth_tick(n);
+        self.n = match v {
+            Some(_) => n.checked_add(1),
+            None => None,
+        };
+        v
+    }
+}
+
+impl FusedIterator for Every {}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use std::time::Duration;
+    use crate::{DateTime, Period};
+
+    #[test]
+    fn test_every() {
+        let d = DateTime::from_str("2023-12-31 23:00:00.000000").unwrap();
+        let v: Vec<String> = d.every(Duration::from_secs(1800)).take(3).map(|v| v.to_string()).collect();
+        assert_eq!(v, ["2023-12-31 23:00:00.000000", "2023-12-31 23:30:00.000000", "2024-01-01 00:00:00.000000"]);
+    }
+
+    #[test]
+    fn test_every_period() {
+        let d = DateTime::from_str("2024-01-31 10:00:00.000000").unwrap();
+        let month = Period { months: 1, ..Default::default() };
+        let v: Vec<String> = d.every_period(month).take(3).map(|v| v.to_string()).collect();
+        assert_eq!(v, ["2024-01-31 10:00:00.000000", "2024-02-29 10:00:00.000000", "2024-03-31 10:00:00.000000"]);
+        let last = DateTime::from_str("9999-11-30 00:00:00.000000").unwrap();
+        assert_eq!(last.every_period(month).count(), 2);
+        let huge = Period { days: 200_000_000, ..Default::default() };
+        assert_eq!(d.every_period(huge).take(3).count(), 1);
+    }
+}
@@ -0,0 +1,64 @@
+use arbitrary::{Arbitrary, Result, Unstructured};
+use crate::{Date, DateTime, Time};
+
+/// offsets in whole minutes strictly within ±15:00
+const MAX_OFFSET_MINUTES: i32 = 15 * 60 - 1;
+
+impl<'a> Arbitrary<'a> for Date {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let days = u.int_in_range(Date::MIN.to_unix_day()..=Date::MAX.to_unix_day())?;
+        Ok(Date::from_days_since_epoch(days))
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (0, Some(8))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Time {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let micros = u.int_in_range(0..=86_399_999_999i64)?;
+        Ok(Time::from(DateTime::from_wall_micros(micros, 0)))
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (0, Some(8))
+    }
+}
+
+impl<'a> Arbitrary<'a> for DateTime {
+    /// wall-clock fields between `DateTime::MIN` and `DateTime::MAX` at any whole-minute offset
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let micros = u.int_in_range(DateTime::MIN.wall_micros()..=DateTime::MAX.wall_micros())?;
+        let minutes = u.int_in_range(-MAX_OFFSET_MINUTES..=MAX_OFFSET_MINUTES)?;
+        Ok(DateTime::from_wall_micros(micros, minutes * 60))
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (0, Some(12))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use arbitrary::{Arbitrary, Unstructured};
+    use crate::{Date, DateTime, Time};
+
+    #[test]
+    fn test_arbitrary_valid() {
+        let bytes: Vec<u8> = (0..4096u32).map(|i| (i.wrapping_mul(2654435761) >> 13) as u8).collect();
+        let mut u = Unstructured::new(&bytes);
+        while !u.is_empty() {
+            let d = DateTime::arbitrary(&mut u).unwrap();
+            assert_eq!(DateTime::from_str(&d.to_rfc3339()).unwrap(), d);
+            let date = Date::arbitrary(&mut u).unwrap();
+            assert_eq!(Date::from_str(&date.to_string()).unwrap(), date);
+            let t = Time::arbitrary(&mut u).unwrap();
+            assert_eq!(Time::from_str(&t.to_string()).unwrap(), t);
+        }
+        // exhausted input still yields a valid value
+        let d = DateTime::arbitrary(&mut Unstructured::new(&[])).unwrap();
+        assert_eq!(d.to_rfc3339(), "0001-01-01T00:00:00.000000-14:59");
+    }
+}
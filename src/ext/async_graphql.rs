@@ -0,0 +1,72 @@
+use std::str::FromStr;
+use async_graphql::{InputType, InputValueError, InputValueResult, Scalar, ScalarType, Value};
+use crate::{Date, DateTime};
+use crate::error::Error;
+
+/// the input string and what was expected of it, rather than only the error code
+fn invalid<T: InputType>(s: &str, expected: &str, e: Error) -> InputValueError<T> {
+    InputValueError::custom(format!("invalid value {:?}, expected {} ({})", s, expected, e.code()))
+}
+
+/// an rfc 3339 string, "2023-08-20T13:02:03.123456+08:00"
+#[Scalar(name = "DateTime")]
+impl ScalarType for DateTime {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::String(s) => DateTime::from_str(s)
+                .map_err(|e| invalid(s, "an RFC 3339 datetime like \"2023-08-20T13:02:03+08:00\"", e)),
+            _ => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn is_valid(value: &Value) -> bool {
+        matches!(value, Value::String(_))
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.to_rfc3339())
+    }
+}
+
+/// an rfc 3339 full-date string, "2023-08-20"
+#[Scalar(name = "Date")]
+impl ScalarType for Date {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::String(s) => Date::from_str(s).map_err(|e| invalid(s, "a date like \"2023-08-20\"", e)),
+            _ => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn is_valid(value: &Value) -> bool {
+        matches!(value, Value::String(_))
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use async_graphql::{ScalarType, Value};
+    use crate::{Date, DateTime};
+
+    #[test]
+    fn test_graphql_scalars() {
+        let d = DateTime::from_str("2023-08-20T13:02:03.123456+08:00").unwrap();
+        let v = d.to_value();
+        assert_eq!(v, Value::String("2023-08-20T13:02:03.123456+08:00".to_string()));
+        assert_eq!(<DateTime as ScalarType>::parse(v).unwrap(), d);
+        let e = <DateTime as ScalarType>::parse(Value::String("2023-13-20T13:02:03Z".to_string())).unwrap_err();
+        assert_eq!(
+            e.into_server_error(Default::default()).message,
+            r#"Failed to parse "DateTime": invalid value "2023-13-20T13:02:03Z", expected an RFC 3339 datetime like "2023-08-20T13:02:03+08:00" (OutOfRangeMonth)"#
+        );
+        assert!(<DateTime as ScalarType>::parse(Value::from(1)).is_err());
+        let date = Date::from_str("2023-08-20").unwrap();
+        assert_eq!(<Date as ScalarType>::parse(date.to_value()).unwrap(), date);
+        assert!(<Date as ScalarType>::parse(Value::String("20/08/2023".to_string())).is_err());
+    }
+}
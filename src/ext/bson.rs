@@ -0,0 +1,38 @@
+use crate::DateTime;
+use crate::error::{Error, Field};
+
+impl From<DateTime> for bson::DateTime {
+    /// the instant in milliseconds. microseconds are truncated toward the past,
+    /// so 1969-12-31 23:59:59.999999 becomes 23:59:59.999 and ordering is kept.
+    /// `from_millis(v.to_timestamp(TimestampUnit::Millis, policy)?)` to round or reject instead
+    fn from(v: DateTime) -> Self {
+        bson::DateTime::from_millis(v.unix_micros().div_euclid(1000))
+    }
+}
+
+impl TryFrom<bson::DateTime> for DateTime {
+    type Error = Error;
+
+    /// at utc. bson covers a wider range, fails outside of the years 0001...9999
+    fn try_from(v: bson::DateTime) -> Result<Self, Self::Error> {
+        DateTime::from_unix_micros(v.timestamp_millis() as i128 * 1000)
+            .ok_or(Error::OutOfRange { field: Field::Timestamp })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use crate::DateTime;
+
+    #[test]
+    fn test_bson_datetime() {
+        let d = DateTime::from_str("2023-08-20T13:02:03.123456+08:00").unwrap();
+        let b = bson::DateTime::from(d);
+        assert_eq!(b.timestamp_millis(), 1692507723123);
+        assert_eq!(DateTime::try_from(b).unwrap().to_rfc3339(), "2023-08-20T05:02:03.123000Z");
+        let before = DateTime::from_str("1969-12-31T23:59:59.999999Z").unwrap();
+        assert_eq!(bson::DateTime::from(before).timestamp_millis(), -1);
+        assert!(DateTime::try_from(bson::DateTime::MAX).is_err());
+    }
+}
@@ -0,0 +1,44 @@
+//! appending formatted values to a `bytes::BytesMut`, as network codecs assemble frames
+use bytes::{BufMut, BytesMut};
+use crate::{Date, DateTime, Time};
+
+impl DateTime {
+    /// append `to_rfc3339` to `buf` without an intermediate `String`
+    pub fn format_into_bytes(&self, buf: &mut BytesMut) {
+        let (ascii, len) = self.to_rfc3339_ascii();
+        buf.put_slice(&ascii[..len]);
+    }
+}
+
+impl Date {
+    /// append "YYYY-MM-DD" to `buf` without an intermediate `String`
+    pub fn format_into_bytes(&self, buf: &mut BytesMut) {
+        buf.put_slice(&self.to_ascii());
+    }
+}
+
+impl Time {
+    /// append "HH:MM:SS.ffffff" to `buf` without an intermediate `String`
+    pub fn format_into_bytes(&self, buf: &mut BytesMut) {
+        buf.put_slice(&self.to_ascii());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use bytes::BytesMut;
+    use crate::{Date, DateTime, Time};
+
+    #[test]
+    fn test_format_into_bytes() {
+        let d = DateTime::from_str("2023-08-20T13:02:03.123456+08:00").unwrap();
+        let mut buf = BytesMut::from(&b"date: "[..]);
+        d.format_into_bytes(&mut buf);
+        buf.extend_from_slice(b" ");
+        Date::from(d).format_into_bytes(&mut buf);
+        buf.extend_from_slice(b" ");
+        Time::from(d).format_into_bytes(&mut buf);
+        assert_eq!(&buf[..], b"date: 2023-08-20T13:02:03.123456+08:00 2023-08-20 13:02:03.123456");
+    }
+}
@@ -0,0 +1,136 @@
+use ::chrono::{Datelike, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc};
+use crate::{Date, DateTime};
+use crate::error::{Error, Field};
+
+impl TryFrom<Date> for NaiveDate {
+    type Error = Error;
+
+    fn try_from(d: Date) -> Result<Self, Self::Error> {
+        NaiveDate::from_ymd_opt(d.year as i32, d.mon as u32, d.day as u32)
+            .ok_or(Error::InvalidDate)
+    }
+}
+
+impl TryFrom<NaiveDate> for Date {
+    type Error = Error;
+
+    /// fails outside of `Date::MIN...Date::MAX`
+    fn try_from(d: NaiveDate) -> Result<Self, Self::Error> {
+        if d.year() < Date::MIN.year as i32 || d.year() > Date::MAX.year as i32 {
+            return Err(Error::OutOfRange { field: Field::Year });
+        }
+        Ok(Date {
+            day: d.day() as u8,
+            mon: d.month() as u8,
+            year: d.year() as u16,
+        })
+    }
+}
+
+impl TryFrom<DateTime> for NaiveDateTime {
+    type Error = Error;
+
+    /// the wall-clock fields, dropping the offset
+    fn try_from(v: DateTime) -> Result<Self, Self::Error> {
+        let date = NaiveDate::try_from(Date::from(v))?;
+        let time = NaiveTime::from_hms_micro_opt(v.hour as u32, v.min as u32, v.sec as u32, v.micro)
+            .ok_or(Error::InvalidTime)?;
+        Ok(date.and_time(time))
+    }
+}
+
+impl TryFrom<NaiveDateTime> for DateTime {
+    type Error = Error;
+
+    /// the wall-clock fields at utc, truncated to microseconds.
+    /// a leap second is clamped to the last microsecond of the minute
+    fn try_from(v: NaiveDateTime) -> Result<Self, Self::Error> {
+        let date = Date::try_from(v.date())?;
+        Ok(DateTime {
+            micro: (v.nanosecond() / 1000).min(999999),
+            sec: v.second() as u8,
+            min: v.minute() as u8,
+            hour: v.hour() as u8,
+            day: date.day,
+            mon: date.mon,
+            year: date.year,
+            offset: 0,
+        })
+    }
+}
+
+impl TryFrom<DateTime> for ::chrono::DateTime<FixedOffset> {
+    type Error = Error;
+
+    fn try_from(v: DateTime) -> Result<Self, Self::Error> {
+        let offset = FixedOffset::east_opt(v.offset).ok_or(Error::InvalidOffset)?;
+        NaiveDateTime::try_from(v)?
+            .and_local_timezone(offset)
+            .single()
+            .ok_or(Error::InvalidOffset)
+    }
+}
+
+impl TryFrom<::chrono::DateTime<FixedOffset>> for DateTime {
+    type Error = Error;
+
+    /// the local fields and offset of `v`, fails with `OutOfRangeOffset` past `DateTime::MAX_OFFSET`
+    fn try_from(v: ::chrono::DateTime<FixedOffset>) -> Result<Self, Self::Error> {
+        Ok(DateTime {
+            offset: DateTime::check_offset(v.offset().local_minus_utc())?,
+            ..DateTime::try_from(v.naive_local())?
+        })
+    }
+}
+
+impl TryFrom<DateTime> for ::chrono::DateTime<Utc> {
+    type Error = Error;
+
+    /// the same instant
+    fn try_from(v: DateTime) -> Result<Self, Self::Error> {
+        Ok(::chrono::DateTime::<FixedOffset>::try_from(v)?.with_timezone(&Utc))
+    }
+}
+
+impl TryFrom<::chrono::DateTime<Utc>> for DateTime {
+    type Error = Error;
+
+    fn try_from(v: ::chrono::DateTime<Utc>) -> Result<Self, Self::Error> {
+        DateTime::try_from(v.naive_utc())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use ::chrono::{FixedOffset, NaiveDate, NaiveDateTime, Utc};
+    use crate::{Date, DateTime};
+    use crate::error::{Error, Field};
+
+    #[test]
+    fn test_chrono_date() {
+        let d = Date::from_str("2024-02-29").unwrap();
+        let n = NaiveDate::try_from(d).unwrap();
+        assert_eq!(n, NaiveDate::from_ymd_opt(2024, 2, 29).unwrap());
+        assert_eq!(Date::try_from(n).unwrap(), d);
+        assert!(NaiveDate::try_from(Date { day: 30, mon: 2, year: 2024 }).is_err());
+        assert!(Date::try_from(NaiveDate::from_ymd_opt(10000, 1, 1).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_chrono_datetime() {
+        let d = DateTime::from_str("2023-08-20T13:02:03.123456+08:00").unwrap();
+        let naive = NaiveDateTime::try_from(d).unwrap();
+        assert_eq!(naive.to_string(), "2023-08-20 13:02:03.123456");
+        assert_eq!(DateTime::try_from(naive).unwrap().to_rfc3339(), "2023-08-20T13:02:03.123456Z");
+        let fixed = ::chrono::DateTime::<FixedOffset>::try_from(d).unwrap();
+        assert_eq!(fixed.naive_local(), naive);
+        assert_eq!(fixed.offset().local_minus_utc(), 8 * 3600);
+        assert_eq!(DateTime::try_from(fixed).unwrap(), d);
+        let utc = ::chrono::DateTime::<Utc>::try_from(d).unwrap();
+        assert_eq!(utc.naive_utc().to_string(), "2023-08-20 05:02:03.123456");
+        assert_eq!(DateTime::try_from(utc).unwrap().to_rfc3339(), "2023-08-20T05:02:03.123456Z");
+        let far = fixed.with_timezone(&FixedOffset::east_opt(20 * 3600).unwrap());
+        assert_eq!(DateTime::try_from(far).unwrap_err(), Error::OutOfRange { field: Field::Offset });
+    }
+}
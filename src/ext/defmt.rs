@@ -0,0 +1,35 @@
+//! only the integer fields go over the wire, the host formats them with the interned pattern
+use defmt::{Format, Formatter, write};
+use crate::{Date, DateTime, Time};
+
+impl Format for Date {
+    /// `2023-08-20`
+    fn format(&self, f: Formatter) {
+        write!(f, "{=u16:04}-{=u8:02}-{=u8:02}", self.year, self.mon, self.day)
+    }
+}
+
+impl Format for Time {
+    /// `13:02:03.123456`
+    fn format(&self, f: Formatter) {
+        write!(f, "{=u8:02}:{=u8:02}:{=u8:02}.{=u32:06}", self.hour, self.min, self.sec, self.micro)
+    }
+}
+
+impl Format for DateTime {
+    /// rfc 3339, `2023-08-20T13:02:03.123456+08:00`
+    fn format(&self, f: Formatter) {
+        write!(
+            f,
+            "{=u16:04}-{=u8:02}-{=u8:02}T{=u8:02}:{=u8:02}:{=u8:02}.{=u32:06}",
+            self.year, self.mon, self.day, self.hour, self.min, self.sec, self.micro
+        );
+        if self.offset == 0 {
+            write!(f, "Z")
+        } else {
+            let minutes = self.offset.unsigned_abs() / 60;
+            let sign = if self.offset < 0 { "-" } else { "+" };
+            write!(f, "{=str}{=u8:02}:{=u8:02}", sign, (minutes / 60) as u8, (minutes % 60) as u8)
+        }
+    }
+}
@@ -0,0 +1,71 @@
+//! diesel support for the postgres `Timestamptz`, `Timestamp` and `Date` sql types.
+//! `AsExpression` and `FromSqlRow` are derived on the types themselves
+use ::diesel::deserialize::{self, FromSql};
+use ::diesel::pg::data_types::{PgDate, PgTimestamp};
+use ::diesel::pg::{Pg, PgValue};
+use ::diesel::serialize::{self, Output, ToSql};
+use ::diesel::sql_types::{Date as SqlDate, Timestamp, Timestamptz};
+use crate::{Date, DateTime};
+use crate::ext::pg;
+
+impl ToSql<Timestamptz, Pg> for DateTime {
+    /// the instant
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        ToSql::<Timestamp, Pg>::to_sql(&PgTimestamp(pg::to_timestamptz(*self)), &mut out.reborrow())
+    }
+}
+
+impl FromSql<Timestamptz, Pg> for DateTime {
+    /// at utc
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        let PgTimestamp(micros) = FromSql::<Timestamp, Pg>::from_sql(bytes)?;
+        Ok(pg::from_timestamp(micros)?)
+    }
+}
+
+impl ToSql<Timestamp, Pg> for DateTime {
+    /// the wall-clock fields
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        ToSql::<Timestamp, Pg>::to_sql(&PgTimestamp(pg::to_timestamp(*self)), &mut out.reborrow())
+    }
+}
+
+impl FromSql<Timestamp, Pg> for DateTime {
+    /// the wall-clock fields at utc
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        FromSql::<Timestamptz, Pg>::from_sql(bytes)
+    }
+}
+
+impl ToSql<SqlDate, Pg> for Date {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        ToSql::<SqlDate, Pg>::to_sql(&PgDate(pg::to_date(*self)), &mut out.reborrow())
+    }
+}
+
+impl FromSql<SqlDate, Pg> for Date {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        let PgDate(days) = FromSql::<SqlDate, Pg>::from_sql(bytes)?;
+        Ok(pg::from_date(days)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use ::diesel::pg::Pg;
+    use ::diesel::sql_types::{Date as SqlDate, Timestamptz};
+    use ::diesel::{debug_query, IntoSql};
+    use crate::{Date, DateTime};
+
+    #[test]
+    fn test_diesel_bind() {
+        let d = DateTime::from_str("2000-01-01T08:00:00+08:00").unwrap();
+        let q = debug_query::<Pg, _>(&d.into_sql::<Timestamptz>()).to_string();
+        assert!(q.contains("$1"));
+        assert!(q.contains("DateTime"));
+        let date = Date::from_str("2024-02-29").unwrap();
+        let q = debug_query::<Pg, _>(&date.into_sql::<SqlDate>()).to_string();
+        assert!(q.contains("year: 2024"));
+    }
+}
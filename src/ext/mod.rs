@@ -0,0 +1,64 @@
+//! conversions to and from the types of other crates, each behind a feature of the same name
+
+#[cfg(feature = "chrono")]
+mod chrono;
+
+#[cfg(feature = "time03")]
+mod time03;
+
+#[cfg(any(feature = "sqlx-postgres", feature = "sqlx-mysql", feature = "sqlx-sqlite"))]
+mod sqlx;
+
+#[cfg(feature = "rusqlite")]
+mod rusqlite;
+
+#[cfg(any(feature = "postgres", feature = "diesel"))]
+mod pg;
+
+#[cfg(feature = "postgres")]
+mod postgres;
+
+#[cfg(feature = "diesel")]
+mod diesel;
+
+#[cfg(feature = "prost")]
+mod prost;
+
+#[cfg(feature = "wasm")]
+mod wasm;
+
+#[cfg(feature = "pyo3")]
+mod pyo3;
+
+#[cfg(feature = "rand")]
+pub(crate) mod rand;
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+
+#[cfg(feature = "proptest")]
+mod proptest;
+
+#[cfg(feature = "schemars")]
+mod schemars;
+
+#[cfg(feature = "utoipa")]
+mod utoipa;
+
+#[cfg(feature = "defmt")]
+mod defmt;
+
+#[cfg(feature = "async-graphql")]
+mod async_graphql;
+
+#[cfg(feature = "bson")]
+mod bson;
+
+#[cfg(feature = "bytes")]
+mod bytes;
+
+#[cfg(feature = "tokio")]
+mod tokio;
+
+#[cfg(feature = "rkyv")]
+mod rkyv;
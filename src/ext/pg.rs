@@ -0,0 +1,60 @@
+//! the binary representations of the postgres `timestamp`, `timestamptz` and `date` types,
+//! microseconds and days since 2000-01-01. `i64::MAX` / `i32::MAX` and the minimums are `infinity`
+use crate::{Date, DateTime};
+use crate::error::{Error, Field};
+
+/// 2000-01-01 in days since 1970-01-01
+const PG_EPOCH_DAYS: i64 = 10957;
+
+const PG_EPOCH_MICROS: i64 = PG_EPOCH_DAYS * 86_400_000_000;
+
+/// `timestamptz`, the instant
+pub(crate) fn to_timestamptz(dt: DateTime) -> i64 {
+    dt.unix_micros() - PG_EPOCH_MICROS
+}
+
+/// `timestamp`, the wall-clock fields
+pub(crate) fn to_timestamp(dt: DateTime) -> i64 {
+    dt.wall_micros() - PG_EPOCH_MICROS
+}
+
+/// a `timestamp` or `timestamptz`, at utc
+pub(crate) fn from_timestamp(micros: i64) -> Result<DateTime, Error> {
+    if micros == i64::MAX || micros == i64::MIN {
+        return Err(Error::InfiniteTimestamp);
+    }
+    DateTime::from_unix_micros(micros as i128 + PG_EPOCH_MICROS as i128)
+        .ok_or(Error::OutOfRange { field: Field::Timestamp })
+}
+
+pub(crate) fn to_date(d: Date) -> i32 {
+    (d.to_unix_day() - PG_EPOCH_DAYS) as i32
+}
+
+pub(crate) fn from_date(days: i32) -> Result<Date, Error> {
+    if days == i32::MAX || days == i32::MIN {
+        return Err(Error::InfiniteDate);
+    }
+    Date::from_unix_day(days as i64 + PG_EPOCH_DAYS).ok_or(Error::OutOfRange { field: Field::Date })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use crate::{Date, DateTime};
+    use super::*;
+
+    #[test]
+    fn test_pg_epoch() {
+        let d = DateTime::from_str("2000-01-01T08:00:00.000001+08:00").unwrap();
+        assert_eq!(to_timestamptz(d), 1);
+        assert_eq!(to_timestamp(d), 8 * 3_600_000_000 + 1);
+        assert_eq!(from_timestamp(1).unwrap().to_string(), "2000-01-01 00:00:00.000001");
+        assert_eq!(from_timestamp(-1).unwrap().to_string(), "1999-12-31 23:59:59.999999");
+        assert!(from_timestamp(i64::MAX).is_err());
+        let date = Date::from_str("1999-12-31").unwrap();
+        assert_eq!(to_date(date), -1);
+        assert_eq!(from_date(-1).unwrap(), date);
+        assert!(from_date(i32::MIN).is_err());
+    }
+}
@@ -0,0 +1,85 @@
+//! `postgres` / `tokio-postgres` support through postgres-types, in the binary format
+use std::error::Error as StdError;
+use bytes::BytesMut;
+use postgres_types::{accepts, to_sql_checked, FromSql, IsNull, ToSql, Type};
+use crate::{Date, DateTime};
+use crate::ext::pg;
+
+type BoxError = Box<dyn StdError + Sync + Send>;
+
+fn be_bytes<const N: usize>(raw: &[u8]) -> Result<[u8; N], BoxError> {
+    raw.try_into().map_err(|_| "invalid message length".into())
+}
+
+impl<'a> FromSql<'a> for DateTime {
+    /// `timestamptz` at utc, `timestamp` as the wall-clock fields at utc
+    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<Self, BoxError> {
+        Ok(pg::from_timestamp(i64::from_be_bytes(be_bytes(raw)?))?)
+    }
+
+    accepts!(TIMESTAMP, TIMESTAMPTZ);
+}
+
+impl ToSql for DateTime {
+    /// `timestamptz` as the instant, `timestamp` as the wall-clock fields
+    fn to_sql(&self, ty: &Type, w: &mut BytesMut) -> Result<IsNull, BoxError> {
+        let micros = if *ty == Type::TIMESTAMPTZ {
+            pg::to_timestamptz(*self)
+        } else {
+            pg::to_timestamp(*self)
+        };
+        w.extend_from_slice(&micros.to_be_bytes());
+        Ok(IsNull::No)
+    }
+
+    accepts!(TIMESTAMP, TIMESTAMPTZ);
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for Date {
+    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<Self, BoxError> {
+        Ok(pg::from_date(i32::from_be_bytes(be_bytes(raw)?))?)
+    }
+
+    accepts!(DATE);
+}
+
+impl ToSql for Date {
+    fn to_sql(&self, _: &Type, w: &mut BytesMut) -> Result<IsNull, BoxError> {
+        w.extend_from_slice(&pg::to_date(*self).to_be_bytes());
+        Ok(IsNull::No)
+    }
+
+    accepts!(DATE);
+    to_sql_checked!();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use bytes::BytesMut;
+    use postgres_types::{FromSql, ToSql, Type};
+    use crate::{Date, DateTime};
+
+    #[test]
+    fn test_postgres_types() {
+        let d = DateTime::from_str("2000-01-01T08:00:00.000001+08:00").unwrap();
+        let mut buf = BytesMut::new();
+        d.to_sql_checked(&Type::TIMESTAMPTZ, &mut buf).unwrap();
+        assert_eq!(&buf[..], &1i64.to_be_bytes());
+        let utc = DateTime::from_sql(&Type::TIMESTAMPTZ, &buf).unwrap();
+        assert_eq!(utc.to_rfc3339(), "2000-01-01T00:00:00.000001Z");
+        buf.clear();
+        d.to_sql_checked(&Type::TIMESTAMP, &mut buf).unwrap();
+        assert_eq!(DateTime::from_sql(&Type::TIMESTAMP, &buf).unwrap().to_string(), "2000-01-01 08:00:00.000001");
+        assert!(d.to_sql_checked(&Type::DATE, &mut buf).is_err());
+        assert!(DateTime::from_sql(&Type::TIMESTAMP, &i64::MAX.to_be_bytes()).is_err());
+        assert!(DateTime::from_sql(&Type::TIMESTAMP, &[0; 4]).is_err());
+
+        let date = Date::from_str("2024-02-29").unwrap();
+        buf.clear();
+        date.to_sql_checked(&Type::DATE, &mut buf).unwrap();
+        assert_eq!(&buf[..], &8825i32.to_be_bytes());
+        assert_eq!(Date::from_sql(&Type::DATE, &buf).unwrap(), date);
+    }
+}
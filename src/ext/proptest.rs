@@ -0,0 +1,64 @@
+use proptest::arbitrary::Arbitrary;
+use proptest::strategy::{BoxedStrategy, Strategy};
+use crate::{Date, DateTime, Time};
+
+/// offsets in whole minutes strictly within ±15:00
+const MAX_OFFSET_MINUTES: i32 = 15 * 60 - 1;
+
+impl Arbitrary for Date {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (Date::MIN.to_unix_day()..=Date::MAX.to_unix_day())
+            .prop_map(Date::from_days_since_epoch)
+            .boxed()
+    }
+}
+
+impl Arbitrary for Time {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (0..86_400_000_000i64)
+            .prop_map(|micros| Time::from(DateTime::from_wall_micros(micros, 0)))
+            .boxed()
+    }
+}
+
+impl Arbitrary for DateTime {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    /// wall-clock fields between `DateTime::MIN` and `DateTime::MAX` at any whole-minute offset
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (DateTime::MIN.wall_micros()..=DateTime::MAX.wall_micros(), -MAX_OFFSET_MINUTES..=MAX_OFFSET_MINUTES)
+            .prop_map(|(micros, minutes)| DateTime::from_wall_micros(micros, minutes * 60))
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use proptest::prelude::*;
+    use crate::{Date, DateTime, Time};
+
+    proptest! {
+        #[test]
+        fn test_proptest_valid(d in any::<DateTime>(), date in any::<Date>(), t in any::<Time>()) {
+            prop_assert_eq!(DateTime::from_str(&d.to_rfc3339()).unwrap(), d);
+            prop_assert_eq!(Date::from_str(&date.to_string()).unwrap(), date);
+            prop_assert_eq!(Time::from_str(&t.to_string()).unwrap(), t);
+        }
+
+        #[test]
+        fn test_proptest_canonical(d in any::<DateTime>(), offset in -DateTime::MAX_OFFSET..=DateTime::MAX_OFFSET) {
+            for v in [d, DateTime { offset, ..d }] {
+                let back = DateTime::from_str(&v.to_canonical_string()).unwrap();
+                prop_assert!(back.eq_fields(&v), "{} {:?}", v.to_canonical_string(), back);
+            }
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use prost_types::Timestamp;
+use crate::DateTime;
+use crate::error::{Error, Field};
+
+impl From<DateTime> for Timestamp {
+    /// the instant, with `nanos` always in `0...999999999`
+    fn from(v: DateTime) -> Self {
+        let (seconds, nanos) = v.unix_secs_nanos();
+        Timestamp { seconds, nanos: nanos as i32 }
+    }
+}
+
+impl TryFrom<Timestamp> for DateTime {
+    type Error = Error;
+
+    /// at utc, truncated to microseconds. fails if `nanos` is not in `0...999999999`
+    /// or the instant is outside of 0001-01-01...9999-12-31, the range protobuf allows
+    fn try_from(t: Timestamp) -> Result<Self, Self::Error> {
+        if !(0..1_000_000_000).contains(&t.nanos) {
+            return Err(Error::OutOfRange { field: Field::Nanos });
+        }
+        DateTime::from_unix_nanos(t.seconds as i128 * 1_000_000_000 + t.nanos as i128)
+            .ok_or(Error::OutOfRange { field: Field::Timestamp })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use prost_types::Timestamp;
+    use crate::DateTime;
+
+    #[test]
+    fn test_prost_timestamp() {
+        let d = DateTime::from_str("2023-08-20T13:02:03.123456+08:00").unwrap();
+        let t = Timestamp::from(d);
+        assert_eq!(t, Timestamp { seconds: 1692507723, nanos: 123456000 });
+        assert_eq!(DateTime::try_from(t).unwrap().to_rfc3339(), "2023-08-20T05:02:03.123456Z");
+        let before = Timestamp { seconds: -1, nanos: 500_000_000 };
+        assert_eq!(DateTime::try_from(before).unwrap().to_string(), "1969-12-31 23:59:59.500000");
+        assert_eq!(Timestamp::from(DateTime::try_from(before).unwrap()), before);
+        assert!(DateTime::try_from(Timestamp { seconds: 0, nanos: -1 }).is_err());
+        assert!(DateTime::try_from(Timestamp { seconds: 253402300800, nanos: 0 }).is_err());
+    }
+}
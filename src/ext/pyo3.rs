@@ -0,0 +1,111 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyDate, PyDateAccess, PyDateTime, PyDelta, PyDeltaAccess, PyTimeAccess, PyTzInfo, timezone_utc_bound};
+use crate::{Date, DateTime};
+
+/// `datetime.timezone.utc`, or a fixed `datetime.timezone` of `offset` seconds east
+fn py_timezone(py: Python<'_>, offset: i32) -> PyResult<Bound<'_, PyTzInfo>> {
+    if offset == 0 {
+        return Ok(timezone_utc_bound(py));
+    }
+    let delta = PyDelta::new_bound(py, 0, offset, 0, true)?;
+    let tz = py.import_bound("datetime")?.getattr("timezone")?.call1((delta,))?;
+    Ok(tz.downcast_into::<PyTzInfo>()?)
+}
+
+impl ToPyObject for DateTime {
+    /// an aware `datetime.datetime` with a fixed `datetime.timezone` for the offset
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        let tz = py_timezone(py, self.offset).expect("failed to construct datetime.timezone");
+        PyDateTime::new_bound(py, self.year as i32, self.mon, self.day, self.hour, self.min, self.sec, self.micro, Some(&tz))
+            .expect("failed to construct datetime.datetime")
+            .into()
+    }
+}
+
+impl IntoPy<PyObject> for DateTime {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+impl FromPyObject<'_> for DateTime {
+    /// the wall-clock fields and `utcoffset()` of a `datetime.datetime`, which may be
+    /// for any tzinfo. a naive datetime has offset 0, one past `DateTime::MAX_OFFSET` fails
+    fn extract_bound(ob: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let dt = ob.downcast::<PyDateTime>()?;
+        let offset = match dt.call_method0("utcoffset")? {
+            v if v.is_none() => 0,
+            v => {
+                let delta = v.downcast::<PyDelta>()?;
+                if delta.get_microseconds() != 0 {
+                    return Err(PyValueError::new_err("utcoffset with microseconds is not supported"));
+                }
+                let offset = delta.get_days() * 86400 + delta.get_seconds();
+                DateTime::check_offset(offset).map_err(|e| PyValueError::new_err(e.to_string()))?
+            }
+        };
+        Ok(DateTime {
+            micro: dt.get_microsecond(),
+            sec: dt.get_second(),
+            min: dt.get_minute(),
+            hour: dt.get_hour(),
+            day: dt.get_day(),
+            mon: dt.get_month(),
+            year: dt.get_year() as u16,
+            offset,
+        })
+    }
+}
+
+impl ToPyObject for Date {
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        PyDate::new_bound(py, self.year as i32, self.mon, self.day)
+            .expect("failed to construct datetime.date")
+            .into()
+    }
+}
+
+impl IntoPy<PyObject> for Date {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+impl FromPyObject<'_> for Date {
+    /// a `datetime.date`, or the date part of a `datetime.datetime`
+    fn extract_bound(ob: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let d = ob.downcast::<PyDate>()?;
+        Ok(Date { day: d.get_day(), mon: d.get_month(), year: d.get_year() as u16 })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use pyo3::prelude::*;
+    use crate::{Date, DateTime};
+
+    #[test]
+    fn test_pyo3() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let d = DateTime::from_str("2023-08-20T13:02:03.123456+08:00").unwrap();
+            let obj = d.into_py(py);
+            let iso: String = obj.call_method0(py, "isoformat").unwrap().extract(py).unwrap();
+            assert_eq!(iso, "2023-08-20T13:02:03.123456+08:00");
+            assert_eq!(obj.extract::<DateTime>(py).unwrap(), d);
+            let utc = DateTime::from_str("2023-08-20T05:02:03Z").unwrap();
+            let iso: String = utc.into_py(py).call_method0(py, "isoformat").unwrap().extract(py).unwrap();
+            assert_eq!(iso, "2023-08-20T05:02:03+00:00");
+            let naive = py.eval_bound("__import__('datetime').datetime(2023, 8, 20, 13, 2, 3)", None, None).unwrap();
+            assert_eq!(naive.extract::<DateTime>().unwrap().to_rfc3339(), "2023-08-20T13:02:03.000000Z");
+            let date = Date::from_str("2023-08-20").unwrap();
+            assert_eq!(date.into_py(py).extract::<Date>(py).unwrap(), date);
+            assert!(py.eval_bound("'2023'", None, None).unwrap().extract::<DateTime>().is_err());
+            let far = "__import__('datetime').datetime(2023, 8, 20, tzinfo=__import__('datetime').timezone(__import__('datetime').timedelta(hours=20)))";
+            let e = py.eval_bound(far, None, None).unwrap().extract::<DateTime>().unwrap_err();
+            assert!(e.to_string().contains("OutOfRangeOffset"), "{}", e);
+        });
+    }
+}
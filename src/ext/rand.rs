@@ -0,0 +1,107 @@
+use rand::Rng;
+use rand::distributions::uniform::{SampleBorrow, SampleUniform, UniformInt, UniformSampler};
+use crate::{Date, DateTime};
+
+/// samples a `DateTime` with microsecond resolution, in the offset of the lower bound
+#[derive(Clone, Copy, Debug)]
+pub struct UniformDateTime {
+    micros: UniformInt<i64>,
+    offset: i32,
+}
+
+impl UniformSampler for UniformDateTime {
+    type X = DateTime;
+
+    fn new<B1, B2>(low: B1, high: B2) -> Self
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        let (low, high) = (*low.borrow(), *high.borrow());
+        Self { micros: UniformInt::new(low.unix_micros(), high.unix_micros()), offset: low.offset }
+    }
+
+    fn new_inclusive<B1, B2>(low: B1, high: B2) -> Self
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        let (low, high) = (*low.borrow(), *high.borrow());
+        Self { micros: UniformInt::new_inclusive(low.unix_micros(), high.unix_micros()), offset: low.offset }
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::X {
+        DateTime::from_unix_micros(self.micros.sample(rng) as i128)
+            .expect("sample between two datetimes")
+            .to_offset_secs(self.offset)
+    }
+}
+
+impl SampleUniform for DateTime {
+    type Sampler = UniformDateTime;
+}
+
+/// samples a `Date` by its day since the epoch
+#[derive(Clone, Copy, Debug)]
+pub struct UniformDate {
+    days: UniformInt<i64>,
+}
+
+impl UniformSampler for UniformDate {
+    type X = Date;
+
+    fn new<B1, B2>(low: B1, high: B2) -> Self
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        Self { days: UniformInt::new(low.borrow().to_unix_day(), high.borrow().to_unix_day()) }
+    }
+
+    fn new_inclusive<B1, B2>(low: B1, high: B2) -> Self
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        Self { days: UniformInt::new_inclusive(low.borrow().to_unix_day(), high.borrow().to_unix_day()) }
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::X {
+        Date::from_unix_day(self.days.sample(rng)).expect("sample between two dates")
+    }
+}
+
+impl SampleUniform for Date {
+    type Sampler = UniformDate;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use rand::{Rng, SeedableRng};
+    use rand::distributions::Uniform;
+    use rand::rngs::StdRng;
+    use crate::{Date, DateTime};
+
+    #[test]
+    fn test_rand_range() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let start = DateTime::from_str("2023-08-20T13:00:00+08:00").unwrap();
+        let end = DateTime::from_str("2023-08-20T05:00:01Z").unwrap();
+        for _ in 0..1000 {
+            let d = rng.gen_range(start..end);
+            assert!(d >= start && d < end);
+            assert_eq!(d.offset, 8 * 3600);
+        }
+        let first = Date::from_str("2023-02-27").unwrap();
+        let last = Date::from_str("2023-03-01").unwrap();
+        let mut seen = [false; 3];
+        for _ in 0..100 {
+            let d = rng.gen_range(first..=last);
+            seen[first.diff_days(d).unsigned_abs() as usize] = true;
+        }
+        assert_eq!(seen, [true; 3]);
+        let d = rng.sample(Uniform::new(first, last));
+        assert!(d >= first && d < last);
+    }
+}
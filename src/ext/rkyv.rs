@@ -0,0 +1,63 @@
+use rkyv::bytecheck::Verify;
+use rkyv::rancor::{Fallible, Source};
+use crate::{ArchivedDate, ArchivedDateTime, ArchivedTime, Date, DateTime, Time};
+
+// the archives are checked as `Date::try_new`, `Time::try_new` and `with_offset` do, so
+// `rkyv::access` and `rkyv::from_bytes` reject the fields a `Date` could never hold
+
+unsafe impl<C: Fallible + ?Sized> Verify<C> for ArchivedDate
+where
+    C::Error: Source,
+{
+    fn verify(&self, _: &mut C) -> Result<(), C::Error> {
+        Date::try_new(self.year.to_native(), self.mon, self.day).map_err(Source::new)?;
+        Ok(())
+    }
+}
+
+unsafe impl<C: Fallible + ?Sized> Verify<C> for ArchivedTime
+where
+    C::Error: Source,
+{
+    fn verify(&self, _: &mut C) -> Result<(), C::Error> {
+        Time::try_new(self.hour, self.min, self.sec, self.micro.to_native()).map_err(Source::new)?;
+        Ok(())
+    }
+}
+
+unsafe impl<C: Fallible + ?Sized> Verify<C> for ArchivedDateTime
+where
+    C::Error: Source,
+{
+    fn verify(&self, _: &mut C) -> Result<(), C::Error> {
+        Date::try_new(self.year.to_native(), self.mon, self.day).map_err(Source::new)?;
+        Time::try_new(self.hour, self.min, self.sec, self.micro.to_native()).map_err(Source::new)?;
+        DateTime::check_offset(self.offset.to_native()).map_err(Source::new)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rkyv::rancor::Error;
+    use crate::{Date, DateTime, Time};
+
+    #[test]
+    fn test_rkyv_invalid() {
+        let bytes = rkyv::to_bytes::<Error>(&Date { day: 1, mon: 13, year: 2023 }).unwrap();
+        let e = rkyv::from_bytes::<Date, Error>(&bytes).unwrap_err();
+        assert!(e.to_string().contains("OutOfRangeMonth"), "{}", e);
+        let bytes = rkyv::to_bytes::<Error>(&Date { day: 1, mon: 1, year: 0 }).unwrap();
+        assert!(rkyv::from_bytes::<Date, Error>(&bytes).is_err());
+        let bytes = rkyv::to_bytes::<Error>(&Time { micro: 0, sec: 0, min: 0, hour: 24 }).unwrap();
+        assert!(rkyv::from_bytes::<Time, Error>(&bytes).is_err());
+        let d = DateTime::try_new(2023, 8, 20, 13, 2, 3, 123456).unwrap();
+        for bad in [DateTime { mon: 13, ..d }, DateTime { micro: 1_000_000, ..d }, DateTime { offset: i32::MAX, ..d }] {
+            let bytes = rkyv::to_bytes::<Error>(&bad).unwrap();
+            assert!(rkyv::from_bytes::<DateTime, Error>(&bytes).is_err(), "{:?}", bad);
+            assert!(rkyv::access::<crate::ArchivedDateTime, Error>(&bytes).is_err());
+        }
+        let bytes = rkyv::to_bytes::<Error>(&d).unwrap();
+        assert!(rkyv::from_bytes::<DateTime, Error>(&bytes).unwrap().eq_fields(&d));
+    }
+}
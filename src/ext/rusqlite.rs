@@ -0,0 +1,80 @@
+//! rusqlite support using the text representations understood by the SQLite date functions,
+//! "2006-01-02 15:04:05.999999+08:00", "2006-01-02" and "15:04:05.999999".
+//! integers are read as seconds since the epoch
+use std::str::FromStr;
+use ::rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+use crate::{Date, DateTime, Time};
+
+fn parse<T: FromStr>(value: ValueRef<'_>) -> FromSqlResult<T>
+where
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    T::from_str(value.as_str()?).map_err(|e| FromSqlError::Other(Box::new(e)))
+}
+
+impl ToSql for DateTime {
+    fn to_sql(&self) -> ::rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.format("%F %T%.6f%:z")))
+    }
+}
+
+impl FromSql for DateTime {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        match value {
+            ValueRef::Integer(secs) => {
+                DateTime::from_unix_micros(secs as i128 * 1_000_000).ok_or(FromSqlError::OutOfRange(secs))
+            }
+            _ => parse(value),
+        }
+    }
+}
+
+impl ToSql for Date {
+    fn to_sql(&self) -> ::rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.to_string()))
+    }
+}
+
+impl FromSql for Date {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        parse(value)
+    }
+}
+
+impl ToSql for Time {
+    fn to_sql(&self) -> ::rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.to_string()))
+    }
+}
+
+impl FromSql for Time {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        parse(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use ::rusqlite::Connection;
+    use crate::{Date, DateTime, Time};
+
+    #[test]
+    fn test_rusqlite() {
+        let conn = Connection::open_in_memory().unwrap();
+        let d = DateTime::from_str("2023-08-20T13:02:03.123456+08:00").unwrap();
+        let (text, back): (String, DateTime) = conn.query_row("SELECT ?1, ?1", [d], |r| Ok((r.get(0)?, r.get(1)?))).unwrap();
+        assert_eq!(text, "2023-08-20 13:02:03.123456+08:00");
+        assert_eq!(back, d);
+        // the SQLite date functions understand the stored text
+        let utc: String = conn.query_row("SELECT datetime(?1)", [d], |r| r.get(0)).unwrap();
+        assert_eq!(utc, "2023-08-20 05:02:03");
+        let from_int: DateTime = conn.query_row("SELECT unixepoch(?1)", [d], |r| r.get(0)).unwrap();
+        assert_eq!(from_int.to_rfc3339(), "2023-08-20T05:02:03.000000Z");
+        let date = Date::from(d);
+        assert_eq!(conn.query_row("SELECT ?1", [date], |r| r.get::<_, Date>(0)).unwrap(), date);
+        let time = Time::from(d);
+        assert_eq!(conn.query_row("SELECT ?1", [time], |r| r.get::<_, Time>(0)).unwrap(), time);
+        assert!(conn.query_row("SELECT 'not a date'", [], |r| r.get::<_, DateTime>(0)).is_err());
+    }
+}
@@ -0,0 +1,54 @@
+use schemars::JsonSchema;
+use schemars::r#gen::SchemaGenerator;
+use schemars::schema::{InstanceType, Schema, SchemaObject};
+use crate::{Date, DateTime, SignedDuration, Time};
+
+/// a string with the json schema `format` of its serde form
+fn string_schema(format: &str) -> Schema {
+    SchemaObject {
+        instance_type: Some(InstanceType::String.into()),
+        format: Some(format.to_string()),
+        ..Default::default()
+    }
+    .into()
+}
+
+macro_rules! impl_json_schema {
+    ($ty:ty, $name:literal, $format:literal) => {
+        impl JsonSchema for $ty {
+            fn is_referenceable() -> bool {
+                false
+            }
+
+            fn schema_name() -> String {
+                $name.to_string()
+            }
+
+            fn json_schema(_: &mut SchemaGenerator) -> Schema {
+                string_schema($format)
+            }
+        }
+    };
+}
+
+impl_json_schema!(DateTime, "DateTime", "date-time");
+impl_json_schema!(Date, "Date", "date");
+// there is no offset, so not `time`. schemars uses the same name for chrono::NaiveTime
+impl_json_schema!(Time, "Time", "partial-date-time");
+impl_json_schema!(SignedDuration, "SignedDuration", "duration");
+
+#[cfg(test)]
+mod tests {
+    use schemars::schema_for;
+    use crate::{Date, DateTime, SignedDuration, Time};
+
+    #[test]
+    fn test_json_schema() {
+        let s = serde_json::to_value(schema_for!(DateTime)).unwrap();
+        assert_eq!(s["type"], "string");
+        assert_eq!(s["format"], "date-time");
+        assert_eq!(serde_json::to_value(schema_for!(Date)).unwrap()["format"], "date");
+        assert_eq!(serde_json::to_value(schema_for!(Time)).unwrap()["format"], "partial-date-time");
+        assert_eq!(serde_json::to_value(schema_for!(SignedDuration)).unwrap()["format"], "duration");
+    }
+}
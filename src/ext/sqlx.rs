@@ -0,0 +1,108 @@
+//! sqlx support, delegating the wire formats to the sqlx impls of the time 0.3 types:
+//! `DateTime` maps to `OffsetDateTime` (TIMESTAMPTZ), and also decodes from columns
+//! without an offset (TIMESTAMP, DATETIME) at utc. `Date` and `Time` map to `time::Date` and `time::Time`
+use ::sqlx::decode::Decode;
+use ::sqlx::encode::{Encode, IsNull};
+use ::sqlx::error::BoxDynError;
+use ::sqlx::types::Type;
+use ::sqlx::{Database, ValueRef};
+use time03::{OffsetDateTime, PrimitiveDateTime};
+use crate::{Date, DateTime, Time};
+
+macro_rules! impl_sqlx {
+    ($db:ty) => {
+        impl Type<$db> for DateTime {
+            fn type_info() -> <$db as Database>::TypeInfo {
+                <OffsetDateTime as Type<$db>>::type_info()
+            }
+
+            fn compatible(ty: &<$db as Database>::TypeInfo) -> bool {
+                <OffsetDateTime as Type<$db>>::compatible(ty) || <PrimitiveDateTime as Type<$db>>::compatible(ty)
+            }
+        }
+
+        impl<'q> Encode<'q, $db> for DateTime {
+            fn encode_by_ref(&self, buf: &mut <$db as Database>::ArgumentBuffer<'q>) -> Result<IsNull, BoxDynError> {
+                Encode::<$db>::encode(OffsetDateTime::try_from(*self)?, buf)
+            }
+        }
+
+        impl<'r> Decode<'r, $db> for DateTime {
+            fn decode(value: <$db as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+                if <OffsetDateTime as Type<$db>>::compatible(&value.type_info()) {
+                    Ok(DateTime::try_from(<OffsetDateTime as Decode<$db>>::decode(value)?)?)
+                } else {
+                    Ok(DateTime::try_from(<PrimitiveDateTime as Decode<$db>>::decode(value)?)?)
+                }
+            }
+        }
+
+        impl Type<$db> for Date {
+            fn type_info() -> <$db as Database>::TypeInfo {
+                <time03::Date as Type<$db>>::type_info()
+            }
+
+            fn compatible(ty: &<$db as Database>::TypeInfo) -> bool {
+                <time03::Date as Type<$db>>::compatible(ty)
+            }
+        }
+
+        impl<'q> Encode<'q, $db> for Date {
+            fn encode_by_ref(&self, buf: &mut <$db as Database>::ArgumentBuffer<'q>) -> Result<IsNull, BoxDynError> {
+                Encode::<$db>::encode(time03::Date::try_from(*self)?, buf)
+            }
+        }
+
+        impl<'r> Decode<'r, $db> for Date {
+            fn decode(value: <$db as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+                Ok(Date::try_from(<time03::Date as Decode<$db>>::decode(value)?)?)
+            }
+        }
+
+        impl Type<$db> for Time {
+            fn type_info() -> <$db as Database>::TypeInfo {
+                <time03::Time as Type<$db>>::type_info()
+            }
+
+            fn compatible(ty: &<$db as Database>::TypeInfo) -> bool {
+                <time03::Time as Type<$db>>::compatible(ty)
+            }
+        }
+
+        impl<'q> Encode<'q, $db> for Time {
+            fn encode_by_ref(&self, buf: &mut <$db as Database>::ArgumentBuffer<'q>) -> Result<IsNull, BoxDynError> {
+                Encode::<$db>::encode(time03::Time::try_from(*self)?, buf)
+            }
+        }
+
+        impl<'r> Decode<'r, $db> for Time {
+            fn decode(value: <$db as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+                Ok(Time::from(<time03::Time as Decode<$db>>::decode(value)?))
+            }
+        }
+    };
+}
+
+#[cfg(feature = "sqlx-postgres")]
+impl_sqlx!(::sqlx::Postgres);
+
+#[cfg(feature = "sqlx-mysql")]
+impl_sqlx!(::sqlx::MySql);
+
+#[cfg(feature = "sqlx-sqlite")]
+impl_sqlx!(::sqlx::Sqlite);
+
+#[cfg(all(test, feature = "sqlx-postgres"))]
+mod tests {
+    use ::sqlx::types::Type;
+    use ::sqlx::Postgres;
+    use time03::{OffsetDateTime, PrimitiveDateTime};
+    use crate::{Date, DateTime};
+
+    #[test]
+    fn test_sqlx_postgres_types() {
+        assert_eq!(<DateTime as Type<Postgres>>::type_info(), <OffsetDateTime as Type<Postgres>>::type_info());
+        assert!(<DateTime as Type<Postgres>>::compatible(&<PrimitiveDateTime as Type<Postgres>>::type_info()));
+        assert!(!<Date as Type<Postgres>>::compatible(&<PrimitiveDateTime as Type<Postgres>>::type_info()));
+    }
+}
@@ -0,0 +1,134 @@
+use time03::{Month, OffsetDateTime, PrimitiveDateTime, UtcOffset};
+use crate::{Date, DateTime, Time};
+use crate::error::{Error, Field};
+
+impl TryFrom<Date> for time03::Date {
+    type Error = Error;
+
+    fn try_from(d: Date) -> Result<Self, Self::Error> {
+        let mon = Month::try_from(d.mon).map_err(|_| Error::OutOfRange { field: Field::Month })?;
+        time03::Date::from_calendar_date(d.year as i32, mon, d.day).map_err(|_| Error::InvalidDate)
+    }
+}
+
+impl TryFrom<time03::Date> for Date {
+    type Error = Error;
+
+    /// fails outside of `Date::MIN...Date::MAX`
+    fn try_from(d: time03::Date) -> Result<Self, Self::Error> {
+        if d.year() < Date::MIN.year as i32 || d.year() > Date::MAX.year as i32 {
+            return Err(Error::OutOfRange { field: Field::Year });
+        }
+        Ok(Date {
+            day: d.day(),
+            mon: u8::from(d.month()),
+            year: d.year() as u16,
+        })
+    }
+}
+
+impl TryFrom<Time> for time03::Time {
+    type Error = Error;
+
+    fn try_from(t: Time) -> Result<Self, Self::Error> {
+        time03::Time::from_hms_micro(t.hour, t.min, t.sec, t.micro).map_err(|_| Error::InvalidTime)
+    }
+}
+
+impl From<time03::Time> for Time {
+    /// truncated to microseconds
+    fn from(t: time03::Time) -> Self {
+        Time {
+            micro: t.microsecond(),
+            sec: t.second(),
+            min: t.minute(),
+            hour: t.hour(),
+        }
+    }
+}
+
+impl TryFrom<DateTime> for PrimitiveDateTime {
+    type Error = Error;
+
+    /// the wall-clock fields, dropping the offset
+    fn try_from(v: DateTime) -> Result<Self, Self::Error> {
+        Ok(PrimitiveDateTime::new(
+            time03::Date::try_from(Date::from(v))?,
+            time03::Time::try_from(Time::from(v))?,
+        ))
+    }
+}
+
+impl TryFrom<PrimitiveDateTime> for DateTime {
+    type Error = Error;
+
+    /// the wall-clock fields at utc, truncated to microseconds
+    fn try_from(v: PrimitiveDateTime) -> Result<Self, Self::Error> {
+        let date = Date::try_from(v.date())?;
+        let time = Time::from(v.time());
+        Ok(DateTime {
+            micro: time.micro,
+            sec: time.sec,
+            min: time.min,
+            hour: time.hour,
+            day: date.day,
+            mon: date.mon,
+            year: date.year,
+            offset: 0,
+        })
+    }
+}
+
+impl TryFrom<DateTime> for OffsetDateTime {
+    type Error = Error;
+
+    fn try_from(v: DateTime) -> Result<Self, Self::Error> {
+        let offset = UtcOffset::from_whole_seconds(v.offset).map_err(|_| Error::InvalidOffset)?;
+        Ok(PrimitiveDateTime::try_from(v)?.assume_offset(offset))
+    }
+}
+
+impl TryFrom<OffsetDateTime> for DateTime {
+    type Error = Error;
+
+    /// the local fields and offset of `v`, fails with `OutOfRangeOffset` past `DateTime::MAX_OFFSET`
+    fn try_from(v: OffsetDateTime) -> Result<Self, Self::Error> {
+        Ok(DateTime {
+            offset: DateTime::check_offset(v.offset().whole_seconds())?,
+            ..DateTime::try_from(PrimitiveDateTime::new(v.date(), v.time()))?
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use time03::{Month, OffsetDateTime, PrimitiveDateTime, UtcOffset};
+    use crate::{Date, DateTime, Time};
+    use crate::error::{Error, Field};
+
+    #[test]
+    fn test_time03_date() {
+        let d = Date::from_str("2024-02-29").unwrap();
+        let t = time03::Date::try_from(d).unwrap();
+        assert_eq!(t, time03::Date::from_calendar_date(2024, Month::February, 29).unwrap());
+        assert_eq!(Date::try_from(t).unwrap(), d);
+        assert!(time03::Date::try_from(Date { day: 30, mon: 2, year: 2024 }).is_err());
+        let time = Time::from_str("11:12:13.123456").unwrap();
+        assert_eq!(Time::from(time03::Time::try_from(time).unwrap()), time);
+    }
+
+    #[test]
+    fn test_time03_datetime() {
+        let d = DateTime::from_str("2023-08-20T13:02:03.123456+08:00").unwrap();
+        let p = PrimitiveDateTime::try_from(d).unwrap();
+        assert_eq!(p.hour(), 13);
+        assert_eq!(DateTime::try_from(p).unwrap().to_rfc3339(), "2023-08-20T13:02:03.123456Z");
+        let o = OffsetDateTime::try_from(d).unwrap();
+        assert_eq!(o.offset().whole_seconds(), 8 * 3600);
+        assert_eq!(o.unix_timestamp(), 1692507723);
+        assert_eq!(DateTime::try_from(o).unwrap(), d);
+        let far = o.to_offset(UtcOffset::from_hms(25, 0, 0).unwrap());
+        assert_eq!(DateTime::try_from(far).unwrap_err(), Error::OutOfRange { field: Field::Offset });
+    }
+}
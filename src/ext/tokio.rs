@@ -0,0 +1,83 @@
+use core::time::Duration;
+use tokio::time::{Instant, Sleep};
+use crate::{DateTime, Every};
+
+impl DateTime {
+    /// the tokio instant of this datetime, counted from the present of `DateTime::now_utc`.
+    /// an instant already past is the present, so a timer set to it wakes at once
+    pub fn to_tokio_instant(&self) -> Instant {
+        let until = *self - DateTime::now_utc();
+        match until.is_negative() {
+            true => Instant::now(),
+            false => Instant::now() + until.unsigned_abs(),
+        }
+    }
+
+    /// a tokio timer that fires at this datetime, at once if it is past.
+    /// the deadline is fixed when called, a later change of the system clock does not move it
+    pub fn sleep_until(&self) -> Sleep {
+        tokio::time::sleep_until(self.to_tokio_instant())
+    }
+}
+
+impl Every {
+    /// wait for the next instant of the iterator on the tokio timer and return it, like
+    /// `tokio::time::Interval::tick`. instants already past are returned at once, one after
+    /// the other, `None` once the iterator ends
+    pub async fn tick(&mut self) -> Option<DateTime> {
+        let next = self.next()?;
+        next.sleep_until().await;
+        Some(next)
+    }
+
+    /// the time from now until the next instant, zero if it is past, without consuming it
+    pub fn until_next(&self) -> Option<Duration> {
+        let next = { *self }.next()?;
+        Some(next.to_tokio_instant().saturating_duration_since(Instant::now()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use tokio::time::Instant;
+    use crate::{DateTime, MockClock};
+
+    fn paused<F: core::future::Future>(f: F) -> F::Output {
+        let rt = tokio::runtime::Builder::new_current_thread().enable_time().start_paused(true).build().unwrap();
+        rt.block_on(f)
+    }
+
+    #[test]
+    fn test_tokio_sleep_until() {
+        let now = DateTime::UNIX_EPOCH;
+        let clock = MockClock::new(now);
+        let _guard = clock.install();
+        paused(async {
+            let start = Instant::now();
+            (now + Duration::from_secs(10)).sleep_until().await;
+            assert_eq!(start.elapsed(), Duration::from_secs(10));
+            let start = Instant::now();
+            (now - Duration::from_secs(10)).sleep_until().await;
+            assert_eq!(start.elapsed(), Duration::ZERO);
+            assert_eq!(now.to_tokio_instant(), Instant::now());
+        });
+    }
+
+    #[test]
+    fn test_tokio_every_tick() {
+        let now = DateTime::UNIX_EPOCH;
+        let clock = MockClock::new(now);
+        let _guard = clock.install();
+        paused(async {
+            let start = Instant::now();
+            let mut every = (now - Duration::from_secs(1)).every(Duration::from_secs(2));
+            assert_eq!(every.until_next(), Some(Duration::ZERO));
+            assert_eq!(every.tick().await, Some(now - Duration::from_secs(1)));
+            assert_eq!(start.elapsed(), Duration::ZERO);
+            assert_eq!(every.until_next(), Some(Duration::from_secs(1)));
+            assert_eq!(every.tick().await, Some(now + Duration::from_secs(1)));
+            assert_eq!(start.elapsed(), Duration::from_secs(1));
+        });
+    }
+}
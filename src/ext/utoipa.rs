@@ -0,0 +1,39 @@
+use utoipa::openapi::schema::{KnownFormat, ObjectBuilder, Schema, SchemaFormat, Type};
+use utoipa::openapi::RefOr;
+use utoipa::{PartialSchema, ToSchema};
+use crate::{Date, DateTime, SignedDuration, Time};
+
+macro_rules! impl_to_schema {
+    ($ty:ty, $format:expr) => {
+        impl PartialSchema for $ty {
+            fn schema() -> RefOr<Schema> {
+                ObjectBuilder::new().schema_type(Type::String).format(Some($format)).into()
+            }
+        }
+
+        impl ToSchema for $ty {}
+    };
+}
+
+impl_to_schema!(DateTime, SchemaFormat::KnownFormat(KnownFormat::DateTime));
+impl_to_schema!(Date, SchemaFormat::KnownFormat(KnownFormat::Date));
+// there is no offset, so not `time`. the same name as the schemars feature
+impl_to_schema!(Time, SchemaFormat::Custom("partial-date-time".to_string()));
+impl_to_schema!(SignedDuration, SchemaFormat::KnownFormat(KnownFormat::Duration));
+
+#[cfg(test)]
+mod tests {
+    use utoipa::{PartialSchema, ToSchema};
+    use crate::{Date, DateTime, SignedDuration, Time};
+
+    #[test]
+    fn test_to_schema() {
+        let s = serde_json::to_value(DateTime::schema()).unwrap();
+        assert_eq!(s["type"], "string");
+        assert_eq!(s["format"], "date-time");
+        assert_eq!(serde_json::to_value(Date::schema()).unwrap()["format"], "date");
+        assert_eq!(serde_json::to_value(Time::schema()).unwrap()["format"], "partial-date-time");
+        assert_eq!(serde_json::to_value(SignedDuration::schema()).unwrap()["format"], "duration");
+        assert_eq!(DateTime::name(), "DateTime");
+    }
+}
@@ -0,0 +1,25 @@
+use crate::DateTime;
+use crate::error::{Error, Field};
+
+impl From<DateTime> for js_sys::Date {
+    /// the instant, truncated to the millisecond precision of javascript. `to_timestamp`
+    /// with `TimestampUnit::Millis` and a `PrecisionLoss` to round or reject instead
+    fn from(v: DateTime) -> Self {
+        let millis = v.unix_micros().div_euclid(1000);
+        js_sys::Date::new(&(millis as f64).into())
+    }
+}
+
+impl TryFrom<js_sys::Date> for DateTime {
+    type Error = Error;
+
+    /// at utc. fails for an invalid date, whose time is NaN, or one outside of the years 0001...9999
+    fn try_from(v: js_sys::Date) -> Result<Self, Self::Error> {
+        let millis = v.get_time();
+        if !millis.is_finite() {
+            return Err(Error::InvalidDate);
+        }
+        DateTime::from_unix_micros(millis as i128 * 1000)
+            .ok_or(Error::OutOfRange { field: Field::Timestamp })
+    }
+}
@@ -0,0 +1,583 @@
+use core::fmt::Write;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use crate::{days_in_month, Date, DateTime};
+use crate::error::{Error, Field};
+use crate::month::MONTHS;
+use crate::weekday::WEEKDAYS;
+
+/// `2023-08-20`, for `DateTime::format` and `parse_from_str`
+pub const FORMAT_DATE: &str = "%Y-%m-%d";
+/// `13:02:03`
+pub const FORMAT_TIME: &str = "%H:%M:%S";
+/// `2023-08-20 13:02:03`
+pub const FORMAT_DATETIME: &str = "%Y-%m-%d %H:%M:%S";
+/// `2023-08-20T13:02:03.123+08:00`
+pub const FORMAT_RFC3339_MILLIS: &str = "%Y-%m-%dT%H:%M:%S%.3f%:z";
+/// `2023-08-20T13:02:03.123456+08:00`
+pub const FORMAT_RFC3339_MICROS: &str = "%Y-%m-%dT%H:%M:%S%.6f%:z";
+/// `20230820130203`, for file names and ids
+pub const FORMAT_COMPACT: &str = "%Y%m%d%H%M%S";
+
+/// the exif DateTime, DateTimeOriginal and DateTimeDigitized tags
+const EXIF_PATTERN: &str = "%Y:%m:%d %H:%M:%S";
+
+/// one piece of a compiled strftime-style pattern
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) enum Item<'a> {
+    Literal(&'a str),
+    /// `%Y` 4 digit year
+    Year,
+    /// `%y` 2 digit year, 69...99 is 19xx and 00...68 is 20xx
+    YearShort,
+    /// `%m` 01...12
+    Month,
+    /// `%d` 01...31
+    Day,
+    /// `%j` 001...366
+    DayOfYear,
+    /// `%H` 00...23
+    Hour,
+    /// `%I` 01...12
+    Hour12,
+    /// `%p` AM or PM
+    AmPm,
+    /// `%M` 00...59
+    Minute,
+    /// `%S` 00...59
+    Second,
+    /// `%f` 6 digit microseconds
+    Micro,
+    /// `%.3f` a dot and 3 digit milliseconds
+    FracMillis,
+    /// `%.6f` a dot and 6 digit microseconds
+    FracMicros,
+    /// `%z` +hhmm
+    Offset,
+    /// `%:z` +hh:mm
+    OffsetColon,
+    /// `%a` Mon
+    WeekdayShort,
+    /// `%A` Monday
+    WeekdayLong,
+    /// `%b` Jan
+    MonthShort,
+    /// `%B` January
+    MonthLong,
+    /// `%s` seconds since the epoch
+    Timestamp,
+    /// an unsupported specifier, written as is by `format` and rejected by `parse`
+    Unknown(&'a str),
+}
+
+const DATE_ITEMS: [Item<'static>; 5] = [Item::Year, Item::Literal("-"), Item::Month, Item::Literal("-"), Item::Day];
+
+const TIME_ITEMS: [Item<'static>; 5] = [Item::Hour, Item::Literal(":"), Item::Minute, Item::Literal(":"), Item::Second];
+
+/// split a pattern into literals and specifiers
+pub(crate) fn compile(fmt: &str) -> Vec<Item<'_>> {
+    let mut items = Vec::new();
+    let mut rest = fmt;
+    while !rest.is_empty() {
+        match rest.find('%') {
+            None => {
+                items.push(Item::Literal(rest));
+                break;
+            }
+            Some(0) => {}
+            Some(i) => {
+                items.push(Item::Literal(&rest[..i]));
+                rest = &rest[i..];
+            }
+        }
+        let spec = &rest[1..];
+        let len = if spec.starts_with(".3f") {
+            items.push(Item::FracMillis);
+            3
+        } else if spec.starts_with(".6f") {
+            items.push(Item::FracMicros);
+            3
+        } else if spec.starts_with(":z") {
+            items.push(Item::OffsetColon);
+            2
+        } else {
+            let c = match spec.chars().next() {
+                Some(c) => c,
+                None => {
+                    items.push(Item::Unknown(rest));
+                    break;
+                }
+            };
+            match c {
+                'Y' => items.push(Item::Year),
+                'y' => items.push(Item::YearShort),
+                'm' => items.push(Item::Month),
+                'd' => items.push(Item::Day),
+                'j' => items.push(Item::DayOfYear),
+                'H' => items.push(Item::Hour),
+                'I' => items.push(Item::Hour12),
+                'p' => items.push(Item::AmPm),
+                'M' => items.push(Item::Minute),
+                'S' => items.push(Item::Second),
+                'f' => items.push(Item::Micro),
+                'z' => items.push(Item::Offset),
+                'a' => items.push(Item::WeekdayShort),
+                'A' => items.push(Item::WeekdayLong),
+                'b' => items.push(Item::MonthShort),
+                'B' => items.push(Item::MonthLong),
+                's' => items.push(Item::Timestamp),
+                'F' => items.extend_from_slice(&DATE_ITEMS),
+                'T' => items.extend_from_slice(&TIME_ITEMS),
+                '%' => items.push(Item::Literal("%")),
+                _ => items.push(Item::Unknown(&rest[..1 + c.len_utf8()])),
+            }
+            c.len_utf8()
+        };
+        rest = &rest[1 + len..];
+    }
+    items
+}
+
+/// How many patterns each thread keeps compiled for `format` and `parse_from_str`
+#[cfg(feature = "std")]
+const CACHE_LEN: usize = 8;
+
+/// a compiled pattern of the cache, the items borrow `pattern`
+#[cfg(feature = "std")]
+struct Compiled {
+    pattern: Box<str>,
+    items: Vec<Item<'static>>,
+}
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    /// the recently used patterns of this thread, the last used first
+    static CACHE: core::cell::RefCell<Vec<Compiled>> = const { core::cell::RefCell::new(Vec::new()) };
+}
+
+/// run `f` with the compiled `fmt`, taken from a small per-thread lru cache so patterns
+/// passed again and again, e.g. from a config, are not split on every call
+pub(crate) fn with_compiled<R>(fmt: &str, f: impl FnOnce(&[Item]) -> R) -> R {
+    let mut f = Some(f);
+    #[cfg(feature = "std")]
+    {
+        let done = CACHE.try_with(|cache| {
+            // taken while a call below formats, which then compiles its pattern itself
+            let mut cache = cache.try_borrow_mut().ok()?;
+            match cache.iter().position(|c| &*c.pattern == fmt) {
+                Some(i) => cache[..=i].rotate_right(1),
+                None => {
+                    let pattern: Box<str> = fmt.into();
+                    // SAFETY: the items borrow the heap allocation of `pattern`, which does not
+                    // move with the entry and is dropped with it. they are only lent out while
+                    // the cache is borrowed, to a call that can not keep them
+                    let items = compile(unsafe { &*(&*pattern as *const str) });
+                    cache.truncate(CACHE_LEN - 1);
+                    cache.insert(0, Compiled { pattern, items });
+                }
+            }
+            Some(f.take()?(&cache[0].items))
+        });
+        if let Ok(Some(r)) = done {
+            return r;
+        }
+    }
+    f.take().expect("called once")(&compile(fmt))
+}
+
+pub(crate) fn format_items(items: &[Item], dt: &DateTime) -> String {
+    let mut s = String::with_capacity(32);
+    for item in items {
+        match *item {
+            Item::Literal(l) | Item::Unknown(l) => s.push_str(l),
+            Item::Year => write!(s, "{:04}", dt.year).unwrap(),
+            Item::YearShort => write!(s, "{:02}", dt.year % 100).unwrap(),
+            Item::Month => write!(s, "{:02}", dt.mon).unwrap(),
+            Item::Day => write!(s, "{:02}", dt.day).unwrap(),
+            Item::DayOfYear => write!(s, "{:03}", dt.day_of_year()).unwrap(),
+            Item::Hour => write!(s, "{:02}", dt.hour).unwrap(),
+            Item::Hour12 => write!(s, "{:02}", (dt.hour + 11) % 12 + 1).unwrap(),
+            Item::AmPm => s.push_str(if dt.hour < 12 { "AM" } else { "PM" }),
+            Item::Minute => write!(s, "{:02}", dt.min).unwrap(),
+            Item::Second => write!(s, "{:02}", dt.sec).unwrap(),
+            Item::Micro => write!(s, "{:06}", dt.micro).unwrap(),
+            Item::FracMillis => write!(s, ".{:03}", dt.micro / 1000).unwrap(),
+            Item::FracMicros => write!(s, ".{:06}", dt.micro).unwrap(),
+            Item::Offset | Item::OffsetColon => {
+                let sign = if dt.offset < 0 { '-' } else { '+' };
+                let minutes = dt.offset.unsigned_abs() / 60;
+                let sep = if *item == Item::OffsetColon { ":" } else { "" };
+                write!(s, "{}{:02}{}{:02}", sign, minutes / 60, sep, minutes % 60).unwrap()
+            }
+            Item::WeekdayShort => s.push_str(&WEEKDAYS[dt.weekday().num_days_from_monday() as usize][..3]),
+            Item::WeekdayLong => s.push_str(WEEKDAYS[dt.weekday().num_days_from_monday() as usize]),
+            Item::MonthShort => s.push_str(&MONTHS[dt.mon as usize - 1][..3]),
+            Item::MonthLong => s.push_str(MONTHS[dt.mon as usize - 1]),
+            Item::Timestamp => {
+                let secs = dt.unix_micros().div_euclid(1_000_000);
+                write!(s, "{}", secs).unwrap()
+            }
+        }
+    }
+    s
+}
+
+/// the fields read so far, missing ones default to 1970-01-01 00:00:00 at utc
+#[derive(Default)]
+struct Parsed {
+    year: Option<u16>,
+    year_short: Option<u16>,
+    mon: Option<u8>,
+    day: Option<u8>,
+    day_of_year: Option<u16>,
+    hour: Option<u8>,
+    hour12: Option<u8>,
+    pm: Option<bool>,
+    min: u8,
+    sec: u8,
+    micro: u32,
+    offset: i32,
+    weekday: Option<usize>,
+    timestamp: Option<i64>,
+}
+
+pub(crate) fn parse_items(items: &[Item], s: &str) -> Result<DateTime, Error> {
+    let bytes = s.as_bytes();
+    let mut pos = 0;
+    let mut p = Parsed::default();
+    for item in items {
+        match *item {
+            Item::Literal(l) => {
+                if !bytes[pos..].starts_with(l.as_bytes()) {
+                    return Err(Error::InvalidChar { field: Field::Literal, pos });
+                }
+                pos += l.len();
+            }
+            Item::Unknown(_) => return Err(Error::InvalidFormatSpecifier),
+            Item::Year => p.year = Some(digits(bytes, &mut pos, 4, Field::Year)? as u16),
+            Item::YearShort => p.year_short = Some(digits(bytes, &mut pos, 2, Field::Year)? as u16),
+            Item::Month => p.mon = Some(digits(bytes, &mut pos, 2, Field::Month)? as u8),
+            Item::Day => p.day = Some(digits(bytes, &mut pos, 2, Field::Day)? as u8),
+            Item::DayOfYear => p.day_of_year = Some(digits(bytes, &mut pos, 3, Field::Day)? as u16),
+            Item::Hour => p.hour = Some(digits(bytes, &mut pos, 2, Field::Hour)? as u8),
+            Item::Hour12 => p.hour12 = Some(digits(bytes, &mut pos, 2, Field::Hour)? as u8),
+            Item::AmPm => p.pm = Some(name(bytes, &mut pos, &["AM", "PM"], Field::AmPm)? == 1),
+            Item::Minute => p.min = digits(bytes, &mut pos, 2, Field::Minute)? as u8,
+            Item::Second => p.sec = digits(bytes, &mut pos, 2, Field::Second)? as u8,
+            Item::Micro => p.micro = digits(bytes, &mut pos, 6, Field::Micro)?,
+            Item::FracMillis | Item::FracMicros => {
+                if bytes.get(pos) != Some(&b'.') {
+                    return Err(Error::InvalidChar { field: Field::Micro, pos });
+                }
+                pos += 1;
+                p.micro = if *item == Item::FracMillis {
+                    digits(bytes, &mut pos, 3, Field::Micro)? * 1000
+                } else {
+                    digits(bytes, &mut pos, 6, Field::Micro)?
+                };
+            }
+            Item::Offset | Item::OffsetColon => {
+                let sign = match bytes.get(pos) {
+                    Some(b'Z') | Some(b'z') => {
+                        pos += 1;
+                        p.offset = 0;
+                        continue;
+                    }
+                    Some(b'+') => 1,
+                    Some(b'-') => -1,
+                    _ => return Err(Error::InvalidChar { field: Field::Offset, pos }),
+                };
+                pos += 1;
+                let h = digits(bytes, &mut pos, 2, Field::Offset)? as i32;
+                if *item == Item::OffsetColon {
+                    if bytes.get(pos) != Some(&b':') {
+                        return Err(Error::InvalidChar { field: Field::Offset, pos });
+                    }
+                    pos += 1;
+                }
+                let m = digits(bytes, &mut pos, 2, Field::Offset)? as i32;
+                p.offset = crate::datetime::offset_from_hm(sign, h, m)?;
+            }
+            Item::WeekdayShort | Item::WeekdayLong => {
+                let long = *item == Item::WeekdayLong;
+                p.weekday = Some(names(bytes, &mut pos, &WEEKDAYS, long, Field::Weekday)?);
+            }
+            Item::MonthShort | Item::MonthLong => {
+                let long = *item == Item::MonthLong;
+                p.mon = Some(names(bytes, &mut pos, &MONTHS, long, Field::Month)? as u8 + 1);
+            }
+            Item::Timestamp => {
+                let start = pos;
+                if bytes.get(pos) == Some(&b'-') {
+                    pos += 1;
+                }
+                while bytes.get(pos).is_some_and(u8::is_ascii_digit) {
+                    pos += 1;
+                }
+                let timestamp = s[start..pos].parse();
+                p.timestamp = Some(timestamp.map_err(|_| Error::InvalidChar { field: Field::Timestamp, pos: start })?);
+            }
+        }
+    }
+    if pos != bytes.len() {
+        return Err(Error::TrailingInput);
+    }
+    p.resolve()
+}
+
+impl Parsed {
+    fn resolve(self) -> Result<DateTime, Error> {
+        if let Some(secs) = self.timestamp {
+            let utc = DateTime::from_unix_micros(secs as i128 * 1_000_000)
+                .ok_or(Error::OutOfRange { field: Field::Timestamp })?;
+            return Ok(utc.to_offset_secs(self.offset));
+        }
+        let year = self
+            .year
+            .or(self.year_short.map(|y| if y >= 69 { 1900 + y } else { 2000 + y }))
+            .unwrap_or(1970);
+        if year < 1 {
+            return Err(Error::OutOfRange { field: Field::Year });
+        }
+        let date = match self.day_of_year {
+            Some(doy) => {
+                if doy == 0 || doy > crate::days_in_year(year) {
+                    return Err(Error::OutOfRange { field: Field::Day });
+                }
+                let jan1 = Date { day: 1, mon: 1, year };
+                Date::from_days_since_epoch(jan1.to_unix_day() + doy as i64 - 1)
+            }
+            None => {
+                let mon = self.mon.unwrap_or(1);
+                let day = self.day.unwrap_or(1);
+                if !(1..=12).contains(&mon) {
+                    return Err(Error::OutOfRange { field: Field::Month });
+                }
+                if day == 0 || day > days_in_month(year, mon) {
+                    return Err(Error::OutOfRange { field: Field::Day });
+                }
+                Date { day, mon, year }
+            }
+        };
+        if let Some(weekday) = self.weekday {
+            if date.weekday().num_days_from_monday() as usize != weekday {
+                return Err(Error::InconsistentWeekday);
+            }
+        }
+        let hour = match (self.hour, self.hour12) {
+            (Some(hour), _) => hour,
+            (None, Some(hour12)) => {
+                if hour12 == 0 || hour12 > 12 {
+                    return Err(Error::OutOfRange { field: Field::Hour });
+                }
+                hour12 % 12 + if self.pm == Some(true) { 12 } else { 0 }
+            }
+            (None, None) => 0,
+        };
+        if hour > 23 {
+            return Err(Error::OutOfRange { field: Field::Hour });
+        }
+        if self.min > 59 {
+            return Err(Error::OutOfRange { field: Field::Minute });
+        }
+        if self.sec > 59 {
+            return Err(Error::OutOfRange { field: Field::Second });
+        }
+        Ok(DateTime {
+            micro: self.micro,
+            sec: self.sec,
+            min: self.min,
+            hour,
+            day: date.day,
+            mon: date.mon,
+            year: date.year,
+            offset: self.offset,
+        })
+    }
+}
+
+/// read exactly `n` decimal digits
+fn digits(bytes: &[u8], pos: &mut usize, n: usize, field: Field) -> Result<u32, Error> {
+    let mut v = 0;
+    for i in 0..n {
+        match bytes.get(*pos + i) {
+            Some(c) if c.is_ascii_digit() => v = v * 10 + (c - b'0') as u32,
+            _ => return Err(Error::InvalidChar { field, pos: *pos + i }),
+        }
+    }
+    *pos += n;
+    Ok(v)
+}
+
+/// match one of `options` ignoring case, returning its index
+fn name(bytes: &[u8], pos: &mut usize, options: &[&str], field: Field) -> Result<usize, Error> {
+    for (i, option) in options.iter().enumerate() {
+        let end = *pos + option.len();
+        if end <= bytes.len() && bytes[*pos..end].eq_ignore_ascii_case(option.as_bytes()) {
+            *pos = end;
+            return Ok(i);
+        }
+    }
+    Err(Error::InvalidChar { field, pos: *pos })
+}
+
+/// match a full name or its 3 letter abbreviation
+fn names(bytes: &[u8], pos: &mut usize, full: &[&str], long: bool, field: Field) -> Result<usize, Error> {
+    let options: Vec<&str> = full.iter().map(|n| if long { *n } else { &n[..3] }).collect();
+    name(bytes, pos, &options, field)
+}
+
+impl DateTime {
+    /// format with a strftime-style pattern, e.g. `"%Y-%m-%dT%H:%M:%S%.3f%:z"`.
+    ///
+    /// supported: `%Y %y %m %d %j %H %I %p %M %S %f %.3f %.6f %z %:z %a %A %b %B %s %F %T %%`.
+    /// unsupported specifiers are written as is
+    pub fn format(&self, fmt: &str) -> String {
+        with_compiled(fmt, |items| format_items(items, self))
+    }
+
+    /// parse with a strftime-style pattern, see `format`. the whole input must match,
+    /// missing fields default to 1970-01-01 00:00:00 at utc, and `%s` takes precedence
+    /// over the calendar fields
+    pub fn parse_from_str(s: &str, fmt: &str) -> Result<Self, Error> {
+        with_compiled(fmt, |items| parse_items(items, s))
+    }
+
+    /// format the wall-clock fields for `touch -t`, the full form "202308201302.03"
+    pub fn to_touch(&self) -> String {
+        self.format("%Y%m%d%H%M.%S")
+    }
+
+    /// parse the `[[CC]YY]MMDDhhmm[.ss]` argument of `touch -t` at offset 0. without a century
+    /// 69...99 are 19xx and 00...68 are 20xx, without a year it is the current local year,
+    /// which needs the `std` feature
+    pub fn parse_touch(s: &str) -> Result<Self, Error> {
+        let (digits, secs) = match s.split_once('.') {
+            Some((digits, secs)) => (digits, Some(secs)),
+            None => (s, None),
+        };
+        let (digits, date_fmt) = match digits.len() {
+            12 => (digits.to_string(), "%Y%m%d%H%M"),
+            10 => (digits.to_string(), "%y%m%d%H%M"),
+            #[cfg(feature = "std")]
+            8 => (format!("{:04}{}", DateTime::now().year, digits), "%Y%m%d%H%M"),
+            _ => return Err(Error::InvalidTouchLength),
+        };
+        match secs {
+            Some(secs) => DateTime::parse_from_str(&format!("{}.{}", digits, secs), &format!("{}.%S", date_fmt)),
+            None => DateTime::parse_from_str(&digits, date_fmt),
+        }
+    }
+
+    /// format the wall-clock fields as an exif DateTimeOriginal, "2023:08:20 13:02:03".
+    /// exif keeps the offset in a separate tag
+    pub fn to_exif(&self) -> String {
+        self.format(EXIF_PATTERN)
+    }
+
+    /// parse an exif DateTimeOriginal at offset 0. cameras write a blank or all-zero
+    /// value when the time is unknown, which is an `UnknownExifDate` error
+    pub fn parse_exif(s: &str) -> Result<Self, Error> {
+        let s = s.trim_end_matches('\0');
+        if s.bytes().all(|c| matches!(c, b' ' | b'0' | b':')) {
+            return Err(Error::UnknownExifDate);
+        }
+        DateTime::parse_from_str(s, EXIF_PATTERN)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use crate::DateTime;
+
+    #[test]
+    fn test_format() {
+        let d = DateTime::from_str("2023-08-20T13:02:03.123456+08:00").unwrap();
+        assert_eq!(d.format("%F %T%.6f"), "2023-08-20 13:02:03.123456");
+        assert_eq!(d.format("%Y-%m-%dT%H:%M:%S%.3f%:z"), "2023-08-20T13:02:03.123+08:00");
+        assert_eq!(d.format("%a %d %b %y %I:%M %p %z"), "Sun 20 Aug 23 01:02 PM +0800");
+        assert_eq!(d.format("%A %B %j %f %%"), "Sunday August 232 123456 %");
+        assert_eq!(d.format("%s"), "1692507723");
+        assert_eq!(d.format("%Q %"), "%Q %");
+    }
+
+    #[test]
+    fn test_format_presets() {
+        use crate::{FORMAT_COMPACT, FORMAT_DATE, FORMAT_DATETIME, FORMAT_RFC3339_MICROS, FORMAT_RFC3339_MILLIS, FORMAT_TIME};
+        let d = DateTime::from_str("2023-08-20T13:02:03.123456+08:00").unwrap();
+        assert_eq!(d.format(FORMAT_DATE), "2023-08-20");
+        assert_eq!(d.format(FORMAT_TIME), "13:02:03");
+        assert_eq!(d.format(FORMAT_DATETIME), "2023-08-20 13:02:03");
+        assert_eq!(d.format(FORMAT_RFC3339_MILLIS), "2023-08-20T13:02:03.123+08:00");
+        assert_eq!(d.format(FORMAT_RFC3339_MICROS), "2023-08-20T13:02:03.123456+08:00");
+        assert_eq!(d.format(FORMAT_COMPACT), "20230820130203");
+        assert!(DateTime::parse_from_str("2023-08-20T13:02:03.123456+08:00", FORMAT_RFC3339_MICROS).unwrap().eq_fields(&d));
+        let millis = DateTime::parse_from_str("2023-08-20T13:02:03.123+08:00", FORMAT_RFC3339_MILLIS).unwrap();
+        assert_eq!(millis.micro, 123000);
+        assert_eq!(DateTime::parse_from_str("20230820130203", FORMAT_COMPACT).unwrap().to_string(), "2023-08-20 13:02:03.000000");
+        assert_eq!(DateTime::parse_from_str("2023-08-20", FORMAT_DATE).unwrap().to_string(), "2023-08-20 00:00:00.000000");
+        assert_eq!(DateTime::parse_from_str("2023-08-20 13:02:03", FORMAT_DATETIME).unwrap().hour, 13);
+    }
+
+    #[test]
+    fn test_format_cache() {
+        let d = DateTime::from_str("2023-08-20T13:02:03.123456+08:00").unwrap();
+        for _ in 0..2 {
+            for i in 0..10 {
+                let fmt = format!("%Y {} %H", i);
+                assert_eq!(d.format(&fmt), format!("2023 {} 13", i));
+                assert_eq!(DateTime::parse_from_str(&format!("2023 {} 13", i), &fmt).unwrap().hour, 13);
+            }
+        }
+        assert_eq!(d.format("%F"), "2023-08-20");
+        assert_eq!(d.format("%F"), "2023-08-20");
+    }
+
+    #[test]
+    fn test_exif() {
+        let d = DateTime::parse_exif("2023:08:20 13:02:03").unwrap();
+        assert_eq!(d.to_string(), "2023-08-20 13:02:03.000000");
+        assert_eq!(d.to_exif(), "2023:08:20 13:02:03");
+        assert_eq!(DateTime::parse_exif("2023:08:20 13:02:03\0").unwrap(), d);
+        assert_eq!(DateTime::parse_exif("0000:00:00 00:00:00").unwrap_err().to_string(), "Rbatis Error: UnknownExifDate");
+        assert!(DateTime::parse_exif("    :  :     :  :  ").is_err());
+        assert!(DateTime::parse_exif("2023-08-20 13:02:03").is_err());
+    }
+
+    #[test]
+    fn test_touch() {
+        let d = DateTime::parse_touch("202308201302.03").unwrap();
+        assert_eq!(d.to_string(), "2023-08-20 13:02:03.000000");
+        assert_eq!(d.to_touch(), "202308201302.03");
+        assert_eq!(DateTime::parse_touch("2308201302").unwrap().to_string(), "2023-08-20 13:02:00.000000");
+        assert_eq!(DateTime::parse_touch("6908201302.59").unwrap().year, 1969);
+        #[cfg(feature = "std")]
+        {
+            let short = DateTime::parse_touch("01311302").unwrap();
+            assert_eq!((short.year, short.mon, short.day), (DateTime::now().year, 1, 31));
+        }
+        assert!(DateTime::parse_touch("202308201302.3").is_err());
+        assert!(DateTime::parse_touch("2023082013").is_err());
+        assert!(DateTime::parse_touch("202313201302").is_err());
+    }
+
+    #[test]
+    fn test_parse_from_str() {
+        let d = DateTime::parse_from_str("20/08/2023 01:02:03 PM", "%d/%m/%Y %I:%M:%S %p").unwrap();
+        assert_eq!(d.to_string(), "2023-08-20 13:02:03.000000");
+        let d = DateTime::parse_from_str("Sun, 20 Aug 2023 13:02:03 -0500", "%a, %d %b %Y %T %z").unwrap();
+        assert_eq!(d.to_rfc3339(), "2023-08-20T13:02:03.000000-05:00");
+        let d = DateTime::parse_from_str("2023-232 12:00:00.250", "%Y-%j %T%.3f").unwrap();
+        assert_eq!(d.to_string(), "2023-08-20 12:00:00.250000");
+        let d = DateTime::parse_from_str("1692507723 +08:00", "%s %:z").unwrap();
+        assert_eq!(d.to_rfc3339(), "2023-08-20T13:02:03.000000+08:00");
+        assert_eq!(DateTime::parse_from_str("12:30", "%H:%M").unwrap().to_string(), "1970-01-01 12:30:00.000000");
+        assert!(DateTime::parse_from_str("Mon, 20 Aug 2023", "%a, %d %b %Y").is_err());
+        assert!(DateTime::parse_from_str("2023-02-29", "%F").is_err());
+        assert!(DateTime::parse_from_str("0000-06-01", "%F").is_err());
+        assert!(DateTime::parse_from_str("2023-08-20x", "%F").is_err());
+        assert!(DateTime::parse_from_str("2023-08-20", "%Q").is_err());
+    }
+}
@@ -0,0 +1,90 @@
+//! dates in http headers, as in rfc 9110. the IMF-fixdate `Sun, 06 Nov 1994 08:49:37 GMT` is
+//! written, the obsolete rfc 850 `Sunday, 06-Nov-94 08:49:37 GMT` and asctime
+//! `Sun Nov  6 08:49:37 1994` forms are read as well
+use core::time::Duration;
+use alloc::string::String;
+use crate::DateTime;
+use crate::error::{Error, Field};
+
+const IMF_FIXDATE: &str = "%a, %d %b %Y %T GMT";
+const RFC_850: &str = "%A, %d-%b-%y %T GMT";
+const ASCTIME: &str = "%a %b %d %T %Y";
+
+/// the value of a `Retry-After` header
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum RetryAfter {
+    /// a number of seconds to wait
+    Delay(Duration),
+    /// an http date to wait until
+    Date(DateTime),
+}
+
+/// parse an http date in any of its three forms, at utc
+pub fn parse_http_date(s: &str) -> Result<DateTime, Error> {
+    let s = s.trim();
+    if let Ok(d) = DateTime::parse_from_str(s, IMF_FIXDATE) {
+        return Ok(d);
+    }
+    if let Ok(d) = DateTime::parse_from_str(s, RFC_850) {
+        return Ok(d);
+    }
+    // asctime pads the day with a space
+    let mut bytes = s.as_bytes().to_vec();
+    if bytes.len() == 24 && bytes[8] == b' ' {
+        bytes[8] = b'0';
+    }
+    let s = core::str::from_utf8(&bytes).map_err(|_| Error::InvalidHttpDate)?;
+    DateTime::parse_from_str(s, ASCTIME).map_err(|_| Error::InvalidHttpDate)
+}
+
+/// format as an IMF-fixdate at utc, the fraction of a second is dropped
+pub fn format_http_date(dt: &DateTime) -> String {
+    dt.to_offset_secs(0).format(IMF_FIXDATE)
+}
+
+/// the value of a `Last-Modified` header
+pub fn format_last_modified(dt: &DateTime) -> String {
+    format_http_date(dt)
+}
+
+/// the value of an `If-Modified-Since` header. it has whole seconds, so compare it with
+/// the last modification time truncated to seconds
+pub fn parse_if_modified_since(s: &str) -> Result<DateTime, Error> {
+    parse_http_date(s)
+}
+
+/// the value of a `Retry-After` header, a number of seconds or an http date
+pub fn parse_retry_after(s: &str) -> Result<RetryAfter, Error> {
+    let s = s.trim();
+    if !s.is_empty() && s.bytes().all(|c| c.is_ascii_digit()) {
+        let secs = s.parse::<u64>().map_err(|_| Error::OutOfRange { field: Field::RetryAfter })?;
+        return Ok(RetryAfter::Delay(Duration::from_secs(secs)));
+    }
+    parse_http_date(s).map(RetryAfter::Date)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use std::time::Duration;
+    use crate::DateTime;
+    use super::*;
+
+    #[test]
+    fn test_http_dates() {
+        let d = DateTime::from_str("1994-11-06T08:49:37Z").unwrap();
+        assert_eq!(parse_if_modified_since("Sun, 06 Nov 1994 08:49:37 GMT").unwrap(), d);
+        assert_eq!(parse_if_modified_since("Sunday, 06-Nov-94 08:49:37 GMT").unwrap(), d);
+        assert_eq!(parse_if_modified_since("Sun Nov  6 08:49:37 1994").unwrap(), d);
+        assert!(parse_if_modified_since("1994-11-06T08:49:37Z").is_err());
+        assert!(parse_if_modified_since("Sun, 06 Nov 1994 08:49:37 PST").is_err());
+        let local = DateTime::from_str("1994-11-06T09:49:37.5+01:00").unwrap();
+        assert_eq!(format_last_modified(&local), "Sun, 06 Nov 1994 08:49:37 GMT");
+
+        assert_eq!(parse_retry_after(" 120 ").unwrap(), RetryAfter::Delay(Duration::from_secs(120)));
+        assert_eq!(parse_retry_after("Fri, 31 Dec 1999 23:59:59 GMT").unwrap(),
+            RetryAfter::Date(DateTime::from_str("1999-12-31T23:59:59Z").unwrap()));
+        assert!(parse_retry_after("-1").is_err());
+        assert!(parse_retry_after("").is_err());
+    }
+}
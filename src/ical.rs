@@ -0,0 +1,110 @@
+use core::fmt::{self, Display, Formatter};
+use core::str::FromStr;
+use alloc::string::{String, ToString};
+use crate::DateTime;
+use crate::error::Error;
+
+const ICAL_PATTERN: &str = "%Y%m%dT%H%M%S";
+
+/// An iCalendar DATE-TIME of rfc 5545, with whole seconds.
+///
+/// Parse using `FromStr`, which also takes the `TZID=...:` parameter in front of the value.
+/// Format using the `Display` trait.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum IcalDateTime {
+    /// `19970714T173000Z`, at utc
+    Utc(DateTime),
+    /// `19970714T173000`, the same wall-clock time in any zone
+    Floating(DateTime),
+    /// `TZID=America/New_York:19970714T133000`, wall-clock time in a zone that is not resolved
+    Zoned { tzid: String, local: DateTime },
+}
+
+impl IcalDateTime {
+    /// the fields of the value at offset 0, for `Utc` that is also the instant
+    pub fn datetime(&self) -> DateTime {
+        match self {
+            IcalDateTime::Utc(d) | IcalDateTime::Floating(d) => *d,
+            IcalDateTime::Zoned { local, .. } => *local,
+        }
+    }
+}
+
+impl DateTime {
+    /// format the instant as an iCalendar utc DATE-TIME, `19970714T173000Z`,
+    /// the fraction of a second is dropped
+    pub fn to_ical(&self) -> String {
+        let mut s = self.to_offset_secs(0).format(ICAL_PATTERN);
+        s.push('Z');
+        s
+    }
+}
+
+impl FromStr for IcalDateTime {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        if let Some(param) = s.strip_prefix("TZID=") {
+            let (tzid, value) = param.rsplit_once(':').ok_or(Error::InvalidIcalTzid)?;
+            // a tzid with special characters is quoted
+            let tzid = tzid.strip_prefix('"').and_then(|t| t.strip_suffix('"')).unwrap_or(tzid);
+            if tzid.is_empty() {
+                return Err(Error::InvalidIcalTzid);
+            }
+            if value.ends_with('Z') {
+                return Err(Error::InvalidIcalTzid);
+            }
+            let local = DateTime::parse_from_str(value, ICAL_PATTERN)?;
+            return Ok(IcalDateTime::Zoned { tzid: tzid.to_string(), local });
+        }
+        match s.strip_suffix('Z') {
+            Some(value) => Ok(IcalDateTime::Utc(DateTime::parse_from_str(value, ICAL_PATTERN)?)),
+            None => Ok(IcalDateTime::Floating(DateTime::parse_from_str(s, ICAL_PATTERN)?)),
+        }
+    }
+}
+
+impl Display for IcalDateTime {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            IcalDateTime::Utc(d) => f.write_str(&d.to_ical()),
+            IcalDateTime::Floating(d) => f.write_str(&d.format(ICAL_PATTERN)),
+            IcalDateTime::Zoned { tzid, local } => {
+                if tzid.contains([':', ';', ',']) {
+                    write!(f, "TZID=\"{}\":{}", tzid, local.format(ICAL_PATTERN))
+                } else {
+                    write!(f, "TZID={}:{}", tzid, local.format(ICAL_PATTERN))
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use crate::{DateTime, IcalDateTime};
+
+    #[test]
+    fn test_ical_datetime() {
+        let utc = IcalDateTime::from_str("19970714T173000Z").unwrap();
+        assert_eq!(utc, IcalDateTime::Utc(DateTime::from_str("1997-07-14T17:30:00Z").unwrap()));
+        assert_eq!(utc.to_string(), "19970714T173000Z");
+        let floating = IcalDateTime::from_str("19980118T230000").unwrap();
+        assert_eq!(floating.datetime().to_string(), "1998-01-18 23:00:00.000000");
+        assert_eq!(floating.to_string(), "19980118T230000");
+        let zoned = IcalDateTime::from_str("TZID=America/New_York:19970714T133000").unwrap();
+        assert_eq!(zoned, IcalDateTime::Zoned {
+            tzid: "America/New_York".to_string(),
+            local: DateTime::from_str("1997-07-14T13:30:00Z").unwrap(),
+        });
+        assert_eq!(zoned.to_string(), "TZID=America/New_York:19970714T133000");
+        let quoted = IcalDateTime::from_str("TZID=\"Custom:Zone\":19970714T133000").unwrap();
+        assert_eq!(quoted.to_string(), "TZID=\"Custom:Zone\":19970714T133000");
+        let d = DateTime::from_str("1997-07-14T13:30:00.5-04:00").unwrap();
+        assert_eq!(d.to_ical(), "19970714T173000Z");
+        assert!(IcalDateTime::from_str("TZID=America/New_York:19970714T133000Z").is_err());
+        assert!(IcalDateTime::from_str("1997-07-14T17:30:00Z").is_err());
+        assert!(IcalDateTime::from_str("19970714").is_err());
+    }
+}
@@ -1,31 +1,82 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+extern crate alloc;
+
+#[cfg(feature = "std")]
 pub mod sys;
 pub mod error;
+pub mod http;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "arrow")]
+pub mod arrow;
+#[cfg(feature = "avro")]
+pub mod avro;
+#[cfg(feature = "redis")]
+pub mod redis;
+#[cfg(feature = "tracing")]
+pub mod tracing;
 
 mod date;
 mod time;
 mod datetime;
+mod period;
+mod duration;
+mod weekday;
+mod month;
+mod every;
+mod repeating;
+mod ical;
+mod view;
+mod local;
+mod clock;
+mod batch;
+mod digits;
+mod format;
+mod mysql;
+mod msgpack;
+mod ext;
 pub use date::*;
 pub use time::*;
 pub use datetime::*;
+pub use period::*;
+pub use duration::*;
+pub use weekday::*;
+pub use month::*;
+pub use every::*;
+pub use repeating::*;
+pub use ical::*;
+pub use view::*;
+pub use local::*;
+pub use clock::*;
+pub use batch::*;
+pub use format::{FORMAT_COMPACT, FORMAT_DATE, FORMAT_DATETIME, FORMAT_RFC3339_MICROS, FORMAT_RFC3339_MILLIS, FORMAT_TIME};
+#[cfg(feature = "rand")]
+pub use ext::rand::{UniformDate, UniformDateTime};
 
 
 // get a character from the bytes as as a decimal
 macro_rules! get_digit {
-    ($bytes:ident, $index:expr, $error:expr) => {
+    ($bytes:ident, $index:expr, $field:expr) => {
         match $bytes.get($index) {
-            Some(c) if (b'0'..=b'9').contains(&c) => c - b'0',
-            _ => return Err(Error::E($error.to_string())),
+            Some(c) if c.is_ascii_digit() => c - b'0',
+            _ => return Err(Error::InvalidChar { field: $field, pos: $index }),
         }
     };
 }
 pub(crate) use get_digit;
-// as above without bounds check, requires length to checked first!
-macro_rules! get_digit_unchecked {
-    ($bytes:ident, $index:expr, $error:expr) => {
-        match $bytes.get_unchecked($index) {
-            c if (b'0'..=b'9').contains(&c) => c - b'0',
-            _ => return Err(Error::E($error.to_string())),
+// the decimal value of `n` ascii digits starting at `index`, usable in const fns
+pub(crate) const fn const_digits(bytes: &[u8], index: usize, n: usize) -> Option<u32> {
+    if index + n > bytes.len() {
+        return None;
+    }
+    let mut v = 0;
+    let mut i = index;
+    while i < index + n {
+        if !bytes[i].is_ascii_digit() {
+            return None;
         }
-    };
+        v = v * 10 + (bytes[i] - b'0') as u32;
+        i += 1;
+    }
+    Some(v)
 }
-pub(crate) use get_digit_unchecked;
\ No newline at end of file
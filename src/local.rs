@@ -0,0 +1,172 @@
+use crate::{Date, DateTime, Time};
+
+/// The instants a wall-clock time has in a zone with daylight saving or other offset changes
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum LocalResult<T> {
+    /// the time exists once
+    Unique(T),
+    /// the time repeats when the clocks go back, at the earlier and the later offset
+    Ambiguous(T, T),
+    /// the time is skipped when the clocks go forward
+    Gap,
+}
+
+impl<T> LocalResult<T> {
+    /// the value of `Unique`, `None` when it is ambiguous or in a gap
+    pub fn single(self) -> Option<T> {
+        match self {
+            LocalResult::Unique(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// the value of `Unique` or the earlier one of `Ambiguous`
+    pub fn earliest(self) -> Option<T> {
+        match self {
+            LocalResult::Unique(v) | LocalResult::Ambiguous(v, _) => Some(v),
+            LocalResult::Gap => None,
+        }
+    }
+
+    /// the value of `Unique` or the later one of `Ambiguous`
+    pub fn latest(self) -> Option<T> {
+        match self {
+            LocalResult::Unique(v) | LocalResult::Ambiguous(_, v) => Some(v),
+            LocalResult::Gap => None,
+        }
+    }
+}
+
+impl DateTime {
+    /// the instants of the wall-clock `date` and `time` in the local zone of the os.
+    /// a time the clocks skip is a `Gap` and one they repeat is `Ambiguous`, rather than
+    /// picking one of the offsets
+    #[cfg(feature = "std")]
+    pub fn from_local(date: Date, time: Time) -> LocalResult<DateTime> {
+        Self::from_zone(date, time, |sec| crate::sys::Timespec { sec, nsec: 0 }.local().tm_utcoff)
+    }
+
+    /// the instants of the wall-clock `date` and `time` in a zone whose utc offset in seconds
+    /// at a unix second is `offset_at`, e.g. from a tz database. as `from_local`, assuming the
+    /// offset changes at most once within a day of the time
+    pub fn from_zone(date: Date, time: Time, offset_at: impl Fn(i64) -> i32) -> LocalResult<DateTime> {
+        let wall = DateTime::new(date, time, 0);
+        let secs = wall.unix_micros().div_euclid(1_000_000);
+        let (before, after) = (offset_at(secs - 86400), offset_at(secs + 86400));
+        let at = |offset: i32| {
+            let v = DateTime { offset, ..wall };
+            (offset_at(v.unix_micros().div_euclid(1_000_000)) == offset).then_some(v)
+        };
+        if before == after {
+            return match at(before) {
+                Some(v) => LocalResult::Unique(v),
+                None => LocalResult::Gap,
+            };
+        }
+        match (at(before), at(after)) {
+            (Some(a), Some(b)) if a.unix_micros() <= b.unix_micros() => LocalResult::Ambiguous(a, b),
+            (Some(a), Some(b)) => LocalResult::Ambiguous(b, a),
+            (Some(v), None) | (None, Some(v)) => LocalResult::Unique(v),
+            (None, None) => LocalResult::Gap,
+        }
+    }
+
+    /// the first instant after `self` at which the wall clock of the zone `offset_at`, as for
+    /// `from_zone`, reads `time`, for daily jobs. on a day the clocks skip `time` it is the
+    /// instant `time` would be at the offset before the change, e.g. 03:30 for a 02:30 skipped
+    /// by moving from 02:00 to 03:00, and of a time they repeat only the earlier instant
+    /// counts, so the job runs once every day. `None` past `DateTime::MAX` or if `offset_at`
+    /// gives no such instant within 2 days of the first candidate
+    pub fn next_occurrence(&self, time: Time, offset_at: impl Fn(i64) -> i32) -> Option<DateTime> {
+        let start = self.with_timezone(&offset_at).ok()?;
+        let first = Date::from(start).to_unix_day();
+        for day in first..=first + 2 {
+            let date = Date::from_unix_day(day)?;
+            let v = match Self::from_zone(date, time, &offset_at) {
+                LocalResult::Unique(v) | LocalResult::Ambiguous(v, _) => v,
+                LocalResult::Gap => {
+                    let wall = DateTime::new(date, time, 0);
+                    let before = offset_at(wall.unix_micros().div_euclid(1_000_000) - 86400);
+                    DateTime { offset: before, ..wall }.with_timezone(&offset_at).ok()?
+                }
+            };
+            if v > *self {
+                return Some(v);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use crate::{Date, DateTime, LocalResult, Time};
+
+    /// +01:00, +02:00 from 2023-03-26 01:00 utc until 2023-10-29 01:00 utc
+    fn paris(sec: i64) -> i32 {
+        if (1679792400..1698541200).contains(&sec) { 7200 } else { 3600 }
+    }
+
+    fn resolve(date: &str, time: &str) -> LocalResult<String> {
+        match DateTime::from_zone(Date::from_str(date).unwrap(), Time::from_str(time).unwrap(), paris) {
+            LocalResult::Unique(v) => LocalResult::Unique(v.to_rfc3339()),
+            LocalResult::Ambiguous(a, b) => LocalResult::Ambiguous(a.to_rfc3339(), b.to_rfc3339()),
+            LocalResult::Gap => LocalResult::Gap,
+        }
+    }
+
+    #[test]
+    fn test_local_result() {
+        assert_eq!(resolve("2023-08-20", "13:02:03"), LocalResult::Unique("2023-08-20T13:02:03.000000+02:00".to_string()));
+        assert_eq!(resolve("2023-03-26", "01:59:59"), LocalResult::Unique("2023-03-26T01:59:59.000000+01:00".to_string()));
+        assert_eq!(resolve("2023-03-26", "02:30:00"), LocalResult::Gap);
+        assert_eq!(resolve("2023-03-26", "03:00:00"), LocalResult::Unique("2023-03-26T03:00:00.000000+02:00".to_string()));
+        assert_eq!(
+            resolve("2023-10-29", "02:30:00"),
+            LocalResult::Ambiguous("2023-10-29T02:30:00.000000+02:00".to_string(), "2023-10-29T02:30:00.000000+01:00".to_string())
+        );
+        assert_eq!(resolve("2023-10-29", "03:00:00"), LocalResult::Unique("2023-10-29T03:00:00.000000+01:00".to_string()));
+        let r = resolve("2023-10-29", "02:30:00");
+        assert_eq!(r.clone().earliest().unwrap(), "2023-10-29T02:30:00.000000+02:00");
+        assert_eq!(r.clone().latest().unwrap(), "2023-10-29T02:30:00.000000+01:00");
+        assert_eq!(r.single(), None);
+    }
+
+    #[test]
+    fn test_next_occurrence() {
+        let next = |from: &str, time: &str| {
+            let from = DateTime::from_str(from).unwrap();
+            from.next_occurrence(Time::from_str(time).unwrap(), paris).map(|d| d.to_rfc3339())
+        };
+        assert_eq!(next("2023-08-20T10:00:00Z", "13:02:03").unwrap(), "2023-08-20T13:02:03.000000+02:00");
+        assert_eq!(next("2023-08-20T12:00:00Z", "13:02:03").unwrap(), "2023-08-21T13:02:03.000000+02:00");
+        assert_eq!(next("2023-08-20T11:02:03Z", "13:02:03").unwrap(), "2023-08-21T13:02:03.000000+02:00");
+        // 22:30 utc is already the next day in paris
+        assert_eq!(next("2023-08-20T22:30:00Z", "01:00:00").unwrap(), "2023-08-21T01:00:00.000000+02:00");
+        assert_eq!(next("2023-08-20T22:30:00Z", "00:15:00").unwrap(), "2023-08-22T00:15:00.000000+02:00");
+        // 02:30 does not exist on 2023-03-26, the job runs an hour later that day
+        assert_eq!(next("2023-03-25T12:00:00Z", "02:30:00").unwrap(), "2023-03-26T03:30:00.000000+02:00");
+        assert_eq!(next("2023-03-26T00:00:00Z", "02:00:00").unwrap(), "2023-03-26T03:00:00.000000+02:00");
+        assert_eq!(next("2023-03-26T01:30:00Z", "02:30:00").unwrap(), "2023-03-27T02:30:00.000000+02:00");
+        assert_eq!(next("2023-03-25T12:00:00Z", "03:30:00").unwrap(), "2023-03-26T03:30:00.000000+02:00");
+        // 02:30 happens twice on 2023-10-29, only the first one counts
+        assert_eq!(next("2023-10-28T12:00:00Z", "02:30:00").unwrap(), "2023-10-29T02:30:00.000000+02:00");
+        assert_eq!(next("2023-10-29T00:30:00Z", "02:30:00").unwrap(), "2023-10-30T02:30:00.000000+01:00");
+        assert_eq!(next("9999-12-31T23:00:00Z", "00:00:00"), None);
+        // a zone whose offset never settles is searched for a few days, not until the year 9999
+        let from = DateTime::from_str("2023-08-20T10:00:00Z").unwrap();
+        let noon = Time::from_str("12:00:00").unwrap();
+        let flip = |sec: i64| if sec.rem_euclid(2) == 0 { 0 } else { 3600 };
+        assert!(from.next_occurrence(noon, flip).is_some_and(|v| v > from && v.unix_timestamp() < 1692525600 + 3 * 86400));
+        assert_eq!(from.next_occurrence(noon, |_| 86400), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_local_result_os() {
+        let now = DateTime::now();
+        let local = DateTime::from_local(Date::from(now), Time::from(now));
+        assert!(local.earliest().is_some() || local == LocalResult::Gap);
+    }
+}
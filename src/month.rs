@@ -0,0 +1,108 @@
+use core::fmt::{self, Display, Formatter};
+use core::str::FromStr;
+use crate::error::{Error, Field};
+use crate::weekday::find_name;
+
+pub(crate) const MONTHS: [&str; 12] = [
+    "January", "February", "March", "April", "May", "June",
+    "July", "August", "September", "October", "November", "December",
+];
+
+/// Month of the year, `Month::try_from(date.month())` for the month of a date
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Month {
+    January = 1,
+    February,
+    March,
+    April,
+    May,
+    June,
+    July,
+    August,
+    September,
+    October,
+    November,
+    December,
+}
+
+impl Month {
+    /// 1 = january ... 12 = december
+    pub fn number(self) -> u8 {
+        self as u8
+    }
+
+    /// the english name, e.g. "January"
+    pub fn name(self) -> &'static str {
+        MONTHS[self as usize - 1]
+    }
+}
+
+impl TryFrom<u8> for Month {
+    type Error = Error;
+
+    /// 1 = january ... 12 = december
+    fn try_from(n: u8) -> Result<Self, Error> {
+        Ok(match n {
+            1 => Month::January,
+            2 => Month::February,
+            3 => Month::March,
+            4 => Month::April,
+            5 => Month::May,
+            6 => Month::June,
+            7 => Month::July,
+            8 => Month::August,
+            9 => Month::September,
+            10 => Month::October,
+            11 => Month::November,
+            12 => Month::December,
+            _ => return Err(Error::OutOfRange { field: Field::Month }),
+        })
+    }
+}
+
+impl From<Month> for u8 {
+    fn from(m: Month) -> Self {
+        m.number()
+    }
+}
+
+impl FromStr for Month {
+    type Err = Error;
+
+    /// the english name or its 3 letter abbreviation in any case, "jan" or "January"
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let i = find_name(s, &MONTHS).ok_or(Error::InvalidChar { field: Field::Month, pos: 0 })?;
+        Month::try_from(i as u8 + 1)
+    }
+}
+
+impl Display for Month {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+    use core::str::FromStr;
+    use crate::error::{Error, Field};
+    use crate::Month;
+
+    #[test]
+    fn test_month_names() {
+        assert_eq!(Month::from_str("jan").unwrap(), Month::January);
+        assert_eq!(Month::from_str("SEPTEMBER").unwrap(), Month::September);
+        assert_eq!(Month::from_str("Dec").unwrap(), Month::December);
+        assert_eq!(Month::from_str("janu").unwrap_err(), Error::InvalidChar { field: Field::Month, pos: 0 });
+        assert!(Month::from_str("").is_err());
+        assert_eq!(Month::March.to_string(), "March");
+        assert_eq!(Month::try_from(12).unwrap(), Month::December);
+        assert_eq!(Month::try_from(0).unwrap_err(), Error::OutOfRange { field: Field::Month });
+        for n in 1..=12 {
+            let m = Month::try_from(n).unwrap();
+            assert_eq!(u8::from(m), n);
+            assert_eq!(Month::from_str(&m.to_string()).unwrap(), m);
+        }
+    }
+}
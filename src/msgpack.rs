@@ -0,0 +1,93 @@
+//! the messagepack timestamp extension, type -1. the 32-bit form holds whole seconds
+//! from the epoch up to 2106, the 64-bit form adds 30 bits of nanoseconds up to 2514
+//! and the 96-bit form has signed 64-bit seconds
+use alloc::vec::Vec;
+use crate::DateTime;
+use crate::error::{Error, Field};
+
+/// the extension type of timestamps
+const TIMESTAMP_TYPE: u8 = 0xff;
+
+impl DateTime {
+    /// append the instant as a timestamp extension, header included, in the smallest form
+    pub fn write_msgpack_timestamp(&self, buf: &mut Vec<u8>) {
+        let (secs, nanos) = self.unix_secs_nanos();
+        let nanos = nanos as u64;
+        if secs >> 34 == 0 {
+            let data = (nanos << 34) | secs as u64;
+            if data >> 32 == 0 {
+                buf.extend_from_slice(&[0xd6, TIMESTAMP_TYPE]);
+                buf.extend_from_slice(&(data as u32).to_be_bytes());
+            } else {
+                buf.extend_from_slice(&[0xd7, TIMESTAMP_TYPE]);
+                buf.extend_from_slice(&data.to_be_bytes());
+            }
+        } else {
+            buf.extend_from_slice(&[0xc7, 12, TIMESTAMP_TYPE]);
+            buf.extend_from_slice(&(nanos as u32).to_be_bytes());
+            buf.extend_from_slice(&secs.to_be_bytes());
+        }
+    }
+
+    /// read a timestamp extension in any of its forms from the start of `bytes` at utc,
+    /// truncated to microseconds, also returning the number of bytes it took
+    pub fn read_msgpack_timestamp(bytes: &[u8]) -> Result<(Self, usize), Error> {
+        let (header, len) = match bytes.first() {
+            Some(0xd6) => (2, 4),
+            Some(0xd7) => (2, 8),
+            Some(0xc7) if bytes.get(1) == Some(&12) => (3, 12),
+            Some(_) => return Err(Error::InvalidMsgpackTimestamp),
+            None => return Err(Error::TooShort),
+        };
+        if bytes.get(header - 1) != Some(&TIMESTAMP_TYPE) {
+            return Err(Error::InvalidMsgpackTimestamp);
+        }
+        let data = bytes.get(header..header + len).ok_or(Error::TooShort)?;
+        let (secs, nanos) = match len {
+            4 => (u32::from_be_bytes(data.try_into().unwrap()) as i64, 0),
+            8 => {
+                let v = u64::from_be_bytes(data.try_into().unwrap());
+                ((v & 0x3_ffff_ffff) as i64, v >> 34)
+            }
+            _ => (
+                i64::from_be_bytes(data[4..].try_into().unwrap()),
+                u32::from_be_bytes(data[..4].try_into().unwrap()) as u64,
+            ),
+        };
+        if nanos >= 1_000_000_000 {
+            return Err(Error::OutOfRange { field: Field::Nanos });
+        }
+        let dt = DateTime::from_unix_nanos(secs as i128 * 1_000_000_000 + nanos as i128).ok_or(Error::OutOfRange { field: Field::Timestamp })?;
+        Ok((dt, header + len))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use crate::DateTime;
+
+    #[test]
+    fn test_msgpack_timestamp() {
+        let mut buf = Vec::new();
+        DateTime::from_str("2023-08-20T13:02:03+08:00").unwrap().write_msgpack_timestamp(&mut buf);
+        assert_eq!(buf, [0xd6, 0xff, 0x64, 0xe1, 0x9e, 0x4b]);
+        assert_eq!(DateTime::read_msgpack_timestamp(&buf).unwrap().1, 6);
+        buf.clear();
+        let d = DateTime::from_str("2023-08-20T05:02:03.123456Z").unwrap();
+        d.write_msgpack_timestamp(&mut buf);
+        assert_eq!(buf.len(), 10);
+        assert_eq!(DateTime::read_msgpack_timestamp(&buf).unwrap(), (d, 10));
+        buf.clear();
+        let before = DateTime::from_str("1969-12-31T23:59:59.5Z").unwrap();
+        before.write_msgpack_timestamp(&mut buf);
+        assert_eq!(buf[..3], [0xc7, 12, 0xff]);
+        assert_eq!(buf[3..7], 500_000_000u32.to_be_bytes());
+        assert_eq!(buf[7..], (-1i64).to_be_bytes());
+        assert_eq!(DateTime::read_msgpack_timestamp(&buf).unwrap(), (before, 15));
+
+        assert!(DateTime::read_msgpack_timestamp(&[0xd6, 0x01, 0, 0, 0, 0]).is_err());
+        assert!(DateTime::read_msgpack_timestamp(&[0xd6, 0xff, 0, 0]).is_err());
+        assert!(DateTime::read_msgpack_timestamp(&[0xd7, 0xff, 0xff, 0xff, 0xff, 0xff, 0, 0, 0, 0]).is_err());
+    }
+}
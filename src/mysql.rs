@@ -0,0 +1,225 @@
+//! the mysql binary protocol form of DATETIME, TIMESTAMP and DATE values: a length byte
+//! of 0, 4, 7 or 11, then the little-endian year, month, day, hour, minute, second and
+//! microseconds, with the trailing zero fields left out.
+//!
+//! outside of strict mode mysql stores the zero date `0000-00-00 00:00:00` for missing or
+//! invalid values. the usual readers fail on it as any out of range year, the `_opt` ones
+//! give `None`
+use alloc::vec::Vec;
+use core::str::FromStr;
+use crate::{days_in_month, Date, DateTime, Time};
+use crate::error::{Error, Field};
+
+/// the length byte and the bytes it covers, at the start of `bytes`
+fn read_value(bytes: &[u8]) -> Result<&[u8], Error> {
+    let len = *bytes.first().ok_or(Error::TooShort)? as usize;
+    let body = bytes.get(1..1 + len).ok_or(Error::TooShort)?;
+    match len {
+        // the zero date 0000-00-00 00:00:00 can not be represented
+        0 => Err(Error::OutOfRange { field: Field::Year }),
+        4 | 7 | 11 => Ok(body),
+        _ => Err(Error::InvalidLength),
+    }
+}
+
+/// whether `s` is the zero date "0000-00-00", optionally followed by a zero time and fraction
+fn is_zero_text(s: &str) -> bool {
+    let b = s.as_bytes();
+    match b.len() {
+        10 => b == b"0000-00-00",
+        19.. => {
+            &b[..19] == b"0000-00-00 00:00:00"
+                && (b.len() == 19 || (b[19] == b'.' && b.len() > 20 && b[20..].iter().all(|c| *c == b'0')))
+        }
+        _ => false,
+    }
+}
+
+/// whether a binary protocol value is the zero date, sent as length 0 or as zero fields
+fn is_zero_binary(bytes: &[u8]) -> bool {
+    match bytes.first() {
+        Some(0) => true,
+        Some(&len) => bytes.get(1..1 + len as usize).is_some_and(|body| body.iter().all(|b| *b == 0)),
+        None => false,
+    }
+}
+
+fn read_date(body: &[u8]) -> Result<Date, Error> {
+    let year = u16::from_le_bytes([body[0], body[1]]);
+    let (mon, day) = (body[2], body[3]);
+    if year == 0 {
+        return Err(Error::OutOfRange { field: Field::Year });
+    }
+    let max_days = days_in_month(year, mon);
+    if max_days == 0 {
+        return Err(Error::OutOfRange { field: Field::Month });
+    }
+    if day < 1 || day > max_days {
+        return Err(Error::OutOfRange { field: Field::Day });
+    }
+    Ok(Date { day, mon, year })
+}
+
+impl DateTime {
+    /// append the binary protocol form of the wall-clock fields, the offset is not sent
+    pub fn write_mysql_binary(&self, buf: &mut Vec<u8>) {
+        let len = if self.micro != 0 {
+            11
+        } else if self.hour != 0 || self.min != 0 || self.sec != 0 {
+            7
+        } else {
+            4
+        };
+        buf.push(len);
+        buf.extend_from_slice(&self.year.to_le_bytes());
+        buf.extend_from_slice(&[self.mon, self.day]);
+        if len >= 7 {
+            buf.extend_from_slice(&[self.hour, self.min, self.sec]);
+        }
+        if len == 11 {
+            buf.extend_from_slice(&self.micro.to_le_bytes());
+        }
+    }
+
+    /// read a binary protocol value from the start of `bytes` at utc, also returning
+    /// the number of bytes it took
+    pub fn read_mysql_binary(bytes: &[u8]) -> Result<(Self, usize), Error> {
+        let body = read_value(bytes)?;
+        let date = read_date(body)?;
+        let mut time = Time { micro: 0, sec: 0, min: 0, hour: 0 };
+        if body.len() >= 7 {
+            time = time.with_hour(body[4])?.with_minute(body[5])?.with_second(body[6])?;
+        }
+        if body.len() == 11 {
+            time = time.with_micro(u32::from_le_bytes([body[7], body[8], body[9], body[10]]))?;
+        }
+        let dt = DateTime {
+            micro: time.micro,
+            sec: time.sec,
+            min: time.min,
+            hour: time.hour,
+            day: date.day,
+            mon: date.mon,
+            year: date.year,
+            offset: 0,
+        };
+        Ok((dt, 1 + body.len()))
+    }
+
+    /// as `read_mysql_binary`, with the zero date as `None`
+    pub fn read_mysql_binary_opt(bytes: &[u8]) -> Result<(Option<Self>, usize), Error> {
+        if is_zero_binary(bytes) {
+            return Ok((None, 1 + bytes[0] as usize));
+        }
+        Self::read_mysql_binary(bytes).map(|(v, n)| (Some(v), n))
+    }
+
+    /// parse the text form of a DATETIME or TIMESTAMP, "YYYY-MM-DD HH:MM:SS" with an optional
+    /// fraction, or a bare date at midnight, at utc. the zero date `0000-00-00 00:00:00` is `None`
+    pub fn parse_mysql(s: &str) -> Result<Option<Self>, Error> {
+        if is_zero_text(s) {
+            return Ok(None);
+        }
+        if s.len() == 10 {
+            return Ok(Some(DateTime::new(Date::from_str(s)?, Time::MIDNIGHT, 0)));
+        }
+        DateTime::parse_rfc3339(s).map(Some)
+    }
+}
+
+impl Date {
+    /// append the binary protocol form of a DATE, always 4 bytes after the length
+    pub fn write_mysql_binary(&self, buf: &mut Vec<u8>) {
+        buf.push(4);
+        buf.extend_from_slice(&self.year.to_le_bytes());
+        buf.extend_from_slice(&[self.mon, self.day]);
+    }
+
+    /// read a binary protocol DATE from the start of `bytes`, also returning the number
+    /// of bytes it took. a value with a time of day is an error
+    pub fn read_mysql_binary(bytes: &[u8]) -> Result<(Self, usize), Error> {
+        let body = read_value(bytes)?;
+        if body.len() != 4 {
+            return Err(Error::InvalidLength);
+        }
+        Ok((read_date(body)?, 5))
+    }
+
+    /// as `read_mysql_binary`, with the zero date as `None`
+    pub fn read_mysql_binary_opt(bytes: &[u8]) -> Result<(Option<Self>, usize), Error> {
+        if is_zero_binary(bytes) {
+            return Ok((None, 1 + bytes[0] as usize));
+        }
+        Self::read_mysql_binary(bytes).map(|(v, n)| (Some(v), n))
+    }
+
+    /// parse the text form of a DATE, "YYYY-MM-DD", with the zero date `0000-00-00` as `None`
+    pub fn parse_mysql(s: &str) -> Result<Option<Self>, Error> {
+        if s == "0000-00-00" {
+            return Ok(None);
+        }
+        Date::from_str(s).map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use crate::{Date, DateTime};
+
+    #[test]
+    fn test_mysql_binary() {
+        let mut buf = Vec::new();
+        DateTime::from_str("2023-08-20T13:02:03.123456+08:00").unwrap().write_mysql_binary(&mut buf);
+        assert_eq!(buf, [11, 0xe7, 0x07, 8, 20, 13, 2, 3, 0x40, 0xe2, 0x01, 0x00]);
+        let (d, n) = DateTime::read_mysql_binary(&buf).unwrap();
+        assert_eq!((d.to_rfc3339().as_str(), n), ("2023-08-20T13:02:03.123456Z", 12));
+        buf.clear();
+        DateTime::from_str("2023-08-20T13:02:03Z").unwrap().write_mysql_binary(&mut buf);
+        assert_eq!(buf, [7, 0xe7, 0x07, 8, 20, 13, 2, 3]);
+        buf.clear();
+        DateTime::from_str("2023-08-20T00:00:00Z").unwrap().write_mysql_binary(&mut buf);
+        buf.extend_from_slice(b"rest");
+        assert_eq!(&buf[..5], [4, 0xe7, 0x07, 8, 20]);
+        let (d, n) = DateTime::read_mysql_binary(&buf).unwrap();
+        assert_eq!((d.to_string().as_str(), n), ("2023-08-20 00:00:00.000000", 5));
+        assert_eq!(Date::read_mysql_binary(&buf).unwrap(), (Date::from_str("2023-08-20").unwrap(), 5));
+        buf.clear();
+        Date::from_str("2023-08-20").unwrap().write_mysql_binary(&mut buf);
+        assert_eq!(buf, [4, 0xe7, 0x07, 8, 20]);
+
+        assert!(DateTime::read_mysql_binary(&[0]).is_err());
+        assert!(DateTime::read_mysql_binary(&[7, 0xe7, 0x07, 8, 20, 13]).is_err());
+        assert!(DateTime::read_mysql_binary(&[5, 0xe7, 0x07, 8, 20, 13]).is_err());
+        assert!(DateTime::read_mysql_binary(&[4, 0xe7, 0x07, 2, 29]).is_err());
+        assert!(DateTime::read_mysql_binary(&[7, 0xe7, 0x07, 8, 20, 24, 0, 0]).is_err());
+        assert!(Date::read_mysql_binary(&[7, 0xe7, 0x07, 8, 20, 13, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn test_mysql_zero_date() {
+        assert_eq!(DateTime::parse_mysql("0000-00-00 00:00:00").unwrap(), None);
+        assert_eq!(DateTime::parse_mysql("0000-00-00 00:00:00.000000").unwrap(), None);
+        assert_eq!(DateTime::parse_mysql("0000-00-00").unwrap(), None);
+        let d = DateTime::parse_mysql("2023-08-20 13:02:03").unwrap().unwrap();
+        assert_eq!(d.to_rfc3339(), "2023-08-20T13:02:03.000000Z");
+        let d = DateTime::parse_mysql("2023-08-20 13:02:03.5").unwrap().unwrap();
+        assert_eq!(d.micro, 500000);
+        assert_eq!(DateTime::parse_mysql("2023-08-20").unwrap().unwrap().to_string(), "2023-08-20 00:00:00.000000");
+        assert!(DateTime::parse_mysql("0000-00-00 00:00:01").is_err());
+        assert!(DateTime::parse_mysql("0000-00-00 00:00:00.").is_err());
+        assert!(DateTime::parse_mysql("2023-02-30 00:00:00").is_err());
+        assert_eq!(Date::parse_mysql("0000-00-00").unwrap(), None);
+        assert_eq!(Date::parse_mysql("2023-08-20").unwrap(), Some(Date::from_str("2023-08-20").unwrap()));
+        assert!(Date::parse_mysql("2023-00-20").is_err());
+
+        assert_eq!(DateTime::read_mysql_binary_opt(&[0, 9]).unwrap(), (None, 1));
+        assert_eq!(DateTime::read_mysql_binary_opt(&[7, 0, 0, 0, 0, 0, 0, 0]).unwrap(), (None, 8));
+        assert_eq!(Date::read_mysql_binary_opt(&[4, 0, 0, 0, 0]).unwrap(), (None, 5));
+        let (d, n) = DateTime::read_mysql_binary_opt(&[4, 0xe7, 0x07, 8, 20]).unwrap();
+        assert_eq!((d.unwrap().to_string().as_str(), n), ("2023-08-20 00:00:00.000000", 5));
+        assert!(DateTime::read_mysql_binary(&[0]).is_err());
+        assert!(DateTime::read_mysql_binary_opt(&[4, 0, 0, 1, 0]).is_err());
+        assert!(Date::read_mysql_binary_opt(&[]).is_err());
+    }
+}
@@ -0,0 +1,164 @@
+use core::str::FromStr;
+use crate::{DateTime, MonthOverflow};
+use crate::error::{Error, Field};
+
+/// A calendar-aware span of time broken down into years, months, days and time of day.
+///
+/// Produced by `DateTime::period_since`; all non-zero components share the same sign.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Period {
+    pub years: i32,
+    pub months: i32,
+    pub days: i32,
+    pub hours: i32,
+    pub minutes: i32,
+    pub seconds: i32,
+    pub micros: i32,
+}
+
+impl Period {
+    /// the period from `start` to `end`, `end` must not be earlier than `start`
+    pub(crate) fn between(start: DateTime, end: DateTime) -> Self {
+        let mut months = (end.year as i32 * 12 + end.mon as i32) - (start.year as i32 * 12 + start.mon as i32);
+        // a month is only complete once the same day and time is reached again
+        let mut anchor = start.add_months(months, MonthOverflow::Clamp).unwrap_or(end);
+        if anchor > end {
+            months -= 1;
+            anchor = start.add_months(months, MonthOverflow::Clamp).unwrap_or(end);
+        }
+        let rest = (end.unix_micros() - anchor.unix_micros()).max(0) as u64;
+        let secs = rest / 1_000_000;
+        Self {
+            years: months / 12,
+            months: months % 12,
+            days: (secs / 86400) as i32,
+            hours: (secs % 86400 / 3600) as i32,
+            minutes: (secs % 3600 / 60) as i32,
+            seconds: (secs % 60) as i32,
+            micros: (rest % 1_000_000) as i32,
+        }
+    }
+
+    /// every component negated, `None` if one of them is `i32::MIN`
+    pub fn checked_neg(self) -> Option<Self> {
+        Some(Self {
+            years: self.years.checked_neg()?,
+            months: self.months.checked_neg()?,
+            days: self.days.checked_neg()?,
+            hours: self.hours.checked_neg()?,
+            minutes: self.minutes.checked_neg()?,
+            seconds: self.seconds.checked_neg()?,
+            micros: self.micros.checked_neg()?,
+        })
+    }
+
+    /// every component negated, panics if one of them is `i32::MIN`, see `checked_neg`
+    pub fn negate(self) -> Self {
+        Self {
+            years: -self.years,
+            months: -self.months,
+            days: -self.days,
+            hours: -self.hours,
+            minutes: -self.minutes,
+            seconds: -self.seconds,
+            micros: -self.micros,
+        }
+    }
+}
+
+impl FromStr for Period {
+    type Err = Error;
+
+    /// parse an ISO 8601 duration with calendar years and months, "P1Y2M10DT2H30M" or "P2W",
+    /// each designator at most once and in that order. a leading `-` negates every component,
+    /// only the seconds may have a fraction, of up to 6 digits
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, rest) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        let rest = rest.strip_prefix('P').ok_or(Error::InvalidDuration)?;
+        let overflow = Error::OutOfRange { field: Field::Duration };
+        let mut p = Period::default();
+        let (mut time, mut components, mut last) = (false, 0, 0);
+        let mut bytes = rest.as_bytes();
+        while let Some((&c, tail)) = bytes.split_first() {
+            if c == b'T' && !time {
+                time = true;
+                bytes = tail;
+                continue;
+            }
+            let digits = bytes.iter().take_while(|b| b.is_ascii_digit()).count();
+            if digits == 0 {
+                return Err(Error::InvalidDuration);
+            }
+            let v: i32 = core::str::from_utf8(&bytes[..digits]).unwrap_or_default().parse().map_err(|_| overflow)?;
+            bytes = &bytes[digits..];
+            if let [b'.' | b',', tail @ ..] = bytes {
+                let n = tail.iter().take_while(|b| b.is_ascii_digit()).count();
+                if n == 0 || n > 6 || tail.get(n) != Some(&b'S') || !time {
+                    return Err(Error::InvalidDuration);
+                }
+                p.micros = tail[..n].iter().fold(0, |acc, d| acc * 10 + (d - b'0') as i32) * 10i32.pow(6 - n as u32);
+                bytes = &tail[n..];
+            }
+            let (rank, field, v) = match (time, bytes.first()) {
+                (false, Some(b'Y')) => (1, &mut p.years, v),
+                (false, Some(b'M')) => (2, &mut p.months, v),
+                (false, Some(b'W')) => (3, &mut p.days, v.checked_mul(7).ok_or(overflow)?),
+                (false, Some(b'D')) => (4, &mut p.days, v),
+                (true, Some(b'H')) => (5, &mut p.hours, v),
+                (true, Some(b'M')) => (6, &mut p.minutes, v),
+                (true, Some(b'S')) => (7, &mut p.seconds, v),
+                _ => return Err(Error::InvalidDuration),
+            };
+            // each designator at most once, in the order Y M W D T H M S
+            if rank <= last {
+                return Err(Error::InvalidDuration);
+            }
+            last = rank;
+            *field = field.checked_add(v).ok_or(overflow)?;
+            bytes = &bytes[1..];
+            components += 1;
+        }
+        if components == 0 || rest.ends_with('T') {
+            return Err(Error::InvalidDuration);
+        }
+        Ok(if negative { p.negate() } else { p })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use crate::{DateTime, Period};
+    use crate::error::{Error, Field};
+
+    #[test]
+    fn test_period_since() {
+        let start = DateTime::from_str("2020-01-31 10:00:00.000000").unwrap();
+        let end = DateTime::from_str("2023-03-01 09:30:15.000001").unwrap();
+        let p = end.period_since(start);
+        assert_eq!(p, Period { years: 3, months: 1, days: 0, hours: 23, minutes: 30, seconds: 15, micros: 1 });
+        assert_eq!(start.period_since(end), p.negate());
+        assert_eq!(p.checked_neg(), Some(p.negate()));
+        assert_eq!(Period { days: i32::MIN, ..Default::default() }.checked_neg(), None);
+        assert_eq!(start.period_since(start), Period::default());
+    }
+
+    #[test]
+    fn test_period_from_str() {
+        let p = Period::from_str("P1Y2M10DT2H30M").unwrap();
+        assert_eq!(p, Period { years: 1, months: 2, days: 10, hours: 2, minutes: 30, ..Default::default() });
+        assert_eq!(Period::from_str("P2W").unwrap(), Period { days: 14, ..Default::default() });
+        assert_eq!(Period::from_str("PT1.5S").unwrap(), Period { seconds: 1, micros: 500000, ..Default::default() });
+        assert_eq!(Period::from_str("-P1M").unwrap(), Period { months: -1, ..Default::default() });
+        assert_eq!(Period::from_str("P1M").unwrap(), Period::from_str("+P1M").unwrap());
+        assert_eq!(Period::from_str("P1Y2W3D").unwrap(), Period { years: 1, days: 17, ..Default::default() });
+        for bad in ["", "P", "PT", "P1", "P1H", "PT1D", "P1.5D", "PT1.1234567S", "1D", "P1YT", "P1D1D", "P1D1Y",
+            "P1M1Y", "P1D2W", "PT1M1H", "PT1S1S", "PT1HT1M", "P1DT1H1H"] {
+            assert_eq!(Period::from_str(bad).unwrap_err(), Error::InvalidDuration, "{}", bad);
+        }
+        assert_eq!(Period::from_str("P99999999999D").unwrap_err(), Error::OutOfRange { field: Field::Duration });
+    }
+}
@@ -0,0 +1,88 @@
+//! redis values. a `DateTime` is stored as an rfc 3339 string, wrap it in `EpochMillis`
+//! to store the integer milliseconds since the epoch instead, as a sorted set score.
+//! both are read from either form, a score comes back at utc
+use redis::{ErrorKind, FromRedisValue, NumericBehavior, RedisResult, RedisWrite, ToRedisArgs, Value};
+use std::str::FromStr;
+use crate::DateTime;
+
+/// a `DateTime` written as milliseconds since the epoch, rounded down
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct EpochMillis(pub DateTime);
+
+impl ToRedisArgs for DateTime {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        out.write_arg(self.to_rfc3339().as_bytes())
+    }
+}
+
+impl ToRedisArgs for EpochMillis {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        out.write_arg_fmt(self.0.unix_micros().div_euclid(1000))
+    }
+
+    fn describe_numeric_behavior(&self) -> NumericBehavior {
+        NumericBehavior::NumberIsInteger
+    }
+}
+
+fn from_millis(millis: i64) -> RedisResult<DateTime> {
+    DateTime::from_unix_micros(millis as i128 * 1000)
+        .ok_or_else(|| (ErrorKind::TypeError, "OutOfRangeTimestamp").into())
+}
+
+/// rfc 3339, or integer milliseconds as redis returns scores as strings
+fn from_text(s: &str) -> RedisResult<DateTime> {
+    if let Ok(millis) = s.parse::<i64>() {
+        return from_millis(millis);
+    }
+    DateTime::from_str(s).map_err(|e| (ErrorKind::TypeError, "invalid datetime", e.to_string()).into())
+}
+
+impl FromRedisValue for DateTime {
+    fn from_redis_value(v: &Value) -> RedisResult<Self> {
+        match v {
+            Value::Int(millis) => from_millis(*millis),
+            Value::Double(millis) => from_millis(*millis as i64),
+            Value::SimpleString(s) => from_text(s),
+            Value::BulkString(bytes) => match std::str::from_utf8(bytes) {
+                Ok(s) => from_text(s),
+                Err(_) => Err((ErrorKind::TypeError, "invalid utf-8 in datetime").into()),
+            },
+            _ => Err((ErrorKind::TypeError, "response is not a datetime", format!("{:?}", v)).into()),
+        }
+    }
+}
+
+impl FromRedisValue for EpochMillis {
+    fn from_redis_value(v: &Value) -> RedisResult<Self> {
+        DateTime::from_redis_value(v).map(EpochMillis)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use redis::{FromRedisValue, ToRedisArgs, Value};
+    use crate::DateTime;
+    use super::EpochMillis;
+
+    #[test]
+    fn test_redis_values() {
+        let d = DateTime::from_str("2023-08-20T13:02:03.123456+08:00").unwrap();
+        assert_eq!(d.to_redis_args(), vec![b"2023-08-20T13:02:03.123456+08:00".to_vec()]);
+        assert_eq!(EpochMillis(d).to_redis_args(), vec![b"1692507723123".to_vec()]);
+        let back = DateTime::from_redis_value(&Value::BulkString(d.to_redis_args().remove(0))).unwrap();
+        assert_eq!(back.to_rfc3339(), d.to_rfc3339());
+        let score = DateTime::from_redis_value(&Value::BulkString(b"1692507723123".to_vec())).unwrap();
+        assert_eq!(score.to_rfc3339(), "2023-08-20T05:02:03.123000Z");
+        assert_eq!(EpochMillis::from_redis_value(&Value::Int(1692507723123)).unwrap(), EpochMillis(score));
+        assert!(DateTime::from_redis_value(&Value::Nil).is_err());
+        assert!(DateTime::from_redis_value(&Value::SimpleString("2023-13-20T13:02:03Z".to_string())).is_err());
+    }
+}
@@ -0,0 +1,116 @@
+use core::iter::FusedIterator;
+use core::str::FromStr;
+use crate::{DateTime, Every, Period};
+use crate::error::Error;
+
+/// An ISO 8601 repeating interval, "R5/2024-01-01T00:00:00Z/P1D" or "R/2024-01-01T00:00:00Z/PT1H".
+///
+/// Iterates over the occurrences as `(start, end)` pairs, each end the start of the
+/// next one. `Rn` has `n` occurrences and `R` without a count has no end. The interval
+/// is a start and a duration or a start and an end, which then gives a fixed duration;
+/// a duration and an end are not supported, there would be no first occurrence.
+#[derive(Copy, Clone, Debug)]
+pub struct RepeatingInterval {
+    starts: Every,
+    remaining: Option<u32>,
+}
+
+impl RepeatingInterval {
+    /// the occurrences left, `None` for an unbounded `R/`
+    pub fn remaining(&self) -> Option<u32> {
+        self.remaining
+    }
+}
+
+impl FromStr for RepeatingInterval {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let invalid = Error::InvalidRepeatingInterval;
+        let mut parts = s.split('/');
+        let (Some(r), Some(start), Some(step), None) = (parts.next(), parts.next(), parts.next(), parts.next()) else {
+            return Err(invalid);
+        };
+        let remaining = match r.strip_prefix('R').ok_or(invalid)? {
+            "" | "-1" => None,
+            n if n.bytes().all(|b| b.is_ascii_digit()) => Some(n.parse().map_err(|_| invalid)?),
+            _ => return Err(invalid),
+        };
+        let start = DateTime::from_str(start)?;
+        let starts = if step.starts_with(['P', '-', '+']) {
+            let period = Period::from_str(step)?;
+            if period == Period::default() || start.checked_add_period(period).is_some_and(|next| next <= start) {
+                return Err(Error::InvalidDuration);
+            }
+            start.every_period(period)
+        } else {
+            let end = DateTime::from_str(step)?;
+            if end <= start {
+                return Err(invalid);
+            }
+            start.every((end - start).unsigned_abs())
+        };
+        Ok(Self { starts, remaining })
+    }
+}
+
+impl Iterator for RepeatingInterval {
+    type Item = (DateTime, DateTime);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == Some(0) {
+            return None;
+        }
+        let start = self.starts.next()?;
+        let end = { self.starts }.next()?;
+        self.remaining = self.remaining.map(|n| n - 1);
+        Some((start, end))
+    }
+}
+
+impl FusedIterator for RepeatingInterval {}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::{String, ToString};
+    use alloc::vec::Vec;
+    use core::str::FromStr;
+    use crate::RepeatingInterval;
+    use crate::error::Error;
+
+    fn occurrences(s: &str, n: usize) -> Vec<(String, String)> {
+        let r = RepeatingInterval::from_str(s).unwrap();
+        r.take(n).map(|(a, b)| (a.to_rfc3339(), b.to_rfc3339())).collect()
+    }
+
+    #[test]
+    fn test_repeating_interval() {
+        let v = occurrences("R5/2024-01-01T00:00:00Z/P1D", 10);
+        assert_eq!(v.len(), 5);
+        assert_eq!(v[0], ("2024-01-01T00:00:00.000000Z".to_string(), "2024-01-02T00:00:00.000000Z".to_string()));
+        assert_eq!(v[4].1, "2024-01-06T00:00:00.000000Z");
+        let v = occurrences("R/2024-01-31T09:00:00+08:00/P1M", 1000);
+        assert_eq!(v.len(), 1000);
+        assert_eq!(v[1].0, "2024-02-29T09:00:00.000000+08:00");
+        assert_eq!(v[2].0, "2024-03-31T09:00:00.000000+08:00");
+        let v = occurrences("R2/2024-01-01T00:00:00Z/2024-01-01T01:30:00Z", 10);
+        assert_eq!(v[1], ("2024-01-01T01:30:00.000000Z".to_string(), "2024-01-01T03:00:00.000000Z".to_string()));
+        assert_eq!(v.len(), 2);
+        assert_eq!(occurrences("R0/2024-01-01T00:00:00Z/PT1H", 10).len(), 0);
+        assert_eq!(RepeatingInterval::from_str("R3/2024-01-01T00:00:00Z/PT1H").unwrap().remaining(), Some(3));
+        assert_eq!(RepeatingInterval::from_str("R-1/2024-01-01T00:00:00Z/PT1H").unwrap().remaining(), None);
+        assert_eq!(occurrences("R/9999-12-31T00:00:00Z/PT12H", 10).len(), 1);
+        assert_eq!(occurrences("R3/2023-08-20T13:02:03Z/P212809538D", 10).len(), 0);
+    }
+
+    #[test]
+    fn test_repeating_interval_invalid() {
+        for bad in ["", "R5", "R5/2024-01-01T00:00:00Z", "5/2024-01-01T00:00:00Z/P1D", "Rx/2024-01-01T00:00:00Z/P1D",
+            "R5/2024-01-01T00:00:00Z/P1D/P1D", "R5/2024-01-01T00:00:00Z/2023-01-01T00:00:00Z", "R5/P1D/2024-01-01T00:00:00Z"] {
+            assert!(RepeatingInterval::from_str(bad).is_err(), "{}", bad);
+        }
+        assert_eq!(RepeatingInterval::from_str("R1/2024-01-01T00:00:00Z/PT0S").unwrap_err(), Error::InvalidDuration);
+        assert_eq!(RepeatingInterval::from_str("R1/2024-01-01T00:00:00Z/-P1D").unwrap_err(), Error::InvalidDuration);
+        assert_eq!(RepeatingInterval::from_str("R+1/2024-01-01T00:00:00Z/P1D").unwrap_err(), Error::InvalidRepeatingInterval);
+    }
+}
@@ -0,0 +1,353 @@
+//! adapters for `#[serde(with = "...")]` fields that are not RFC 3339 strings.
+//!
+//! ```ignore
+//! #[derive(Serialize, Deserialize)]
+//! struct Event {
+//!     #[serde(with = "fastdate::serde::ts_millis")]
+//!     at: DateTime,
+//! }
+//! ```
+//!
+//! epoch adapters write the instant as an integer and read it back at utc.
+//! for a custom string pattern see `serde_format!`
+use crate::DateTime;
+use crate::error::{Error, Field};
+
+#[doc(hidden)]
+pub use ::serde as __serde;
+
+/// whole units of `unit_micros` since the epoch, rounded down, negative before it
+fn to_epoch(dt: &DateTime, unit_micros: i64) -> i64 {
+    dt.unix_micros().div_euclid(unit_micros)
+}
+
+/// the utc datetime `n` units of `unit_micros` after the epoch, negative before it
+fn from_epoch(n: i64, unit_micros: i64) -> Result<DateTime, Error> {
+    DateTime::from_unix_micros(n as i128 * unit_micros as i128)
+        .ok_or(Error::OutOfRange { field: Field::Timestamp })
+}
+
+macro_rules! epoch_adapter {
+    ($name:ident, $unit_micros:expr, $doc:expr) => {
+        #[doc = $doc]
+        pub mod $name {
+            use ::serde::{Deserialize, Deserializer, Serializer};
+            use crate::DateTime;
+
+            pub fn serialize<S>(dt: &DateTime, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+                serializer.serialize_i64(super::to_epoch(dt, $unit_micros))
+            }
+
+            pub fn deserialize<'de, D>(deserializer: D) -> Result<DateTime, D::Error> where D: Deserializer<'de> {
+                use ::serde::de::Error;
+                let n = i64::deserialize(deserializer)?;
+                super::from_epoch(n, $unit_micros).map_err(D::Error::custom)
+            }
+        }
+    };
+}
+
+epoch_adapter!(ts_seconds, 1_000_000, "seconds since the epoch, `1692507723`");
+epoch_adapter!(ts_millis, 1_000, "milliseconds since the epoch, `1692507723123`");
+epoch_adapter!(ts_micros, 1, "microseconds since the epoch, `1692507723123456`");
+
+/// an RFC 3339 string or a number since the epoch, for feeds that mix both in one field.
+/// writes the RFC 3339 string, reads either at utc for numbers. the unit of an integer is
+/// told from its magnitude: below 10^11 seconds (up to the year 5138), below 10^14
+/// milliseconds, below 10^17 microseconds and nanoseconds above. a float is seconds with a
+/// fraction, a string of digits is read as the integer it spells
+pub mod flexible {
+    use core::fmt;
+    use core::str::FromStr;
+    use ::serde::de::{self, Visitor};
+    use ::serde::{Deserializer, Serializer};
+    use crate::{DateTime, PrecisionLoss, TimestampUnit};
+
+    pub fn serialize<S>(dt: &DateTime, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        serializer.serialize_str(&dt.to_rfc3339())
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<DateTime, D::Error> where D: Deserializer<'de> {
+        deserializer.deserialize_any(FlexibleVisitor)
+    }
+
+    /// the datetime of an integer in the unit its magnitude suggests
+    fn from_integer<E: de::Error>(n: i128) -> Result<DateTime, E> {
+        let unit = match n.unsigned_abs() {
+            0..100_000_000_000 => TimestampUnit::Seconds,
+            100_000_000_000..100_000_000_000_000 => TimestampUnit::Millis,
+            100_000_000_000_000..100_000_000_000_000_000 => TimestampUnit::Micros,
+            _ => TimestampUnit::Nanos,
+        };
+        let n = i64::try_from(n).map_err(|_| E::custom("OutOfRangeTimestamp"))?;
+        DateTime::from_timestamp_with(n, unit, PrecisionLoss::Truncate).map_err(E::custom)
+    }
+
+    struct FlexibleVisitor;
+
+    impl Visitor<'_> for FlexibleVisitor {
+        type Value = DateTime;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("an RFC 3339 string or a number since the epoch")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<DateTime, E> {
+            match v.parse::<i64>() {
+                Ok(n) => from_integer(n as i128),
+                Err(_) => DateTime::from_str(v).map_err(E::custom),
+            }
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> Result<DateTime, E> {
+            from_integer(v as i128)
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<DateTime, E> {
+            from_integer(v as i128)
+        }
+
+        fn visit_f64<E: de::Error>(self, v: f64) -> Result<DateTime, E> {
+            if !v.is_finite() {
+                return Err(E::custom("OutOfRangeTimestamp"));
+            }
+            // rounded half away from zero without std, saturates far out of range,
+            // which from_unix_micros rejects
+            let micros = v * 1e6;
+            let micros = if micros < 0.0 { micros - 0.5 } else { micros + 0.5 } as i128;
+            DateTime::from_unix_micros(micros).ok_or_else(|| E::custom("OutOfRangeTimestamp"))
+        }
+    }
+}
+
+/// a strftime-style pattern, see `DateTime::format`. serde can not pass arguments
+/// to a `with` module, so `serde_format!` generates one calling these with its pattern
+pub mod format {
+    use alloc::string::String;
+    use ::serde::{Deserialize, Deserializer, Serializer};
+    use crate::DateTime;
+
+    pub fn serialize<S>(dt: &DateTime, fmt: &str, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        serializer.serialize_str(&dt.format(fmt))
+    }
+
+    pub fn deserialize<'de, D>(fmt: &str, deserializer: D) -> Result<DateTime, D::Error> where D: Deserializer<'de> {
+        use ::serde::de::Error;
+        let s = String::deserialize(deserializer)?;
+        DateTime::parse_from_str(&s, fmt).map_err(D::Error::custom)
+    }
+}
+
+/// cbor datetimes of rfc 8949, for the `cbor` feature with ciborium.
+/// reading also accepts the untagged value
+#[cfg(feature = "cbor")]
+pub mod cbor {
+    use core::fmt;
+    use core::str::FromStr;
+    use alloc::string::String;
+    use ::ciborium::tag::{Accepted, Required};
+    use ::serde::de::{self, Visitor};
+    use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use crate::DateTime;
+
+    /// tag 0, an rfc 3339 string
+    pub mod rfc3339 {
+        use super::*;
+
+        pub fn serialize<S>(dt: &DateTime, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+            Required::<String, 0>(dt.to_rfc3339()).serialize(serializer)
+        }
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<DateTime, D::Error> where D: Deserializer<'de> {
+            let Accepted(s) = Accepted::<String, 0>::deserialize(deserializer)?;
+            DateTime::from_str(&s).map_err(de::Error::custom)
+        }
+    }
+
+    /// tag 1, seconds since the epoch. an integer for whole seconds, otherwise a float,
+    /// read back at utc
+    pub mod epoch {
+        use super::*;
+
+        pub fn serialize<S>(dt: &DateTime, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+            let micros = dt.unix_micros();
+            if micros % 1_000_000 == 0 {
+                Required::<i64, 1>(micros / 1_000_000).serialize(serializer)
+            } else {
+                Required::<f64, 1>(micros as f64 / 1e6).serialize(serializer)
+            }
+        }
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<DateTime, D::Error> where D: Deserializer<'de> {
+            let Accepted(Micros(micros)) = Accepted::<Micros, 1>::deserialize(deserializer)?;
+            DateTime::from_unix_micros(micros).ok_or_else(|| de::Error::custom("OutOfRangeTimestamp"))
+        }
+    }
+
+    /// microseconds read from integer or float seconds
+    struct Micros(i128);
+
+    impl<'de> Deserialize<'de> for Micros {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de> {
+            struct SecondsVisitor;
+
+            impl Visitor<'_> for SecondsVisitor {
+                type Value = Micros;
+
+                fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    f.write_str("seconds since the epoch")
+                }
+
+                fn visit_i64<E: de::Error>(self, v: i64) -> Result<Micros, E> {
+                    Ok(Micros(v as i128 * 1_000_000))
+                }
+
+                fn visit_u64<E: de::Error>(self, v: u64) -> Result<Micros, E> {
+                    Ok(Micros(v as i128 * 1_000_000))
+                }
+
+                fn visit_f64<E: de::Error>(self, v: f64) -> Result<Micros, E> {
+                    if !v.is_finite() {
+                        return Err(E::custom("OutOfRangeTimestamp"));
+                    }
+                    // saturates far out of range, which from_unix_micros rejects
+                    Ok(Micros((v * 1e6).round() as i128))
+                }
+            }
+
+            deserializer.deserialize_any(SecondsVisitor)
+        }
+    }
+}
+
+/// generate a module usable with `#[serde(with = "...")]` for a strftime-style pattern literal
+///
+/// ```ignore
+/// fastdate::serde_format!(pub day_first, "%d/%m/%Y %H:%M");
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Row {
+///     #[serde(with = "day_first")]
+///     at: DateTime,
+/// }
+/// ```
+#[macro_export]
+macro_rules! serde_format {
+    ($vis:vis $name:ident, $fmt:literal) => {
+        $vis mod $name {
+            pub fn serialize<S>(dt: &$crate::DateTime, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: $crate::serde::__serde::Serializer,
+            {
+                $crate::serde::format::serialize(dt, $fmt, serializer)
+            }
+
+            pub fn deserialize<'de, D>(deserializer: D) -> Result<$crate::DateTime, D::Error>
+            where
+                D: $crate::serde::__serde::Deserializer<'de>,
+            {
+                $crate::serde::format::deserialize($fmt, deserializer)
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use crate::DateTime;
+
+    crate::serde_format!(day_first, "%d/%m/%Y %H:%M");
+
+    #[derive(Debug, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+    struct Event {
+        #[serde(with = "crate::serde::ts_seconds")]
+        secs: DateTime,
+        #[serde(with = "crate::serde::ts_millis")]
+        millis: DateTime,
+        #[serde(with = "crate::serde::ts_micros")]
+        micros: DateTime,
+        #[serde(with = "day_first")]
+        custom: DateTime,
+    }
+
+    #[test]
+    #[cfg(feature = "cbor")]
+    fn test_serde_cbor_tags() {
+        #[derive(Debug, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+        struct Reading {
+            #[serde(with = "crate::serde::cbor::rfc3339")]
+            text: DateTime,
+            #[serde(with = "crate::serde::cbor::epoch")]
+            epoch: DateTime,
+        }
+        fn to_cbor<T: ::serde::Serialize>(v: &T) -> Vec<u8> {
+            let mut buf = Vec::new();
+            ciborium::into_writer(v, &mut buf).unwrap();
+            buf
+        }
+        let d = DateTime::from_str("2013-03-21T20:04:00Z").unwrap();
+        let bytes = to_cbor(&Reading { text: d, epoch: d });
+        // 0("2013-03-21T20:04:00.000000Z") and, as in rfc 8949 appendix a, 1(1363896240)
+        let text = [b"\xc0\x78\x1b".as_slice(), b"2013-03-21T20:04:00.000000Z"].concat();
+        assert!(bytes.windows(text.len()).any(|w| w == text));
+        assert!(bytes.ends_with(b"\xc1\x1a\x51\x4b\x67\xb0"));
+        let back: Reading = ciborium::from_reader(&bytes[..]).unwrap();
+        assert_eq!(back, Reading { text: d, epoch: d });
+        let frac = DateTime::from_str("2013-03-21T20:04:00.5Z").unwrap();
+        let bytes = to_cbor(&Reading { text: frac, epoch: frac });
+        // 1(1363896240.5)
+        assert!(bytes.ends_with(b"\xc1\xfb\x41\xd4\x52\xd9\xec\x20\x00\x00"));
+        let back: Reading = ciborium::from_reader(&bytes[..]).unwrap();
+        assert_eq!(back.epoch, frac);
+    }
+
+    #[test]
+    fn test_serde_flexible() {
+        #[derive(Debug, ::serde::Serialize, ::serde::Deserialize)]
+        struct Feed {
+            #[serde(with = "crate::serde::flexible")]
+            at: DateTime,
+        }
+        let read = |json: &str| serde_json::from_str::<Feed>(json).map(|f| f.at.to_rfc3339());
+        let expect = "2023-08-20T05:02:03.000000Z";
+        assert_eq!(read(r#"{"at":"2023-08-20T13:02:03+08:00"}"#).unwrap(), "2023-08-20T13:02:03.000000+08:00");
+        assert_eq!(read(r#"{"at":1692507723}"#).unwrap(), expect);
+        assert_eq!(read(r#"{"at":"1692507723"}"#).unwrap(), expect);
+        assert_eq!(read(r#"{"at":1692507723123}"#).unwrap(), "2023-08-20T05:02:03.123000Z");
+        assert_eq!(read(r#"{"at":1692507723123456}"#).unwrap(), "2023-08-20T05:02:03.123456Z");
+        assert_eq!(read(r#"{"at":1692507723123456789}"#).unwrap(), "2023-08-20T05:02:03.123456Z");
+        assert_eq!(read(r#"{"at":1692507723.5}"#).unwrap(), "2023-08-20T05:02:03.500000Z");
+        assert_eq!(read(r#"{"at":-1}"#).unwrap(), "1969-12-31T23:59:59.000000Z");
+        assert_eq!(read(r#"{"at":0}"#).unwrap(), "1970-01-01T00:00:00.000000Z");
+        assert!(read(r#"{"at":"yesterday"}"#).is_err());
+        assert!(read(r#"{"at":true}"#).is_err());
+        assert!(read(r#"{"at":18446744073709551615}"#).is_err());
+        let d = DateTime::from_str("2023-08-20T13:02:03.123456+08:00").unwrap();
+        assert_eq!(serde_json::to_string(&Feed { at: d }).unwrap(), r#"{"at":"2023-08-20T13:02:03.123456+08:00"}"#);
+    }
+
+    #[test]
+    fn test_serde_adapters() {
+        let d = DateTime::from_str("2023-08-20T13:02:03.123456+08:00").unwrap();
+        let utc = DateTime::from_str("2023-08-20T05:02:03.123456Z").unwrap();
+        let e = Event { secs: d, millis: d, micros: d, custom: d };
+        let json = serde_json::to_string(&e).unwrap();
+        assert_eq!(
+            json,
+            r#"{"secs":1692507723,"millis":1692507723123,"micros":1692507723123456,"custom":"20/08/2023 13:02"}"#
+        );
+        let back: Event = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.micros, utc);
+        assert_eq!(back.millis.to_string(), "2023-08-20 05:02:03.123000");
+        assert_eq!(back.secs.to_string(), "2023-08-20 05:02:03.000000");
+        assert_eq!(back.custom.to_string(), "2023-08-20 13:02:00.000000");
+        let before = r#"{"secs":-1,"millis":-1,"micros":0,"custom":"20/08/2023 13:02"}"#;
+        let back: Event = serde_json::from_str(before).unwrap();
+        assert_eq!(back.secs.to_string(), "1969-12-31 23:59:59.000000");
+        assert_eq!(back.millis.to_string(), "1969-12-31 23:59:59.999000");
+        assert_eq!(serde_json::to_string(&back).unwrap(), before);
+        let bad = r#"{"secs":-62135596801,"millis":0,"micros":0,"custom":"20/08/2023 13:02"}"#;
+        assert!(serde_json::from_str::<Event>(bad).is_err());
+    }
+}
@@ -1,3 +1,5 @@
+#[cfg(all(feature = "wasm", target_arch = "wasm32", target_os = "unknown"))]
+use std::time::Duration;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 #[cfg(any(target_arch = "wasm32", target_env = "sgx"))]
@@ -12,6 +14,88 @@ mod inner;
 #[path = "windows.rs"]
 mod inner;
 
+/// The current time, asking the javascript host where `SystemTime::now()` panics.
+#[cfg(all(feature = "wasm", target_arch = "wasm32", target_os = "unknown"))]
+pub fn system_now() -> SystemTime {
+    // performance.timeOrigin + performance.now() is sub-millisecond,
+    // Date.now() is the fallback for hosts without a performance object
+    let millis = performance_millis().unwrap_or_else(js_sys::Date::now);
+    let d = Duration::from_secs_f64(millis.abs() / 1000.0);
+    if millis >= 0.0 { UNIX_EPOCH + d } else { UNIX_EPOCH - d }
+}
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32", target_os = "unknown"))]
+fn performance_millis() -> Option<f64> {
+    use js_sys::{Function, Reflect};
+    use wasm_bindgen::{JsCast, JsValue};
+    let perf = Reflect::get(&js_sys::global(), &JsValue::from_str("performance")).ok()?;
+    let origin = Reflect::get(&perf, &JsValue::from_str("timeOrigin")).ok()?.as_f64()?;
+    let now = Reflect::get(&perf, &JsValue::from_str("now")).ok()?.dyn_into::<Function>().ok()?;
+    Some(origin + now.call0(&perf).ok()?.as_f64()?)
+}
+
+/// The current time.
+#[cfg(not(all(feature = "wasm", target_arch = "wasm32", target_os = "unknown")))]
+pub fn system_now() -> SystemTime {
+    SystemTime::now()
+}
+
+/// Nanoseconds since the epoch of the clock `source` names, the system clock where the
+/// platform does not have it.
+pub fn unix_nanos(source: crate::ClockSource) -> i128 {
+    inner::unix_nanos(source).unwrap_or_else(|| match system_now().duration_since(UNIX_EPOCH) {
+        Ok(d) => d.as_nanos() as i128,
+        Err(e) => -(e.duration().as_nanos() as i128),
+    })
+}
+
+/// Nanoseconds of a monotonic clock from an unspecified start, read without a syscall.
+/// It may only advance once per scheduler tick, every few milliseconds.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn coarse_monotonic_nanos() -> u64 {
+    let mut ts = libc::timespec { tv_sec: 0, tv_nsec: 0 };
+    unsafe { libc::clock_gettime(libc::CLOCK_MONOTONIC_COARSE, &mut ts) };
+    ts.tv_sec as u64 * 1_000_000_000 + ts.tv_nsec as u64
+}
+
+/// Nanoseconds of the system clock, `Instant` panics on wasm32-unknown-unknown.
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+pub fn coarse_monotonic_nanos() -> u64 {
+    system_now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_nanos() as u64)
+}
+
+/// Nanoseconds of a monotonic clock from an unspecified start.
+#[cfg(not(any(target_os = "linux", target_os = "android", all(target_arch = "wasm32", target_os = "unknown"))))]
+pub fn coarse_monotonic_nanos() -> u64 {
+    use std::sync::OnceLock;
+    use std::time::Instant;
+    static START: OnceLock<Instant> = OnceLock::new();
+    START.get_or_init(Instant::now).elapsed().as_nanos() as u64
+}
+
+/// Microseconds since the epoch of the system clock at the first call, advanced by a
+/// monotonic clock since, so it never jumps when the system clock is set or slewed.
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub fn monotonic_unix_micros() -> i64 {
+    use std::sync::OnceLock;
+    use std::time::Instant;
+    static ANCHOR: OnceLock<(Instant, i64)> = OnceLock::new();
+    let (start, micros) = ANCHOR.get_or_init(|| {
+        let since = system_now().duration_since(UNIX_EPOCH);
+        let micros = since.map_or_else(|e| -(e.duration().as_micros() as i64), |d| d.as_micros() as i64);
+        (Instant::now(), micros)
+    });
+    micros + start.elapsed().as_micros() as i64
+}
+
+/// Microseconds since the epoch of the system clock, `Instant` panics on wasm32-unknown-unknown
+/// and `performance.now()` of the host is monotonic already.
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+pub fn monotonic_unix_micros() -> i64 {
+    let since = system_now().duration_since(UNIX_EPOCH);
+    since.map_or_else(|e| -(e.duration().as_micros() as i64), |d| d.as_micros() as i64)
+}
+
 /// A record specifying a time value in seconds and nanoseconds, where
 /// nanoseconds represent the offset from the given second.
 ///
@@ -25,9 +109,7 @@ pub struct Timespec {
 impl Timespec {
     /// Constructs a timespec representing the current time in UTC.
     pub fn now() -> Timespec {
-        let st =
-            SystemTime::now().duration_since(UNIX_EPOCH).expect("system time before Unix epoch");
-        Timespec { sec: st.as_secs() as i64, nsec: st.subsec_nanos() as i32 }
+        Timespec::from(system_now())
     }
 
     /// Converts this timespec into the system's local time.
@@ -51,6 +133,23 @@ impl Timespec {
     }
 }
 
+impl From<SystemTime> for Timespec {
+    /// a clock set before the epoch gives a negative `sec`, `nsec` still counts forward from it
+    fn from(t: SystemTime) -> Timespec {
+        match t.duration_since(UNIX_EPOCH) {
+            Ok(d) => Timespec { sec: d.as_secs() as i64, nsec: d.subsec_nanos() as i32 },
+            Err(e) => {
+                let d = e.duration();
+                let sec = -(d.as_secs() as i64);
+                match d.subsec_nanos() {
+                    0 => Timespec { sec, nsec: 0 },
+                    n => Timespec { sec: sec - 1, nsec: (1_000_000_000 - n) as i32 },
+                }
+            }
+        }
+    }
+}
+
 /// Holds a calendar date and time broken down into its components (year, month,
 /// day, and so on), also called a broken-down time value.
 // FIXME: use c_int instead of i32?
@@ -104,6 +203,6 @@ impl Tm {
             0 => inner::utc_tm_to_time(self),
             _ => inner::local_tm_to_time(self),
         };
-        Timespec { sec: sec, nsec: self.tm_nsec }
+        Timespec { sec, nsec: self.tm_nsec }
     }
 }
\ No newline at end of file
@@ -18,7 +18,7 @@ fn time_to_tm(ts: i64, tm: &mut Tm) {
         [31, 29, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31],
     ];
 
-    let mut year = 1970;
+    let mut year: i64 = 1970;
 
     let dayclock = ts % 86400;
     let mut dayno = ts / 86400;
@@ -65,11 +65,21 @@ fn tm_to_time(tm: &Tm) -> i64 {
         + s
 }
 
+#[cfg(not(all(feature = "wasm", target_arch = "wasm32", target_os = "unknown")))]
 pub fn time_to_local_tm(sec: i64, tm: &mut Tm) {
     // FIXME: Add timezone logic
     time_to_tm(sec, tm);
 }
 
+#[cfg(all(feature = "wasm", target_arch = "wasm32", target_os = "unknown"))]
+pub fn time_to_local_tm(sec: i64, tm: &mut Tm) {
+    // the javascript host knows the zone, as minutes west of utc at that instant
+    let date = js_sys::Date::new(&((sec * 1000) as f64).into());
+    let offset = -(date.get_timezone_offset() * 60.0) as i64;
+    time_to_tm(sec + offset, tm);
+    tm.tm_utcoff = offset as i32;
+}
+
 pub fn utc_tm_to_time(tm: &Tm) -> i64 {
     tm_to_time(tm)
 }
@@ -78,3 +88,8 @@ pub fn local_tm_to_time(tm: &Tm) -> i64 {
     // FIXME: Add timezone logic
     tm_to_time(tm)
 }
+
+/// only the system clock, which is the javascript one on wasm32-unknown-unknown
+pub fn unix_nanos(_source: crate::ClockSource) -> Option<i128> {
+    None
+}
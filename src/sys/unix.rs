@@ -52,7 +52,7 @@ fn tm_to_rust_tm(tm: &libc::tm, utcoff: i32, rust_tm: &mut Tm) {
     rust_tm.tm_utcoff = utcoff;
 }
 
-#[cfg(any(target_os = "nacl", target_os = "solaris", target_os = "illumos"))]
+#[cfg(any(target_os = "solaris", target_os = "illumos"))]
 unsafe fn timegm(tm: *mut libc::tm) -> time_t {
     use std::env::{remove_var, set_var, var_os};
     extern "C" {
@@ -106,7 +106,6 @@ pub fn time_to_local_tm(sec: i64, tm: &mut Tm) {
 pub fn utc_tm_to_time(rust_tm: &Tm) -> i64 {
     #[cfg(not(any(
     all(target_os = "android", target_pointer_width = "32"),
-    target_os = "nacl",
     target_os = "solaris",
     target_os = "illumos"
     )))]
@@ -124,3 +123,20 @@ pub fn local_tm_to_time(rust_tm: &Tm) -> i64 {
     rust_tm_to_tm(rust_tm, &mut tm);
     unsafe { libc::mktime(&mut tm) as i64 }
 }
+
+/// `CLOCK_REALTIME`, or `CLOCK_REALTIME_COARSE` where linux has it
+pub fn unix_nanos(source: crate::ClockSource) -> Option<i128> {
+    let id = match source {
+        crate::ClockSource::System | crate::ClockSource::Precise => return None,
+        crate::ClockSource::Realtime => libc::CLOCK_REALTIME,
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        crate::ClockSource::RealtimeCoarse => libc::CLOCK_REALTIME_COARSE,
+        #[cfg(not(any(target_os = "linux", target_os = "android")))]
+        crate::ClockSource::RealtimeCoarse => libc::CLOCK_REALTIME,
+    };
+    let mut ts = libc::timespec { tv_sec: 0, tv_nsec: 0 };
+    if unsafe { libc::clock_gettime(id, &mut ts) } != 0 {
+        return None;
+    }
+    Some(ts.tv_sec as i128 * 1_000_000_000 + ts.tv_nsec as i128)
+}
@@ -129,3 +129,20 @@ pub fn local_tm_to_time(tm: &Tm) -> i64 {
         file_time_to_unix_seconds(&ft)
     }
 }
+
+extern "system" {
+    fn GetSystemTimeAsFileTime(ft: *mut FILETIME);
+    fn GetSystemTimePreciseAsFileTime(ft: *mut FILETIME);
+}
+
+/// `GetSystemTimePreciseAsFileTime`, or the coarse `GetSystemTimeAsFileTime` that only
+/// advances with the system tick
+pub fn unix_nanos(source: crate::ClockSource) -> Option<i128> {
+    let mut ft: FILETIME = unsafe { mem::zeroed() };
+    match source {
+        crate::ClockSource::System => return None,
+        crate::ClockSource::Precise | crate::ClockSource::Realtime => unsafe { GetSystemTimePreciseAsFileTime(&mut ft) },
+        crate::ClockSource::RealtimeCoarse => unsafe { GetSystemTimeAsFileTime(&mut ft) },
+    }
+    Some((file_time_as_u64(&ft) as i64 - HECTONANOSEC_TO_UNIX_EPOCH) as i128 * 100)
+}
@@ -1,9 +1,15 @@
-use std::fmt::{Display, Formatter, Write};
-use std::str::FromStr;
-use crate::{DateTime, get_digit, get_digit_unchecked};
-use crate::error::Error;
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "serde")]
+use alloc::string::{String, ToString};
+use crate::{DateTime, const_digits, get_digit};
+use crate::digits::{put2, put6};
+use crate::error::{Error, Field};
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize), rkyv(compare(PartialEq), derive(Debug), bytecheck(verify)))]
 pub struct Time {
     /// 0...999999
     pub micro: u32,
@@ -16,44 +22,163 @@ pub struct Time {
 }
 
 impl Time{
+    /// midnight, 00:00:00.000000
+    pub const MIDNIGHT: Time = Time {
+        micro: 0,
+        sec: 0,
+        min: 0,
+        hour: 0,
+    };
+
+    /// the time `hour`:`min`:`sec`.`micro`, `None` if a field is out of range.
+    /// usable in consts: `const T: Time = Time::new(9, 30, 0, 0).unwrap();`
+    pub const fn new(hour: u8, min: u8, sec: u8, micro: u32) -> Option<Self> {
+        if hour > 23 || min > 59 || sec > 59 || micro > 999999 {
+            return None;
+        }
+        Some(Self { micro, sec, min, hour })
+    }
+
+    /// the time `hour`:`min`:`sec`.`micro` as `new`, with an error naming the field out of range
+    pub fn try_new(hour: u8, min: u8, sec: u8, micro: u32) -> Result<Self, Error> {
+        if hour > 23 {
+            return Err(Error::OutOfRange { field: Field::Hour });
+        }
+        if min > 59 {
+            return Err(Error::OutOfRange { field: Field::Minute });
+        }
+        if sec > 59 {
+            return Err(Error::OutOfRange { field: Field::Second });
+        }
+        if micro > 999999 {
+            return Err(Error::OutOfRange { field: Field::Micro });
+        }
+        Ok(Self { micro, sec, min, hour })
+    }
+
+    /// parse exactly "HH:MM:SS" with an optional fraction of 1 to 9 digits after `.` or `,`,
+    /// truncated to microseconds, in a const context, `None` if `s` is anything else
+    pub const fn parse_const(s: &str) -> Option<Self> {
+        let bytes = s.as_bytes();
+        match Self::parse_const_partial(bytes, 0) {
+            Some((t, len)) if len == bytes.len() => Some(t),
+            _ => None,
+        }
+    }
+
+    /// the time starting at `offset` and its length, as `parse_bytes_partial` in a const fn
+    /// except that the seconds are required
+    pub(crate) const fn parse_const_partial(bytes: &[u8], offset: usize) -> Option<(Self, usize)> {
+        if bytes.len() < offset + 8 || bytes[offset + 2] != b':' || bytes[offset + 5] != b':' {
+            return None;
+        }
+        let (hour, min, sec) = match (
+            const_digits(bytes, offset, 2),
+            const_digits(bytes, offset + 3, 2),
+            const_digits(bytes, offset + 6, 2),
+        ) {
+            (Some(hour), Some(min), Some(sec)) => (hour, min, sec),
+            _ => return None,
+        };
+        let mut length = 8;
+        let mut micro = 0;
+        if offset + 8 < bytes.len() && (bytes[offset + 8] == b'.' || bytes[offset + 8] == b',') {
+            let mut n = 0;
+            while offset + 9 + n < bytes.len() && bytes[offset + 9 + n].is_ascii_digit() {
+                if n == 9 {
+                    return None;
+                }
+                if n < 6 {
+                    micro = micro * 10 + (bytes[offset + 9 + n] - b'0') as u32;
+                }
+                n += 1;
+            }
+            if n == 0 {
+                return None;
+            }
+            length = 9 + n;
+            while n < 6 {
+                micro *= 10;
+                n += 1;
+            }
+        }
+        match Self::new(hour as u8, min as u8, sec as u8, micro) {
+            Some(t) => Some((t, length)),
+            None => None,
+        }
+    }
+
+    /// parse "HH:MM:SS" with an optional fraction from trusted input such as our own output,
+    /// skipping the digit and range checks. only the length is checked, the fields of invalid
+    /// input are unspecified
+    pub fn parse_unchecked(s: &str) -> Result<Self, Error> {
+        let bytes = s.as_bytes();
+        if bytes.len() < 8 {
+            return Err(Error::TooShort);
+        }
+        Ok(Self::parse_unchecked_partial(bytes, 0).0)
+    }
+
+    /// the time starting at `offset` and its length, the 8 bytes of "HH:MM:SS" must exist.
+    /// fraction digits past the sixth are skipped
+    pub(crate) fn parse_unchecked_partial(bytes: &[u8], offset: usize) -> (Self, usize) {
+        let d = |i: usize| bytes[offset + i].wrapping_sub(b'0');
+        let mut micro: u32 = 0;
+        let mut length = 8;
+        if matches!(bytes.get(offset + 8), Some(b'.') | Some(b',')) {
+            length = 9;
+            let mut scale = 100_000;
+            while let Some(c) = bytes.get(offset + length).filter(|c| c.is_ascii_digit()) {
+                micro += (c - b'0') as u32 * scale;
+                scale /= 10;
+                length += 1;
+            }
+        }
+        let t = Self {
+            micro,
+            sec: d(6).wrapping_mul(10).wrapping_add(d(7)),
+            min: d(3).wrapping_mul(10).wrapping_add(d(4)),
+            hour: d(0).wrapping_mul(10).wrapping_add(d(1)),
+        };
+        (t, length)
+    }
+
     /// Parse a time from bytes with a starting index, no check is performed for extract characters at
     /// the end of the string
     pub(crate) fn parse_bytes_partial(bytes: &[u8], offset: usize) -> Result<(Self, usize), Error> {
-        if bytes.len() - offset < 5 {
-            return Err(Error::E("TooShort".to_string()));
-        }
-        let hour: u8;
-        let minute: u8;
-        unsafe {
-            let h1 = get_digit_unchecked!(bytes, offset, "InvalidCharHour");
-            let h2 = get_digit_unchecked!(bytes, offset + 1, "InvalidCharHour");
-            hour = h1 * 10 + h2;
-
-            match bytes.get_unchecked(offset + 2) {
-                b':' => (),
-                _ => return Err(Error::E("InvalidCharTimeSep".to_string())),
-            }
-            let m1 = get_digit_unchecked!(bytes, offset + 3, "InvalidCharMinute");
-            let m2 = get_digit_unchecked!(bytes, offset + 4, "InvalidCharMinute");
-            minute = m1 * 10 + m2;
+        let Some(&[h1, h2, sep, m1, m2]) = bytes.get(offset..offset + 5) else {
+            return Err(Error::TooShort);
+        };
+        if !(h1.is_ascii_digit() && h2.is_ascii_digit()) {
+            let pos = if h1.is_ascii_digit() { offset + 1 } else { offset };
+            return Err(Error::InvalidChar { field: Field::Hour, pos });
+        }
+        if sep != b':' {
+            return Err(Error::InvalidChar { field: Field::TimeSep, pos: offset + 2 });
         }
+        if !(m1.is_ascii_digit() && m2.is_ascii_digit()) {
+            let pos = if m1.is_ascii_digit() { offset + 4 } else { offset + 3 };
+            return Err(Error::InvalidChar { field: Field::Minute, pos });
+        }
+        let hour = (h1 - b'0') * 10 + (h2 - b'0');
+        let minute = (m1 - b'0') * 10 + (m2 - b'0');
 
         if hour > 23 {
-            return Err(Error::E("OutOfRangeHour".to_string()));
+            return Err(Error::OutOfRange { field: Field::Hour });
         }
 
         if minute > 59 {
-            return Err(Error::E("OutOfRangeMinute".to_string()));
+            return Err(Error::OutOfRange { field: Field::Minute });
         }
         let mut length: usize = 5;
 
         let (second, microsecond) = match bytes.get(offset + 5) {
             Some(b':') => {
-                let s1 = get_digit!(bytes, offset + 6, "InvalidCharSecond");
-                let s2 = get_digit!(bytes, offset + 7, "InvalidCharSecond");
+                let s1 = get_digit!(bytes, offset + 6, Field::Second);
+                let s2 = get_digit!(bytes, offset + 7, Field::Second);
                 let second = s1 * 10 + s2;
                 if second > 59 {
-                    return Err(Error::E("OutOfRangeSecond".to_string()));
+                    return Err(Error::OutOfRange { field: Field::Second });
                 }
                 length = 8;
 
@@ -62,23 +187,26 @@ impl Time{
                 if frac_sep == Some(b'.') || frac_sep == Some(b',') {
                     length = 9;
                     let mut i: usize = 0;
+                    // up to nanoseconds, the digits past microseconds are truncated
                     loop {
                         match bytes.get(offset + length + i) {
-                            Some(c) if (b'0'..=b'9').contains(c) => {
-                                microsecond *= 10;
-                                microsecond += (c - b'0') as u32;
+                            Some(c) if c.is_ascii_digit() => {
+                                if i < 6 {
+                                    microsecond *= 10;
+                                    microsecond += (c - b'0') as u32;
+                                }
                             }
                             _ => {
                                 break;
                             }
                         }
                         i += 1;
-                        if i > 6 {
-                            return Err(Error::E("SecondFractionTooLong".to_string()));
+                        if i > 9 {
+                            return Err(Error::SecondFractionTooLong);
                         }
                     }
                     if i == 0 {
-                        return Err(Error::E("SecondFractionMissing".to_string()));
+                        return Err(Error::SecondFractionMissing);
                     }
                     if i < 6 {
                         microsecond *= 10_u32.pow(6 - i as u32);
@@ -97,6 +225,82 @@ impl Time{
         };
         Ok((t, length))
     }
+
+    /// 0...23
+    pub const fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    /// 0...59
+    pub const fn minute(&self) -> u8 {
+        self.min
+    }
+
+    /// 0...59
+    pub const fn second(&self) -> u8 {
+        self.sec
+    }
+
+    /// 0...999999
+    pub const fn micro(&self) -> u32 {
+        self.micro
+    }
+
+    /// set the hour, failing if it is past 23
+    pub fn set_hour(&mut self, hour: u8) -> Result<(), Error> {
+        *self = self.with_hour(hour)?;
+        Ok(())
+    }
+
+    /// set the minute, failing if it is past 59
+    pub fn set_minute(&mut self, min: u8) -> Result<(), Error> {
+        *self = self.with_minute(min)?;
+        Ok(())
+    }
+
+    /// set the second, failing if it is past 59
+    pub fn set_second(&mut self, sec: u8) -> Result<(), Error> {
+        *self = self.with_second(sec)?;
+        Ok(())
+    }
+
+    /// set the microsecond, failing if it is past 999999
+    pub fn set_micro(&mut self, micro: u32) -> Result<(), Error> {
+        *self = self.with_micro(micro)?;
+        Ok(())
+    }
+
+    /// the same time with hour `hour` (0...23)
+    pub fn with_hour(self, hour: u8) -> Result<Self, Error> {
+        if hour > 23 {
+            return Err(Error::OutOfRange { field: Field::Hour });
+        }
+        Ok(Self { hour, ..self })
+    }
+
+    /// the same time with minute `min` (0...59)
+    pub fn with_minute(self, min: u8) -> Result<Self, Error> {
+        if min > 59 {
+            return Err(Error::OutOfRange { field: Field::Minute });
+        }
+        Ok(Self { min, ..self })
+    }
+
+    /// the same time with second `sec` (0...59)
+    pub fn with_second(self, sec: u8) -> Result<Self, Error> {
+        if sec > 59 {
+            return Err(Error::OutOfRange { field: Field::Second });
+        }
+        Ok(Self { sec, ..self })
+    }
+
+    /// the same time with microsecond `micro` (0...999999)
+    pub fn with_micro(self, micro: u32) -> Result<Self, Error> {
+        if micro > 999999 {
+            return Err(Error::OutOfRange { field: Field::Micro });
+        }
+        Ok(Self { micro, ..self })
+    }
 }
 
 impl From<DateTime> for Time{
@@ -122,26 +326,64 @@ impl FromStr for Time {
     }
 }
 
-impl Display for Time {
-    /// fmt RFC3339Micro = "2006-01-02T15:04:05.999999"
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+impl Time {
+    /// "HH:MM:SS.ffffff" as ascii bytes
+    pub(crate) fn to_ascii(self) -> [u8; 15] {
         let mut buf: [u8; 15] = *b"00:00:00.000000";
+        put2(&mut buf, 0, self.hour as u32);
+        put2(&mut buf, 3, self.min as u32);
+        put2(&mut buf, 6, self.sec as u32);
+        put6(&mut buf, 9, self.micro);
+        buf
+    }
 
-        buf[0] = b'0' + (self.hour / 10) as u8;
-        buf[1] = b'0' + (self.hour % 10) as u8;
-        buf[3] = b'0' + (self.min / 10) as u8;
-        buf[4] = b'0' + (self.min % 10) as u8;
-        buf[6] = b'0' + (self.sec / 10) as u8;
-        buf[7] = b'0' + (self.sec % 10) as u8;
+    /// `to_string` into an inline string, without allocating
+    #[cfg(feature = "arrayvec")]
+    pub fn to_array_string(&self) -> arrayvec::ArrayString<15> {
+        arrayvec::ArrayString::from_byte_string(&self.to_ascii()).unwrap()
+    }
+}
+
+impl Display for Time {
+    /// fmt RFC3339Micro = "2006-01-02T15:04:05.999999"
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        f.write_str(core::str::from_utf8(&self.to_ascii()).unwrap())
+    }
+}
 
-        buf[9] = b'0' + (self.micro / 100000 % 10) as u8;
-        buf[10] = b'0' + (self.micro / 10000 % 10) as u8;
-        buf[11] = b'0' + (self.micro / 1000 % 10) as u8;
-        buf[12] = b'0' + (self.micro / 100 % 10) as u8;
-        buf[13] = b'0' + (self.micro / 10 % 10) as u8;
-        buf[14] = b'0' + (self.micro % 10) as u8;
+/// serialized as an RFC 3339 partial-time string, "15:04:05.999999"
+#[cfg(feature = "serde")]
+impl Serialize for Time {
+    /// "HH:MM:SS.ffffff", or the `u64` microseconds since midnight for binary formats
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&self.to_string())
+        } else {
+            let secs = self.hour as u64 * 3600 + self.min as u64 * 60 + self.sec as u64;
+            serializer.serialize_u64(secs * 1_000_000 + self.micro as u64)
+        }
+    }
+}
 
-        f.write_str(std::str::from_utf8(&buf[..]).unwrap())
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Time {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de> {
+        use serde::de::Error;
+        if !deserializer.is_human_readable() {
+            let micros = u64::deserialize(deserializer)?;
+            if micros >= 86_400_000_000 {
+                return Err(D::Error::custom("OutOfRangeMicro"));
+            }
+            let secs = micros / 1_000_000;
+            return Ok(Time {
+                micro: (micros % 1_000_000) as u32,
+                sec: (secs % 60) as u8,
+                min: (secs / 60 % 60) as u8,
+                hour: (secs / 3600) as u8,
+            });
+        }
+        let s = String::deserialize(deserializer)?;
+        Time::from_str(&s).map_err(D::Error::custom)
     }
 }
 
@@ -158,12 +400,58 @@ mod tests {
 
         let d = Time::from_str("11:12:13.12345").unwrap();
         println!("{}", d);
-        assert_eq!("11:12:13.012345".to_string(), d.to_string());
+        assert_eq!("11:12:13.123450".to_string(), d.to_string());
 
         let d = Time::from_str("11:12:13.1234").unwrap();
         println!("{}", d);
-        assert_eq!("11:12:13.001234".to_string(), d.to_string());
+        assert_eq!("11:12:13.123400".to_string(), d.to_string());
     }
 
+    #[test]
+    fn test_time_with() {
+        let t = Time::from_str("11:12:13.123456").unwrap();
+        assert_eq!(t.with_hour(23).unwrap().to_string(), "23:12:13.123456");
+        assert_eq!(t.with_minute(0).unwrap().to_string(), "11:00:13.123456");
+        assert_eq!(t.with_second(59).unwrap().to_string(), "11:12:59.123456");
+        assert_eq!(t.with_micro(1).unwrap().to_string(), "11:12:13.000001");
+        assert!(t.with_hour(24).is_err());
+        assert!(t.with_minute(60).is_err());
+        assert!(t.with_second(60).is_err());
+        assert!(t.with_micro(1000000).is_err());
+    }
+
+    #[test]
+    fn test_time_const() {
+        const T: Time = Time::new(11, 12, 13, 123456).unwrap();
+        assert_eq!(T, Time::from_str("11:12:13.123456").unwrap());
+        assert_eq!(Time::parse_const("11:12:13.123456"), Some(T));
+        assert_eq!(Time::parse_const("11:12:13,5").unwrap().micro, 500000);
+        assert_eq!(Time::parse_const("11:12:13").unwrap(), Time::new(11, 12, 13, 0).unwrap());
+        assert_eq!(Time::new(24, 0, 0, 0), None);
+        assert_eq!(Time::new(0, 0, 0, 1000000), None);
+        assert_eq!(Time::parse_const("11:12:13."), None);
+        assert_eq!(Time::parse_const("11:12:13.123456789"), Some(T));
+        assert_eq!(Time::from_str("11:12:13.123456789").unwrap(), T);
+        assert_eq!(Time::parse_const("11:12:13.1234567890"), None);
+        assert_eq!(Time::parse_const("11:12"), None);
+        assert_eq!(Time::parse_const("11:60:00"), None);
+    }
+
+    #[test]
+    fn test_time_parse_unchecked() {
+        for v in ["00:00:00", "11:12:13.123456", "23:59:59,5"] {
+            assert_eq!(Time::parse_unchecked(v).unwrap(), Time::from_str(v).unwrap());
+        }
+        assert_eq!(Time::parse_unchecked("11:12:13.123456789").unwrap().micro, 123456);
+        let t = Time::parse_unchecked("25:61:99").unwrap();
+        assert_eq!((t.hour, t.min, t.sec), (25, 61, 99));
+        assert!(Time::parse_unchecked("11:12").is_err());
+    }
 
-}
\ No newline at end of file
+    #[test]
+    #[cfg(feature = "arrayvec")]
+    fn test_time_array_string() {
+        let t = Time::from_str("11:12:13.123456").unwrap();
+        assert_eq!(t.to_array_string().as_str(), t.to_string());
+    }
+}
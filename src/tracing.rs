@@ -0,0 +1,51 @@
+//! timers for `tracing_subscriber::fmt`, writing event timestamps with the formatter of `DateTime`.
+//!
+//! ```ignore
+//! tracing_subscriber::fmt().with_timer(fastdate::tracing::UtcTime).init();
+//! ```
+use std::fmt;
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::time::FormatTime;
+use crate::DateTime;
+
+/// rfc 3339 at utc, `2023-08-20T05:02:03.123456Z`, like the default `SystemTime` timer
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct UtcTime;
+
+impl FormatTime for UtcTime {
+    fn format_time(&self, w: &mut Writer<'_>) -> fmt::Result {
+        w.write_str(&DateTime::utc().to_rfc3339())
+    }
+}
+
+/// rfc 3339 in the local offset of `DateTime::now`, `2023-08-20T13:02:03.123456+08:00`
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct LocalTime;
+
+impl FormatTime for LocalTime {
+    fn format_time(&self, w: &mut Writer<'_>) -> fmt::Result {
+        w.write_str(&DateTime::now().to_rfc3339())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use tracing_subscriber::fmt::format::Writer;
+    use tracing_subscriber::fmt::time::FormatTime;
+    use crate::DateTime;
+    use super::{LocalTime, UtcTime};
+
+    #[test]
+    fn test_tracing_timers() {
+        let before = DateTime::utc();
+        let mut s = String::new();
+        UtcTime.format_time(&mut Writer::new(&mut s)).unwrap();
+        assert!(s.ends_with('Z'));
+        let at = DateTime::from_str(&s).unwrap();
+        assert!(at >= before && at <= DateTime::utc());
+        let mut s = String::new();
+        LocalTime.format_time(&mut Writer::new(&mut s)).unwrap();
+        assert!(DateTime::from_str(&s).unwrap() >= before);
+    }
+}
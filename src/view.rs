@@ -0,0 +1,131 @@
+use core::str::FromStr;
+use crate::DateTime;
+use crate::error::{Error, Field};
+
+/// A borrowed "YYYY-MM-DD" or "YYYY-MM-DD?HH:MM:SS..." that decodes its fields on access.
+///
+/// `new` only checks where the digits and separators are, so filtering millions of lines
+/// by year or month reads two or four digits per line instead of parsing a `DateTime`.
+/// The ranges are checked once converted with `to_datetime`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct DateTimeRef<'a> {
+    s: &'a str,
+}
+
+impl<'a> DateTimeRef<'a> {
+    /// view the date and, when `s` is longer than 10 bytes, the time at its start.
+    /// whatever follows the seconds, a fraction or an offset, is left to `to_datetime`
+    pub fn new(s: &'a str) -> Result<Self, Error> {
+        let bytes = s.as_bytes();
+        if bytes.len() < 10 {
+            return Err(Error::TooShort);
+        }
+        if let Some(pos) = mismatch(bytes, b"dddd-dd-dd") {
+            return Err(Error::InvalidChar { field: Field::Date, pos });
+        }
+        if bytes.len() > 10 {
+            if let Some(pos) = mismatch(&bytes[11..], b"dd:dd:dd") {
+                return Err(Error::InvalidChar { field: Field::Time, pos: 11 + pos });
+            }
+        }
+        Ok(Self { s })
+    }
+
+    /// 0...9999
+    pub fn year(&self) -> u16 {
+        self.num(0) as u16 * 100 + self.num(2) as u16
+    }
+
+    /// 0...99, 1...12 for a valid date
+    pub fn mon(&self) -> u8 {
+        self.num(5)
+    }
+
+    /// 0...99, 1...31 for a valid date
+    pub fn day(&self) -> u8 {
+        self.num(8)
+    }
+
+    /// 0 without a time
+    pub fn hour(&self) -> u8 {
+        self.time_num(11)
+    }
+
+    /// 0 without a time
+    pub fn min(&self) -> u8 {
+        self.time_num(14)
+    }
+
+    /// 0 without a time
+    pub fn sec(&self) -> u8 {
+        self.time_num(17)
+    }
+
+    /// `year * 100 + mon`, e.g. 202308, for range filters on the month
+    pub fn year_month(&self) -> u32 {
+        self.year() as u32 * 100 + self.mon() as u32
+    }
+
+    /// the viewed string
+    pub fn as_str(&self) -> &'a str {
+        self.s
+    }
+
+    /// parse and validate the whole view, as `DateTime::from_str`
+    pub fn to_datetime(&self) -> Result<DateTime, Error> {
+        DateTime::from_str(self.s)
+    }
+
+    /// the two digits at `i`
+    fn num(&self, i: usize) -> u8 {
+        let bytes = self.s.as_bytes();
+        (bytes[i] - b'0') * 10 + (bytes[i + 1] - b'0')
+    }
+
+    fn time_num(&self, i: usize) -> u8 {
+        if self.s.len() > 10 {
+            self.num(i)
+        } else {
+            0
+        }
+    }
+}
+
+/// the first byte of `bytes` that does not match `pattern`, where `d` is any ascii digit,
+/// or its length if it is shorter. `None` if `bytes` starts with `pattern`
+fn mismatch(bytes: &[u8], pattern: &[u8]) -> Option<usize> {
+    let pos = pattern
+        .iter()
+        .zip(bytes)
+        .position(|(p, b)| if *p == b'd' { !b.is_ascii_digit() } else { p != b });
+    match pos {
+        None if bytes.len() < pattern.len() => Some(bytes.len()),
+        pos => pos,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::DateTimeRef;
+
+    #[test]
+    fn test_datetime_ref() {
+        let v = DateTimeRef::new("2023-08-20T13:02:03.123456+08:00 GET /index.html").unwrap();
+        assert_eq!((v.year(), v.mon(), v.day()), (2023, 8, 20));
+        assert_eq!((v.hour(), v.min(), v.sec()), (13, 2, 3));
+        assert_eq!(v.year_month(), 202308);
+        let d = DateTimeRef::new("2023-08-20").unwrap();
+        assert_eq!((d.year(), d.hour()), (2023, 0));
+        assert_eq!(d.to_datetime().unwrap().to_string(), "2023-08-20 00:00:00.000000");
+        let t = DateTimeRef::new("2023-08-20 13:02:03.5Z").unwrap();
+        assert_eq!(t.to_datetime().unwrap().micro, 500000);
+        // the ranges are only checked by to_datetime
+        let bad = DateTimeRef::new("2023-13-40 25:00:00Z").unwrap();
+        assert_eq!((bad.mon(), bad.day(), bad.hour()), (13, 40, 25));
+        assert!(bad.to_datetime().is_err());
+        assert!(DateTimeRef::new("2023-08").is_err());
+        assert!(DateTimeRef::new("2023/08/20").is_err());
+        assert!(DateTimeRef::new("2023-08-20 13:02").is_err());
+        assert!(DateTimeRef::new("2023-08-20 13-02-03").is_err());
+    }
+}
@@ -0,0 +1,156 @@
+use core::fmt::{self, Display, Formatter};
+use core::str::FromStr;
+use crate::error::{Error, Field};
+
+pub(crate) const WEEKDAYS: [&str; 7] = ["Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday"];
+
+/// the index of the name in `names` that `s` is, or the 3 letter abbreviation of, in any case
+pub(crate) fn find_name(s: &str, names: &[&str]) -> Option<usize> {
+    names
+        .iter()
+        .position(|n| s.eq_ignore_ascii_case(n) || s.len() == 3 && s.eq_ignore_ascii_case(&n[..3]))
+}
+
+/// Day of the week, monday first as in ISO 8601
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl Weekday {
+    /// 0 = monday ... 6 = sunday
+    pub fn num_days_from_monday(self) -> u8 {
+        self as u8
+    }
+
+    /// 0 = sunday ... 6 = saturday
+    pub fn num_days_from_sunday(self) -> u8 {
+        (self as u8 + 1) % 7
+    }
+
+    /// the day `n` days after this one, `Monday.nth_next(9)` is a wednesday
+    pub fn nth_next(self, n: u32) -> Self {
+        Self::from_num_days_from_monday((self as u32 + n % 7) as u8)
+    }
+
+    /// 0 = monday ... 6 = sunday, wrapping past 6
+    pub(crate) fn from_num_days_from_monday(n: u8) -> Self {
+        match n % 7 {
+            0 => Weekday::Monday,
+            1 => Weekday::Tuesday,
+            2 => Weekday::Wednesday,
+            3 => Weekday::Thursday,
+            4 => Weekday::Friday,
+            5 => Weekday::Saturday,
+            _ => Weekday::Sunday,
+        }
+    }
+}
+
+impl Weekday {
+    /// the english name, e.g. "Monday"
+    pub fn name(self) -> &'static str {
+        WEEKDAYS[self as usize]
+    }
+}
+
+impl FromStr for Weekday {
+    type Err = Error;
+
+    /// the english name or its 3 letter abbreviation in any case, "mon" or "Monday"
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let i = find_name(s, &WEEKDAYS).ok_or(Error::InvalidChar { field: Field::Weekday, pos: 0 })?;
+        Ok(Weekday::from_num_days_from_monday(i as u8))
+    }
+}
+
+impl Display for Weekday {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The set of days that make up the weekend, see `Date::is_weekend_in`
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Weekend {
+    /// bit `n` is set when the day `n` days from monday is a weekend day
+    days: u8,
+}
+
+impl Weekend {
+    /// saturday and sunday, used by `is_weekend`
+    pub const SATURDAY_SUNDAY: Weekend = Weekend { days: 0b110_0000 };
+    /// friday and saturday, as in much of the middle east
+    pub const FRIDAY_SATURDAY: Weekend = Weekend { days: 0b011_0000 };
+    /// sunday only
+    pub const SUNDAY: Weekend = Weekend { days: 0b100_0000 };
+
+    /// a weekend made of `days`
+    pub fn from_days(days: &[Weekday]) -> Self {
+        Self {
+            days: days.iter().fold(0, |acc, d| acc | 1 << d.num_days_from_monday()),
+        }
+    }
+
+    pub fn contains(self, day: Weekday) -> bool {
+        self.days & 1 << day.num_days_from_monday() != 0
+    }
+}
+
+impl Default for Weekend {
+    fn default() -> Self {
+        Self::SATURDAY_SUNDAY
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+    use core::str::FromStr;
+    use crate::error::{Error, Field};
+    use crate::{Weekday, Weekend};
+
+    #[test]
+    fn test_weekday_num_days() {
+        assert_eq!(Weekday::Monday.num_days_from_monday(), 0);
+        assert_eq!(Weekday::Sunday.num_days_from_monday(), 6);
+        assert_eq!(Weekday::Sunday.num_days_from_sunday(), 0);
+        assert_eq!(Weekday::Saturday.num_days_from_sunday(), 6);
+        assert_eq!(Weekday::from_num_days_from_monday(9), Weekday::Wednesday);
+        assert_eq!(Weekday::Monday.nth_next(9), Weekday::Wednesday);
+        assert_eq!(Weekday::Sunday.nth_next(1), Weekday::Monday);
+        assert_eq!(Weekday::Friday.nth_next(0), Weekday::Friday);
+        assert_eq!(Weekday::Friday.nth_next(u32::MAX), Weekday::from_num_days_from_monday(((4 + u32::MAX as u64) % 7) as u8));
+    }
+
+    #[test]
+    fn test_weekday_names() {
+        assert_eq!(Weekday::from_str("mon").unwrap(), Weekday::Monday);
+        assert_eq!(Weekday::from_str("Monday").unwrap(), Weekday::Monday);
+        assert_eq!(Weekday::from_str("sUnDaY").unwrap(), Weekday::Sunday);
+        assert_eq!(Weekday::from_str("mond").unwrap_err(), Error::InvalidChar { field: Field::Weekday, pos: 0 });
+        assert!(Weekday::from_str("").is_err());
+        assert_eq!(Weekday::Wednesday.to_string(), "Wednesday");
+        for n in 0..7 {
+            let d = Weekday::from_num_days_from_monday(n);
+            assert_eq!(Weekday::from_str(&d.to_string()).unwrap(), d);
+            assert_eq!(Weekday::from_str(&d.name()[..3]).unwrap(), d);
+        }
+    }
+
+    #[test]
+    fn test_weekend() {
+        assert!(Weekend::SATURDAY_SUNDAY.contains(Weekday::Sunday));
+        assert!(!Weekend::SATURDAY_SUNDAY.contains(Weekday::Friday));
+        assert!(Weekend::FRIDAY_SATURDAY.contains(Weekday::Friday));
+        assert!(!Weekend::FRIDAY_SATURDAY.contains(Weekday::Sunday));
+        assert_eq!(Weekend::from_days(&[Weekday::Saturday, Weekday::Sunday]), Weekend::SATURDAY_SUNDAY);
+        assert_eq!(Weekend::from_days(&[Weekday::Sunday]), Weekend::SUNDAY);
+    }
+}
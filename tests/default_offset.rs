@@ -0,0 +1,28 @@
+//! `set_default_offset` changes the whole process, so it is tested in a test binary of its
+//! own and in one test, which no other test runs in parallel with
+#![cfg(feature = "std")]
+
+use std::str::FromStr;
+use fastdate::{clear_default_offset, default_offset, set_default_offset, Date, DateTime, Validation};
+
+#[test]
+fn test_default_offset() {
+    assert_eq!(default_offset(), None);
+    set_default_offset(3600).unwrap();
+    assert_eq!(default_offset(), Some(3600));
+    assert_eq!(DateTime::now().offset, 3600);
+    assert_eq!(Date::today(), Date::from(DateTime::now_utc().to_offset(3600).unwrap()));
+    let d = DateTime::from_str("2023-08-20 13:02:03").unwrap();
+    assert_eq!(d.to_rfc3339(), "2023-08-20T13:02:03.000000+01:00");
+    let d = DateTime::parse_with("2023-08-20", Validation::Relaxed).unwrap();
+    assert_eq!(d.to_rfc3339(), "2023-08-20T00:00:00.000000+01:00");
+    // an explicit offset and the per-call override win
+    assert_eq!(DateTime::from_str("2023-08-20T13:02:03Z").unwrap().offset, 0);
+    assert_eq!(DateTime::parse_in_offset("2023-08-20 13:02:03", -7200).unwrap().offset, -7200);
+    assert_eq!(DateTime::now_in_offset(-7200).unwrap().offset, -7200);
+
+    clear_default_offset();
+    assert_eq!(default_offset(), None);
+    assert_eq!(DateTime::from_str("2023-08-20 13:02:03").unwrap().offset, 0);
+    assert_eq!(DateTime::now().offset, DateTime::local_offset());
+}